@@ -0,0 +1,30 @@
+//! Detect the features of the compiler building the library
+use std::env;
+use std::ffi::OsString;
+use std::process::Command;
+
+fn main() {
+    println!("cargo:rustc-check-cfg=cfg(fmt_align)");
+    // `Formatter::align` is stable since Rust 1.28
+    if rustc_minor_version().map_or(false, |minor| minor >= 28) {
+        println!("cargo:rustc-cfg=fmt_align");
+    }
+}
+
+/// Get the minor version of the compiler, eg : 28 for `rustc 1.28.0`
+fn rustc_minor_version() -> Option<u32> {
+    let rustc = env::var_os("RUSTC").unwrap_or_else(|| OsString::from("rustc"));
+    let output = match Command::new(rustc).arg("--version").output() {
+        Ok(output) => output,
+        Err(_) => return None,
+    };
+    let version = match String::from_utf8(output.stdout) {
+        Ok(version) => version,
+        Err(_) => return None,
+    };
+    let mut parts = version.split('.');
+    if parts.next() != Some("rustc 1") {
+        return None;
+    }
+    parts.next().and_then(|minor| minor.parse().ok())
+}
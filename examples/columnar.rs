@@ -0,0 +1,40 @@
+extern crate prettytable;
+
+use std::time::Instant;
+
+use prettytable::Table;
+use prettytable::row::Row;
+use prettytable::columnar::ColumnTable;
+
+/*
+    Compare the time needed to compute the width of each column of a 100 000 rows table,
+    with a row-major `Table` and with a column-major `ColumnTable`.
+    Run it in release mode to get meaningful numbers :
+    cargo run --release --example columnar
+
+    `ColumnTable` is expected to be noticeably faster for this task, since cells of a
+    same column are contiguous in memory. Actual timings depend on the machine.
+*/
+fn main() {
+    let mut table = Table::new();
+    for i in 0..100_000 {
+        table.add_row(Row::from((0..20).map(|j| i * j)));
+    }
+    let ct = ColumnTable::from(&table);
+
+    let start = Instant::now();
+    let mut widths = Vec::new();
+    for col in 0..table.get_column_num() {
+        widths.push(table.column_iter(col).map(|c| c.get_width()).max().unwrap_or(0));
+    }
+    let elapsed = start.elapsed();
+    println!("Table       : {:?} ({:?})", elapsed, &widths[..3]);
+
+    let start = Instant::now();
+    let mut widths = Vec::new();
+    for col in 0..ct.get_column_num() {
+        widths.push(ct.column_iter(col).map(|c| c.get_width()).max().unwrap_or(0));
+    }
+    let elapsed = start.elapsed();
+    println!("ColumnTable : {:?} ({:?})", elapsed, &widths[..3]);
+}
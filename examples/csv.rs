@@ -25,7 +25,8 @@ fn main() {
     table.printstd();
 
     println!("");
-    println!("{}", table.to_csv(Vec::new()).unwrap().into_string());
+    let writer = table.to_csv(Vec::new()).unwrap();
+    println!("{}", String::from_utf8(writer.into_inner().unwrap()).unwrap());
 }
 
 #[cfg(not(feature = "csv"))]
@@ -0,0 +1,42 @@
+#[macro_use]
+extern crate prettytable;
+
+use std::time::Instant;
+
+use prettytable::Table;
+use prettytable::row::Row;
+
+/*
+    Compare the time needed to load 200 000 rows of 5 short cells from a `Vec<Vec<&str>>`,
+    with `add_row` and `Row::from`, with the `add_row!` macro, and with `Table::load_rows`.
+    Run it in release mode to get meaningful numbers :
+    cargo run --release --example load_rows
+
+    `load_rows` is expected to be the fastest, since it allocates every vector at its final
+    size. Actual timings depend on the machine.
+*/
+fn main() {
+    let words: Vec<String> = (0..1000).map(|i| format!("w{}", i)).collect();
+    let data: Vec<Vec<&str>> = (0..200_000)
+        .map(|i| (0..5).map(|j| &words[(i * 5 + j) % words.len()][..]).collect())
+        .collect();
+
+    let start = Instant::now();
+    let mut table = Table::new();
+    for row in &data {
+        table.add_row(Row::from(row));
+    }
+    println!("add_row    : {:?} ({} rows)", start.elapsed(), table.len());
+
+    let start = Instant::now();
+    let mut table = Table::new();
+    for row in &data {
+        add_row!(table, row[0], row[1], row[2], row[3], row[4]);
+    }
+    println!("add_row!   : {:?} ({} rows)", start.elapsed(), table.len());
+
+    let start = Instant::now();
+    let mut table = Table::new();
+    table.load_rows(&data);
+    println!("load_rows  : {:?} ({} rows)", start.elapsed(), table.len());
+}
@@ -0,0 +1,257 @@
+//! This module contains definition of tables borrowing their content
+use std::io::{self, Write, Error};
+use std::fmt;
+
+use term::Terminal;
+
+use super::{Table, PrintRow, print_rows_with_width};
+use super::row::Row;
+use super::cell::Cell;
+use super::format::{TableFormat, Alignment, PrintLimits, consts, line_width_with};
+use super::utils::{StringWriter, LineCounter, print_align_measured, print_row_lines,
+                   truncate_with, write_lines};
+
+/// A printable table whose cells are borrowed string slices.
+///
+/// Contrary to `Table`, no `Cell` is created, and content is never copied. This is useful
+/// to print data already held in memory (eg: parsed log buffers) without duplicating it.
+/// Cells are printed without any style, aligned to the left.
+///
+/// # Examples
+/// ```rust
+/// use prettytable::borrowed::StrTable;
+///
+/// let buffer = String::from("foo,bar\nbaz,qux");
+/// let table = StrTable::init(buffer.lines().map(|l| l.split(',').collect()).collect());
+/// table.printstd();
+/// ```
+#[derive(Clone, Debug)]
+pub struct StrTable<'a> {
+    format: TableFormat,
+    titles: Option<Vec<&'a str>>,
+    rows: Vec<Vec<&'a str>>,
+}
+
+impl<'a> StrTable<'a> {
+    /// Create an empty table
+    pub fn new() -> StrTable<'a> {
+        StrTable::init(Vec::new())
+    }
+
+    /// Create a table initialized with `rows`
+    pub fn init(rows: Vec<Vec<&'a str>>) -> StrTable<'a> {
+        StrTable {
+            format: *consts::FORMAT_DEFAULT,
+            titles: None,
+            rows: rows,
+        }
+    }
+
+    /// Change the table format. Eg : Separators
+    pub fn set_format(&mut self, format: TableFormat) {
+        self.format = format;
+    }
+
+    /// Get a mutable reference to the internal format
+    pub fn get_format(&mut self) -> &mut TableFormat {
+        &mut self.format
+    }
+
+    /// Set the optional title lines
+    pub fn set_titles(&mut self, titles: Vec<&'a str>) {
+        self.titles = Some(titles);
+    }
+
+    /// Unset the title line
+    pub fn unset_titles(&mut self) {
+        self.titles = None;
+    }
+
+    /// Get the number of rows
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Check if the table is empty
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    /// Compute and return the number of column
+    pub fn get_column_num(&self) -> usize {
+        self.rows.iter().map(|r| r.len()).max().unwrap_or(0)
+    }
+
+    /// Get an immutable reference to a row
+    pub fn get_row(&self, row: usize) -> Option<&[&'a str]> {
+        self.rows.get(row).map(|r| &r[..])
+    }
+
+    /// Append a row in the table
+    pub fn add_row(&mut self, row: Vec<&'a str>) {
+        self.rows.push(row);
+    }
+
+    /// Get the width of all columns, and return a vector
+    /// with the result for each column
+    fn get_all_column_width(&self) -> Vec<usize> {
+        let ambiguous = self.format.get_ambiguous_width();
+        let mut col_width = vec![0usize; self.get_column_num()];
+        for r in self.titles.iter().chain(self.rows.iter()) {
+            for (w, cell) in col_width.iter_mut().zip(r.iter()) {
+                for line in cell.lines() {
+                    let l = line_width_with(line, ambiguous);
+                    if l > *w {
+                        *w = l;
+                    }
+                }
+            }
+        }
+        col_width
+    }
+
+    /// Convert this table into an owned `Table`, copying the content
+    pub fn to_table(&self) -> Table {
+        let mut table = Table::init(self.rows.iter().map(|r| to_row(r)).collect());
+        table.set_format(self.format);
+        if let Some(ref t) = self.titles {
+            table.set_titles(to_row(t));
+        }
+        table
+    }
+
+    /// Internal only
+    fn __print<T: Write + ?Sized>(&self, out: &mut T, limits: &PrintLimits) -> Result<(), Error> {
+        print_rows_with_width(&mut LineCounter::new(out),
+                              &self.format,
+                              &self.titles,
+                              &None,
+                              &None,
+                              self.rows.iter(),
+                              self.get_all_column_width(),
+                              limits,
+                              |r, out, format, col_width| r.print_plain(out, format, col_width))
+    }
+
+    /// Print the table to `out`
+    pub fn print<T: Write + ?Sized>(&self, out: &mut T) -> Result<(), Error> {
+        self.__print(out, &PrintLimits::new())
+    }
+
+    /// Print the table to terminal `out`. Cells have no style, so this prints the same
+    /// content as `print`
+    pub fn print_term<T: Terminal + ?Sized>(&self, out: &mut T) -> Result<(), Error> {
+        self.__print(out, &PrintLimits::new())
+    }
+
+    /// Print the table to standard output, applying the `PrintLimits` configured in the
+    /// table format. Cells have no style, so `force_colorize` has no effect
+    /// # Panic
+    /// Panic if writing to standard output fails. See `try_print_tty` to handle errors instead
+    pub fn print_tty(&self, force_colorize: bool) {
+        if let Err(e) = self.try_print_tty(force_colorize) {
+            panic!("Cannot print table to standard output : {}", e);
+        }
+    }
+
+    /// Print the table to standard output like `print_tty`, and return the number of
+    /// printed lines, or the error which stopped printing
+    pub fn try_print_tty(&self, _force_colorize: bool) -> Result<usize, Error> {
+        let stdout = io::stdout();
+        let mut lock = stdout.lock();
+        let mut out = LineCounter::new(&mut lock);
+        self.__print(&mut out, &self.format.get_limits())?;
+        out.flush()?;
+        Ok(out.lines())
+    }
+
+    /// Print the table to standard output, applying the `PrintLimits` configured in the
+    /// table format. Calling `printstd()` is equivalent to calling `print_tty(false)`
+    /// # Panic
+    /// Panic if writing to standard output fails. See `try_printstd` to handle errors instead
+    pub fn printstd(&self) {
+        self.print_tty(false);
+    }
+
+    /// Print the table to standard output like `printstd`, and return the number of printed
+    /// lines, or the error which stopped printing
+    pub fn try_printstd(&self) -> Result<usize, Error> {
+        self.try_print_tty(false)
+    }
+}
+
+/// Internal only. Copy borrowed cells into a `Row`
+fn to_row(cells: &[&str]) -> Row {
+    Row::new(cells.iter().map(|c| Cell::new(c)).collect())
+}
+
+impl<'a> PrintRow for Vec<&'a str> {
+    fn print_plain<T: Write + ?Sized>(&self,
+                                      out: &mut T,
+                                      format: &TableFormat,
+                                      col_width: &[usize])
+                                      -> Result<(), Error> {
+        let height = self.iter().map(|c| c.lines().count()).max().unwrap_or(0);
+        let ambiguous = format.get_ambiguous_width();
+        print_row_lines(out,
+                        format,
+                        col_width,
+                        ::std::cmp::max(height, 1),
+                        |out, i, j, width, skip_r_fill| {
+            let line = self.get(j).and_then(|c| c.lines().nth(i)).unwrap_or("");
+            let line = truncate_with(line, width, ambiguous);
+            let line_width = line_width_with(line, ambiguous);
+            print_align_measured(out, Alignment::LEFT, line, line_width, ' ', width, skip_r_fill)
+        })
+    }
+}
+
+impl<'a> Default for StrTable<'a> {
+    fn default() -> StrTable<'a> {
+        StrTable::new()
+    }
+}
+
+impl<'a> fmt::Display for StrTable<'a> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        let mut writer = StringWriter::new();
+        if self.print(&mut writer).is_err() {
+            return Err(fmt::Error);
+        }
+        write_lines(fmt, writer.as_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use span::SpanRecorder;
+
+    #[test]
+    fn same_output_as_table() {
+        let data = String::from("a,bc,def\ndef,bc\nx,由系统,y");
+        let mut table = StrTable::init(data.lines().map(|l| l.split(',').collect()).collect());
+        table.set_titles(vec!["t1", "t2\nt2 bis", "t3"]);
+        assert_eq!(table.len(), 3);
+        assert_eq!(table.get_column_num(), 3);
+        assert_eq!(table.get_row(1).unwrap(), &["def", "bc"]);
+        let owned = table.to_table();
+        assert_eq!(owned.len(), 3);
+        assert_eq!(table.to_string(), owned.to_string());
+        table.add_row(vec!["multi\nline"]);
+        assert_eq!(table.to_string(), table.to_table().to_string());
+        assert_eq!(format!("{:-<30.10}", table), format!("{:-<30.10}", table.to_table()));
+    }
+
+    #[test]
+    fn print_term() {
+        let table = StrTable::init(vec![vec!["a", "bc"], vec!["def"]]);
+        let mut out = SpanRecorder::new();
+        table.print_term(&mut out).unwrap();
+        let lines: Vec<String> = out.into_lines()
+            .iter()
+            .map(|l| l.iter().map(|s| s.get_text()).collect())
+            .collect();
+        assert_eq!(lines.join("\n") + "\n", table.to_string().replace("\r\n", "\n"));
+    }
+}
@@ -2,10 +2,224 @@
 
 use std::io::{Write, Error};
 use std::string::ToString;
-use unicode_width::UnicodeWidthStr;
+use std::{error, fmt};
+use std::sync::{Arc, Mutex};
 use term::{Attr, Terminal, color};
-use super::format::Alignment;
-use super::utils::print_align;
+use super::format::{Alignment, AmbiguousWidth, NumberFormat, VerticalAlignment, display_width,
+                    line_width_with};
+use super::utils::{print_align, print_align_measured, truncate_with};
+use super::span::{StyledSpan, StyledText};
+#[cfg(feature = "images")]
+use super::image::{Image, ImageProtocol};
+
+/// Error returned by `Cell::try_style_spec` when a style specifier string is invalid.
+/// Positions are character indices in the specifier string
+#[derive(Clone, Debug, PartialEq)]
+pub enum StyleSpecError {
+    /// A character which is not a style specifier
+    UnknownSpecifier {
+        /// Position of the character
+        position: usize,
+        /// The unknown character
+        specifier: char,
+    },
+    /// A character following `F` or `B` which is not a color specifier
+    UnknownColor {
+        /// Position of the character
+        position: usize,
+        /// The unknown character
+        specifier: char,
+    },
+    /// A 256-color index which is missing or out of range
+    InvalidColorIndex {
+        /// Position of the `#` or `{` starting the index
+        position: usize,
+        /// The digits of the index
+        index: String,
+    },
+    /// A `F` or `B` prefix at the end of the string, not followed by any color
+    DanglingPrefix {
+        /// Position of the prefix
+        position: usize,
+        /// The prefix, either `F` or `B`
+        prefix: char,
+    },
+}
+
+impl fmt::Display for StyleSpecError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match *self {
+            StyleSpecError::UnknownSpecifier { position, specifier } => {
+                write!(fmt, "unknown style specifier '{}' at position {}", specifier, position)
+            }
+            StyleSpecError::UnknownColor { position, specifier } => {
+                write!(fmt, "unknown color specifier '{}' at position {}", specifier, position)
+            }
+            StyleSpecError::InvalidColorIndex { position, ref index } => {
+                write!(fmt, "invalid color index '{}' at position {}", index, position)
+            }
+            StyleSpecError::DanglingPrefix { position, prefix } => {
+                write!(fmt, "'{}' at position {} is not followed by a color", prefix, position)
+            }
+        }
+    }
+}
+
+impl error::Error for StyleSpecError {
+    fn description(&self) -> &str {
+        match *self {
+            StyleSpecError::UnknownSpecifier { .. } => "unknown style specifier",
+            StyleSpecError::UnknownColor { .. } => "unknown color specifier",
+            StyleSpecError::InvalidColorIndex { .. } => "invalid color index",
+            StyleSpecError::DanglingPrefix { .. } => "color prefix not followed by a color",
+        }
+    }
+}
+
+/// A 24-bit RGB color, for terminals supporting truecolor
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    /// Blend this color with `other`. `ratio` is the weight of `other`, from `0.0` which
+    /// returns this color, to `1.0` which returns `other`. It is clamped to this range
+    pub fn blend(&self, other: Rgb, ratio: f64) -> Rgb {
+        let ratio = if ratio < 0.0 { 0.0 } else if ratio > 1.0 { 1.0 } else { ratio };
+        let mix = |a: u8, b: u8| (a as f64 + (b as f64 - a as f64) * ratio).round() as u8;
+        Rgb(mix(self.0, other.0), mix(self.1, other.1), mix(self.2, other.2))
+    }
+
+    /// Return the approximate color of entry `color` of the 256-color palette, as rendered
+    /// by xterm. Indices above 255 are treated as 255
+    pub fn from_256(color: color::Color) -> Rgb {
+        const BASIC: [(u8, u8, u8); 16] = [(0, 0, 0), (205, 0, 0), (0, 205, 0), (205, 205, 0),
+                                           (0, 0, 238), (205, 0, 205), (0, 205, 205),
+                                           (229, 229, 229), (127, 127, 127), (255, 0, 0),
+                                           (0, 255, 0), (255, 255, 0), (92, 92, 255),
+                                           (255, 0, 255), (0, 255, 255), (255, 255, 255)];
+        const LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+        let color = ::std::cmp::min(color, 255) as usize;
+        if color < 16 {
+            let (r, g, b) = BASIC[color];
+            Rgb(r, g, b)
+        } else if color < 232 {
+            let i = color - 16;
+            Rgb(LEVELS[i / 36], LEVELS[(i / 6) % 6], LEVELS[i % 6])
+        } else {
+            let level = 8 + 10 * (color - 232) as u8;
+            Rgb(level, level, level)
+        }
+    }
+
+    /// Return the index of the closest color in the 256-color palette
+    fn to_256(&self) -> color::Color {
+        const LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+        let closest = |v: u8| {
+            (0..6)
+                .min_by_key(|&i| (LEVELS[i] as i32 - v as i32).abs())
+                .unwrap_or(0)
+        };
+        let dist = |r: u8, g: u8, b: u8| {
+            let d = |x: u8, y: u8| (x as i32 - y as i32) * (x as i32 - y as i32);
+            d(r, self.0) + d(g, self.1) + d(b, self.2)
+        };
+        let (r, g, b) = (closest(self.0), closest(self.1), closest(self.2));
+        let cube = dist(LEVELS[r], LEVELS[g], LEVELS[b]);
+        // Gray ramp from 232 (8, 8, 8) to 255 (238, 238, 238)
+        let avg = (self.0 as i32 + self.1 as i32 + self.2 as i32) / 3;
+        let gray = ::std::cmp::min(::std::cmp::max(avg - 8 + 5, 0) / 10, 23) as u8;
+        let level = 8 + 10 * gray;
+        if dist(level, level, level) < cube {
+            232 + gray as color::Color
+        } else {
+            16 + 36 * r as color::Color + 6 * g as color::Color + b as color::Color
+        }
+    }
+
+    /// Return the closest of the 8 basic colors
+    fn to_8(&self) -> color::Color {
+        (self.0 > 127) as color::Color | ((self.1 > 127) as color::Color) << 1 |
+        ((self.2 > 127) as color::Color) << 2
+    }
+}
+
+/// Return the closest of the 8 basic colors to entry `color` of the 256-color palette,
+/// for terminals supporting only those. Bright colors are mapped to their normal counterpart.
+/// This is how colors are downgraded when printing to such terminals
+pub fn basic_color(color: color::Color) -> color::Color {
+    if color < 8 {
+        color
+    } else if color < 16 {
+        color - 8
+    } else {
+        Rgb::from_256(color).to_8()
+    }
+}
+
+lazy_static! {
+    static ref TRUECOLOR: bool = match ::std::env::var("COLORTERM") {
+        Ok(ref v) => v == "truecolor" || v == "24bit",
+        Err(..) => false,
+    };
+}
+
+/// How missing values are rendered by `Cell::from_option`
+///
+/// # Examples
+/// ```rust
+/// use prettytable::cell::{Cell, Placeholder};
+///
+/// let placeholder = Placeholder::new("N/A").dim(true);
+/// assert_eq!(Cell::from_option(Some(1.5), &placeholder).get_content(), "1.5");
+/// let cell = Cell::from_option(None::<f64>, &placeholder);
+/// assert_eq!(cell.get_content(), "N/A");
+/// assert_eq!(cell.get_style_spec(), "D");
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Placeholder {
+    /// Text displayed instead of the missing value
+    text: String,
+    /// Whether the text is dimmed
+    dim: bool,
+}
+
+impl Default for Placeholder {
+    fn default() -> Placeholder {
+        Placeholder::new("-")
+    }
+}
+
+impl Placeholder {
+    /// Create a new `Placeholder` displaying `text`, which may be empty, without style
+    pub fn new(text: &str) -> Placeholder {
+        Placeholder {
+            text: text.to_string(),
+            dim: false,
+        }
+    }
+
+    /// Set whether the text is dimmed. Can be chained
+    pub fn dim(mut self, dim: bool) -> Placeholder {
+        self.dim = dim;
+        self
+    }
+
+    /// Get the text displayed instead of missing values
+    pub fn get_text(&self) -> &str {
+        &self.text
+    }
+
+    /// Check whether the text is dimmed
+    pub fn is_dim(&self) -> bool {
+        self.dim
+    }
+
+    /// Create a new `Cell` displaying this placeholder
+    pub fn cell(&self) -> Cell {
+        let cell = Cell::new(&self.text);
+        if self.dim { cell.with_style(Attr::Dim) } else { cell }
+    }
+}
 
 /// Represent a table cell containing a string.
 ///
@@ -15,27 +229,65 @@ use super::utils::print_align;
 pub struct Cell {
     content: Vec<String>,
     width: usize,
+    ambiguous_width: AmbiguousWidth,
     align: Alignment,
+    line_align: Vec<Option<Alignment>>,
+    valign: VerticalAlignment,
+    fill: char,
     style: Vec<Attr>,
+    fg_rgb: Option<Rgb>,
+    bg_rgb: Option<Rgb>,
+    spans: Option<Vec<Vec<StyledSpan>>>,
+    lazy: Option<Arc<LazyContent>>,
+    #[cfg(feature = "images")]
+    image: Option<Image>,
+}
+
+/// Content of a lazy cell, computed by `compute` the first time it is needed.
+/// Lines and width are cached, and shared by the clones of the cell
+struct LazyContent {
+    compute: Box<Fn() -> String + Send + Sync>,
+    value: Mutex<Option<(Vec<String>, usize)>>,
+}
+
+impl fmt::Debug for LazyContent {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        fmt.write_str("LazyContent")
+    }
+}
+
+/// Split `string` into the lines of a cell, and measure its width
+fn split_lines(string: &str) -> (Vec<String>, usize) {
+    // Single line content is stored as is, without splitting it into lines
+    let multiline = string.is_empty() || string.contains(|c| c == '\n' || c == '\r');
+    let content: Vec<String> = if multiline {
+        string.lines().map(|x| x.to_string()).collect()
+    } else {
+        vec![string.to_string()]
+    };
+    (content, display_width(string))
 }
 
 impl Cell {
     /// Create a new `Cell` initialized with content from `string`.
     /// Text alignment in cell is configurable with the `align` argument
     pub fn new_align(string: &str, align: Alignment) -> Cell {
-        let content: Vec<String> = string.lines().map(|x| x.to_string()).collect();
-        let mut width = 0;
-        for cont in &content {
-            let l = UnicodeWidthStr::width(&cont[..]);
-            if l > width {
-                width = l;
-            }
-        }
+        let (content, width) = split_lines(string);
         Cell {
             content: content,
             width: width,
+            ambiguous_width: AmbiguousWidth::Narrow,
             align: align,
+            line_align: Vec::new(),
+            valign: VerticalAlignment::Top,
+            fill: ' ',
             style: Vec::new(),
+            fg_rgb: None,
+            bg_rgb: None,
+            spans: None,
+            lazy: None,
+            #[cfg(feature = "images")]
+            image: None,
         }
     }
 
@@ -45,11 +297,164 @@ impl Cell {
         Cell::new_align(string, Alignment::LEFT)
     }
 
+    /// Create a new `Cell` whose content is returned by `f`. `f` is called at most once, the
+    /// first time the content, width or height of the cell is needed, eg : when printing it.
+    /// Rows sliced away or filtered out before printing never compute their content.
+    /// By default, content is align to `LEFT`
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use prettytable::cell::Cell;
+    /// let cell = Cell::new_lazy(|| (1..4).map(|i| i.to_string()).collect::<Vec<_>>().join("+"));
+    /// assert!(!cell.is_computed());
+    /// assert_eq!(cell.get_width(), 5);
+    /// assert!(cell.is_computed());
+    /// assert_eq!(cell.get_content(), "1+2+3");
+    /// ```
+    pub fn new_lazy<F>(f: F) -> Cell
+        where F: Fn() -> String + Send + Sync + 'static
+    {
+        let mut cell = Cell::default();
+        cell.lazy = Some(Arc::new(LazyContent {
+                                      compute: Box::new(f),
+                                      value: Mutex::new(None),
+                                  }));
+        cell
+    }
+
+    /// Check whether the content of the cell is known, which is always the case
+    /// unless the cell has been created with `new_lazy` and its content not needed yet
+    pub fn is_computed(&self) -> bool {
+        match self.lazy {
+            Some(ref lazy) => lazy.value.lock().unwrap_or_else(|e| e.into_inner()).is_some(),
+            None => true,
+        }
+    }
+
+    /// Call `f` with the lines and the width of the cell, computing the content of
+    /// lazy cells if needed
+    fn with_lines<R, F: FnOnce(&[String], usize) -> R>(&self, f: F) -> R {
+        match self.lazy {
+            Some(ref lazy) => {
+                let mut value = lazy.value.lock().unwrap_or_else(|e| e.into_inner());
+                if value.is_none() {
+                    *value = Some(split_lines(&(lazy.compute)()));
+                }
+                let &(ref lines, width) = value.as_ref().unwrap();
+                f(lines, width)
+            }
+            None => f(&self.content, self.width),
+        }
+    }
+
+    /// Create a new `Cell` displaying `value`, or `placeholder` if `value` is `None`.
+    /// See `Placeholder` for details
+    pub fn from_option<T: ToString>(value: Option<T>, placeholder: &Placeholder) -> Cell {
+        match value {
+            Some(value) => Cell::new(&value.to_string()),
+            None => placeholder.cell(),
+        }
+    }
+
+    /// Create a new `Cell` displaying `value` rendered with `format`, aligned to `RIGHT`
+    pub fn from_f64_with(value: f64, format: &NumberFormat) -> Cell {
+        Cell::new_align(&format.format(value), Alignment::RIGHT)
+    }
+
+    /// Create a new `Cell` displaying the integer `value` rendered with `format`,
+    /// aligned to `RIGHT`
+    pub fn from_i64_with(value: i64, format: &NumberFormat) -> Cell {
+        Cell::new_align(&format.format_int(value), Alignment::RIGHT)
+    }
+
+    /// Create a new `Cell` displaying `text`, whose spans keep their own style when printed
+    /// to a terminal. The content of the cell is the plain text, which is used to compute
+    /// its width and when printing without colors
+    pub fn new_styled(text: &StyledText) -> Cell {
+        let mut cell = Cell::new(&text.to_string());
+        cell.spans = Some(text.lines());
+        cell
+    }
+
+    /// Get the styled spans of line `idx` if the cell has been created with `new_styled`
+    pub fn get_styled_line(&self, idx: usize) -> Option<&[StyledSpan]> {
+        match self.spans {
+            Some(ref lines) => lines.get(idx).map(|l| &l[..]),
+            None => None,
+        }
+    }
+
     /// Set text alignment in the cell
     pub fn align(&mut self, align: Alignment) {
         self.align = align;
     }
 
+    /// Get text alignment in the cell
+    pub fn get_alignment(&self) -> Alignment {
+        self.align
+    }
+
+    /// Set the alignment of line `line` of a multi-line cell, overriding the alignment of the
+    /// cell for this line only, e.g. to right align an identifier below a name
+    pub fn align_line(&mut self, line: usize, align: Alignment) {
+        if line >= self.line_align.len() {
+            self.line_align.resize(line + 1, None);
+        }
+        self.line_align[line] = Some(align);
+    }
+
+    /// Set the alignment of line `line`. Can be chained
+    ///
+    /// # Examples
+    /// ```rust
+    /// use prettytable::cell::Cell;
+    /// use prettytable::format::Alignment;
+    ///
+    /// let cell = Cell::new("John Doe\n#42").with_line_align(1, Alignment::RIGHT);
+    /// let mut out = Vec::new();
+    /// cell.print(&mut out, 1, 8, false).unwrap();
+    /// assert_eq!(out, b"     #42");
+    /// ```
+    pub fn with_line_align(mut self, line: usize, align: Alignment) -> Cell {
+        self.align_line(line, align);
+        self
+    }
+
+    /// Get the alignment of line `line`, which is the alignment of the cell
+    /// unless it has been overridden with `align_line`
+    pub fn get_line_alignment(&self, line: usize) -> Alignment {
+        self.line_align.get(line).and_then(|a| *a).unwrap_or(self.align)
+    }
+
+    /// Set vertical alignment of the content, when the cell is shorter than its row
+    pub fn valign(&mut self, valign: VerticalAlignment) {
+        self.valign = valign;
+    }
+
+    /// Get vertical alignment of the content
+    pub fn get_valign(&self) -> VerticalAlignment {
+        self.valign
+    }
+
+    /// Set the character filling the blank space around the content of the cell, eg : `.`
+    /// for the leader lines of a table of contents. Lines without content are left blank.
+    /// Defaults to a space
+    pub fn set_fill(&mut self, fill: char) {
+        self.fill = fill;
+    }
+
+    /// Set the character filling the blank space around the content of the cell.
+    /// Can be chained
+    pub fn with_fill(mut self, fill: char) -> Cell {
+        self.set_fill(fill);
+        self
+    }
+
+    /// Get the character filling the blank space around the content of the cell
+    pub fn get_fill(&self) -> char {
+        self.fill
+    }
+
     /// Add a style attribute to the cell
     pub fn style(&mut self, attr: Attr) {
         self.style.push(attr);
@@ -61,10 +466,108 @@ impl Cell {
         self
     }
 
-    /// Remove all style attributes and reset alignment to default (LEFT)
+    /// Set a 24-bit foreground color, overriding any foreground color attribute.
+    /// It is emitted as is when the `COLORTERM` environment variable is `truecolor` or `24bit`,
+    /// and approximated with the closest color supported by the terminal otherwise
+    pub fn fg_rgb(&mut self, color: Rgb) {
+        self.fg_rgb = Some(color);
+    }
+
+    /// Set a 24-bit foreground color. Can be chained
+    pub fn with_fg_rgb(mut self, color: Rgb) -> Cell {
+        self.fg_rgb(color);
+        self
+    }
+
+    /// Set a 24-bit background color, overriding any background color attribute.
+    /// See `fg_rgb` for how it is rendered
+    pub fn bg_rgb(&mut self, color: Rgb) {
+        self.bg_rgb = Some(color);
+    }
+
+    /// Set a 24-bit background color. Can be chained
+    pub fn with_bg_rgb(mut self, color: Rgb) -> Cell {
+        self.bg_rgb(color);
+        self
+    }
+
+    /// Display `image` in the cell instead of its content, on terminals supporting the iTerm2
+    /// or Kitty image protocols. The image is stretched over the width of the column and the
+    /// height of the content, which is printed as a text placeholder on other terminals and
+    /// outputs. Requires the `images` feature
+    #[cfg(feature = "images")]
+    pub fn with_image<I: Into<Image>>(mut self, image: I) -> Cell {
+        self.image = Some(image.into());
+        self
+    }
+
+    /// Get the image displayed in the cell, if any
+    #[cfg(feature = "images")]
+    pub fn get_image(&self) -> Option<&Image> {
+        self.image.as_ref()
+    }
+
+    /// Get the style attributes of the cell
+    pub fn get_style(&self) -> &[Attr] {
+        &self.style
+    }
+
+    /// Get the 24-bit foreground color, if any
+    pub fn get_fg_rgb(&self) -> Option<Rgb> {
+        self.fg_rgb
+    }
+
+    /// Get the 24-bit background color, if any
+    pub fn get_bg_rgb(&self) -> Option<Rgb> {
+        self.bg_rgb
+    }
+
+    /// Replace foreground and background color attributes by the 24-bit color returned by
+    /// `f` for their color, if any. A 24-bit color already set on the cell is kept,
+    /// but the attribute is removed anyway since it would be overridden when printed
+    pub fn override_colors<F: Fn(color::Color) -> Option<Rgb>>(&mut self, f: F) {
+        let mut style = Vec::with_capacity(self.style.len());
+        for attr in self.style.drain(..) {
+            match attr {
+                Attr::ForegroundColor(c) if f(c).is_some() => {
+                    self.fg_rgb = self.fg_rgb.or(f(c));
+                }
+                Attr::BackgroundColor(c) if f(c).is_some() => {
+                    self.bg_rgb = self.bg_rgb.or(f(c));
+                }
+                _ => style.push(attr),
+            }
+        }
+        self.style = style;
+    }
+
+    /// Copy the style attributes and colors of `other`,
+    /// unless this cell already has style attributes or colors of its own.
+    /// The fill character and the vertical alignment of `other` are copied as well if this cell
+    /// is filled with spaces and aligned on top
+    pub fn inherit_style(&mut self, other: &Cell) {
+        if self.style.is_empty() && self.fg_rgb.is_none() && self.bg_rgb.is_none() {
+            self.style = other.style.clone();
+            self.fg_rgb = other.fg_rgb;
+            self.bg_rgb = other.bg_rgb;
+        }
+        if self.fill == ' ' {
+            self.fill = other.fill;
+        }
+        if self.valign == VerticalAlignment::Top {
+            self.valign = other.valign;
+        }
+    }
+
+    /// Remove all style attributes and reset alignment to default (LEFT, Top),
+    /// including the alignment of individual lines
     pub fn reset_style(&mut self) {
         self.style.clear();
+        self.fg_rgb = None;
+        self.bg_rgb = None;
         self.align(Alignment::LEFT);
+        self.line_align.clear();
+        self.valign(VerticalAlignment::Top);
     }
 
     /// Set the cell's style by applying the given specifier string
@@ -81,10 +584,19 @@ impl Cell {
     /// * **b** : **b**old
     /// * **i** : **i**talic
     /// * **u** : **u**nderline
+    /// * **D** : **D**im
+    /// * **R** : **R**everse
+    /// * **k** : blin**k**
+    /// * **s** : **s**tandout
+    /// * **h** : **h**idden (secure mode)
     /// * **c** : Align **c**enter
     /// * **l** : Align **l**eft
     /// * **r** : Align **r**ight
+    /// * **t** : Align **t**op, when the cell is shorter than its row
+    /// * **v** : Align in the middle, **v**ertically centered
+    /// * **o** : Align b**o**ttom
     /// * **d** : **d**efault style
+    /// * **m** : parse **m**arkup in the content, see `StyledText::parse_markup`
     ///
     /// ### List of color specifiers :
     ///
@@ -103,13 +615,89 @@ impl Cell {
     /// * **R** : Bright Red
     /// * **B** : Bright Blue
     /// * ... and so on ...
-    pub fn style_spec(mut self, spec: &str) -> Cell {
+    ///
+    /// Colors of 256-color terminals can be specified by their index, either with **#**
+    /// followed by up to 3 digits, or between braces. Eg : **F#196** or **B{22}**
+    ///
+    /// 24-bit RGB colors are specified with **#** followed by exactly 6 hexadecimal digits.
+    /// Eg : **F#ff8800**. Use braces for 256-color indices followed by other specifiers
+    /// looking like hexadecimal digits, like **F{19}bc** instead of **F#19bc**
+    pub fn style_spec(self, spec: &str) -> Cell {
+        self.parse_spec(spec).0
+    }
+
+    /// Set the cell's style by applying the given specifier string, like `style_spec`, but
+    /// fail on the first unknown specifier, unknown color or `F`/`B` prefix not followed
+    /// by a color, instead of silently ignoring it
+    ///
+    /// # Examples
+    /// ```rust
+    /// use prettytable::cell::{Cell, StyleSpecError};
+    ///
+    /// assert!(Cell::new("foo").try_style_spec("FrBybl").is_ok());
+    /// assert_eq!(Cell::new("foo").try_style_spec("Fzb").unwrap_err(),
+    ///            StyleSpecError::UnknownColor { position: 1, specifier: 'z' });
+    /// ```
+    pub fn try_style_spec(self, spec: &str) -> Result<Cell, StyleSpecError> {
+        match self.parse_spec(spec) {
+            (_, Some(err)) => Err(err),
+            (cell, None) => Ok(cell),
+        }
+    }
+
+    /// Apply the specifier string, skipping invalid specifiers.
+    /// Return the styled cell along with the first error encountered, if any
+    fn parse_spec(mut self, spec: &str) -> (Cell, Option<StyleSpecError>) {
         self.reset_style();
+        let mut error = None;
         let mut foreground = false;
         let mut background = false;
-        for c in spec.chars() {
+        let mut markup = false;
+        let mut chars = spec.chars().enumerate().peekable();
+        while let Some((pos, c)) = chars.next() {
             if foreground || background {
+                let rgb: String = chars.clone().take(6).map(|(_, d)| d).collect();
+                if c == '#' && rgb.len() == 6 && rgb.chars().all(|d| d.is_digit(16)) {
+                    let component = |i| u8::from_str_radix(&rgb[i..i + 2], 16).unwrap_or(0);
+                    let rgb = Rgb(component(0), component(2), component(4));
+                    if foreground {
+                        self.fg_rgb(rgb);
+                    } else {
+                        self.bg_rgb(rgb);
+                    }
+                    for _ in 0..6 {
+                        chars.next();
+                    }
+                    foreground = false;
+                    background = false;
+                    continue;
+                }
                 let color = match c {
+                    '#' | '{' => {
+                        let mut index = String::new();
+                        while let Some(&(_, d)) = chars.peek() {
+                            if d < '0' || d > '9' || (c == '#' && index.len() == 3) {
+                                break;
+                            }
+                            index.push(d);
+                            chars.next();
+                        }
+                        if c == '{' && chars.peek().map(|&(_, d)| d) == Some('}') {
+                            chars.next();
+                        }
+                        match index.parse::<color::Color>() {
+                            Ok(i) if i < 256 => i,
+                            _ => {
+                                error = error.or(Some(StyleSpecError::InvalidColorIndex {
+                                                          position: pos,
+                                                          index: index,
+                                                      }));
+                                foreground = false;
+                                background = false;
+                                continue;
+                            }
+                        }
+                    }
                     'r' => color::RED,
                     'R' => color::BRIGHT_RED,
                     'b' => color::BLUE,
@@ -127,7 +715,10 @@ impl Cell {
                     'd' => color::BLACK,
                     'D' => color::BRIGHT_BLACK,
                     _ => {
-                        // Silently ignore unknown tags
+                        error = error.or(Some(StyleSpecError::UnknownColor {
+                                                  position: pos,
+                                                  specifier: c,
+                                              }));
                         foreground = false;
                         background = false;
                         continue;
@@ -147,34 +738,148 @@ impl Cell {
                     'b' => self.style(Attr::Bold),
                     'i' => self.style(Attr::Italic(true)),
                     'u' => self.style(Attr::Underline(true)),
+                    'D' => self.style(Attr::Dim),
+                    'R' => self.style(Attr::Reverse),
+                    'k' => self.style(Attr::Blink),
+                    's' => self.style(Attr::Standout(true)),
+                    'h' => self.style(Attr::Secure),
                     'c' => self.align(Alignment::CENTER),
                     'l' => self.align(Alignment::LEFT),
                     'r' => self.align(Alignment::RIGHT),
-                    _ => { /* Silently ignore unknown tags */ }
+                    't' => self.valign(VerticalAlignment::Top),
+                    'v' => self.valign(VerticalAlignment::Middle),
+                    'o' => self.valign(VerticalAlignment::Bottom),
+                    'd' => { /* Default style */ }
+                    'm' => markup = true,
+                    _ => {
+                        error = error.or(Some(StyleSpecError::UnknownSpecifier {
+                                                  position: pos,
+                                                  specifier: c,
+                                              }))
+                    }
                 }
             }
         }
-        self
+        if foreground || background {
+            let prefix = if foreground { 'F' } else { 'B' };
+            error = error.or(Some(StyleSpecError::DanglingPrefix {
+                                      position: spec.chars().count() - 1,
+                                      prefix: prefix,
+                                  }));
+        }
+        if markup {
+            let text = StyledText::parse_markup(&self.get_content());
+            let styled = Cell::new_styled(&text);
+            self.content = styled.content;
+            self.width = styled.width;
+            self.spans = styled.spans;
+            self.lazy = None;
+        }
+        (self, error)
+    }
+
+    /// Get a specifier string which gives this style when applied with `style_spec` :
+    /// style attributes, colors and horizontal alignment. Attributes which cannot be
+    /// specified, like `Attr::Italic(false)`, are left out
+    ///
+    /// # Examples
+    /// ```rust
+    /// use prettytable::cell::Cell;
+    ///
+    /// let cell = Cell::new("foo").style_spec("bF{196}B#102030c");
+    /// assert_eq!(cell.get_style_spec(), "bF{196}B#102030c");
+    /// let copy = Cell::new("bar").style_spec(&cell.get_style_spec());
+    /// assert_eq!(copy.get_style(), cell.get_style());
+    /// assert_eq!(copy.get_bg_rgb(), cell.get_bg_rgb());
+    /// ```
+    pub fn get_style_spec(&self) -> String {
+        let mut spec = String::new();
+        for attr in &self.style {
+            match *attr {
+                Attr::Bold => spec.push('b'),
+                Attr::Italic(true) => spec.push('i'),
+                Attr::Underline(true) => spec.push('u'),
+                Attr::Dim => spec.push('D'),
+                Attr::Reverse => spec.push('R'),
+                Attr::Blink => spec.push('k'),
+                Attr::Standout(true) => spec.push('s'),
+                Attr::Secure => spec.push('h'),
+                Attr::ForegroundColor(c) => {
+                    spec.push('F');
+                    spec.push_str(&color_spec(c));
+                }
+                Attr::BackgroundColor(c) => {
+                    spec.push('B');
+                    spec.push_str(&color_spec(c));
+                }
+                _ => (),
+            }
+        }
+        if let Some(Rgb(r, g, b)) = self.fg_rgb {
+            spec.push_str(&format!("F#{:02x}{:02x}{:02x}", r, g, b));
+        }
+        if let Some(Rgb(r, g, b)) = self.bg_rgb {
+            spec.push_str(&format!("B#{:02x}{:02x}{:02x}", r, g, b));
+        }
+        match self.align {
+            Alignment::LEFT => (),
+            Alignment::CENTER => spec.push('c'),
+            Alignment::RIGHT => spec.push('r'),
+        }
+        match self.valign {
+            VerticalAlignment::Top => (),
+            VerticalAlignment::Middle => spec.push('v'),
+            VerticalAlignment::Bottom => spec.push('o'),
+        }
+        spec
     }
 
     /// Return the height of the cell
     pub fn get_height(&self) -> usize {
-        self.content.len()
+        self.with_lines(|lines, _| lines.len())
     }
 
     /// Return the width of the cell
     pub fn get_width(&self) -> usize {
-        self.width
+        self.with_lines(|lines, width| match self.ambiguous_width {
+            AmbiguousWidth::Narrow => width,
+            AmbiguousWidth::Wide => {
+                lines.iter().map(|l| line_width_with(l, AmbiguousWidth::Wide)).max().unwrap_or(0)
+            }
+        })
+    }
+
+    /// Set the width of East Asian ambiguous characters, like `±` or `°`, used to measure
+    /// and print this cell on its own, eg : with `Cell::print`. Defaults to
+    /// `AmbiguousWidth::Narrow`. Tables measure and print their cells according to
+    /// their format instead, see `TableFormat::ambiguous_width`
+    pub fn set_ambiguous_width(&mut self, width: AmbiguousWidth) {
+        self.ambiguous_width = width;
+    }
+
+    /// Get the width of East Asian ambiguous characters used to measure this cell
+    pub fn get_ambiguous_width(&self) -> AmbiguousWidth {
+        self.ambiguous_width
+    }
+
+    /// Return the display width of line `idx`, in terminal columns : wide characters like
+    /// CJK ideographs count for 2 columns, and combining marks for none.
+    /// Lines beyond the cell's height have a width of 0
+    pub fn get_line_width(&self, idx: usize) -> usize {
+        self.with_lines(|lines, _| {
+            lines.get(idx).map_or(0, |l| line_width_with(&l[..], self.ambiguous_width))
+        })
     }
 
     /// Return a copy of the full string contained in the cell
     pub fn get_content(&self) -> String {
-        self.content.join("\n")
+        self.with_lines(|lines, _| lines.join("\n"))
     }
 
     /// Print a partial cell to `out`. Since the cell may be multi-lined,
     /// `idx` is the line index to print. `col_width` is the column width used to
-    /// fill the cells with blanks so it fits in the table.
+    /// fill the cells with blanks so it fits in the table. Content wider than `col_width`
+    /// is truncated.
     /// If `ìdx` is higher than this cell's height, it will print empty content
     pub fn print<T: Write + ?Sized>(&self,
                                     out: &mut T,
@@ -182,8 +887,19 @@ impl Cell {
                                     col_width: usize,
                                     skip_right_fill: bool)
                                     -> Result<(), Error> {
-        let c = self.content.get(idx).map(|s| s.as_ref()).unwrap_or("");
-        print_align(out, self.align, c, ' ', col_width, skip_right_fill)
+        self.with_lines(|lines, _| {
+            let c = lines.get(idx).map(|s| s.as_ref()).unwrap_or("");
+            let align = self.get_line_alignment(idx);
+            let fill = self.line_fill(c);
+            let c = truncate_with(c, col_width, self.ambiguous_width);
+            let width = line_width_with(c, self.ambiguous_width);
+            print_align_measured(out, align, c, width, fill, col_width, skip_right_fill)
+        })
+    }
+
+    /// Get the character filling the blank space around line content `line`
+    fn line_fill(&self, line: &str) -> char {
+        if line.is_empty() { ' ' } else { self.fill }
     }
 
     /// Apply style then call `print` to print the cell into a terminal
@@ -193,22 +909,163 @@ impl Cell {
                                             col_width: usize,
                                             skip_right_fill: bool)
                                             -> Result<(), Error> {
+        self.apply_style(out)?;
+        if !self.print_image(out, idx, col_width, skip_right_fill)? &&
+           !self.print_spans(out, idx, col_width, skip_right_fill)? {
+            self.print(out, idx, col_width, skip_right_fill)?;
+        }
+        ignore_unsupported(out.reset())
+    }
+
+    /// Apply the style of the cell to `out`
+    fn apply_style<T: Terminal + ?Sized>(&self, out: &mut T) -> Result<(), Error> {
         for a in &self.style {
-            match out.attr(*a) {
-                Ok(..) |
-                Err(::term::Error::NotSupported) |
-                Err(::term::Error::ColorOutOfRange) => (), // Ignore unsupported atrributes
-                Err(e) => return Err(term_error_to_io_error(e)),
-            };
-        }
-        self.print(out, idx, col_width, skip_right_fill)?;
-        match out.reset() {
-            Ok(..) |
+            print_attr(out, *a)?;
+        }
+        if let Some(rgb) = self.fg_rgb {
+            print_rgb(out, rgb, true)?;
+        }
+        if let Some(rgb) = self.bg_rgb {
+            print_rgb(out, rgb, false)?;
+        }
+        Ok(())
+    }
+
+    /// Print the styled spans of line `idx` to `out` if the cell has been created with
+    /// `new_styled`, and return whether they have been printed. The style of the cell
+    /// is applied again after each span
+    fn print_spans<T: Terminal + ?Sized>(&self,
+                                         out: &mut T,
+                                         idx: usize,
+                                         col_width: usize,
+                                         skip_right_fill: bool)
+                                         -> Result<bool, Error> {
+        let spans = match self.get_styled_line(idx) {
+            Some(spans) => spans,
+            None => return Ok(false),
+        };
+        let text = self.content.get(idx).map(|s| s.as_ref()).unwrap_or("");
+        let text_width = line_width_with(truncate_with(text, col_width, self.ambiguous_width),
+                                         self.ambiguous_width);
+        let nfill = col_width - text_width;
+        let left = match self.get_line_alignment(idx) {
+            Alignment::LEFT => 0,
+            Alignment::RIGHT => nfill,
+            Alignment::CENTER => nfill / 2,
+        };
+        let fill = self.line_fill(text);
+        print_align(out, Alignment::LEFT, "", fill, left, false)?;
+        let mut remaining = text_width;
+        for span in spans {
+            if remaining == 0 {
+                break;
+            }
+            let part = truncate_with(span.get_text(), remaining, self.ambiguous_width);
+            remaining -= line_width_with(part, self.ambiguous_width);
+            if span.get_style().is_empty() {
+                out.write_all(part.as_bytes())?;
+                continue;
+            }
+            for a in span.get_style() {
+                print_attr(out, *a)?;
+            }
+            out.write_all(part.as_bytes())?;
+            ignore_unsupported(out.reset())?;
+            self.apply_style(out)?;
+        }
+        print_align(out, Alignment::LEFT, "", fill, nfill - left, skip_right_fill)?;
+        Ok(true)
+    }
+
+    /// Print line `idx` of the cell's image to `out` if the terminal can display it,
+    /// and return whether it has been printed
+    #[cfg(feature = "images")]
+    fn print_image<T: Write + ?Sized>(&self,
+                                      out: &mut T,
+                                      idx: usize,
+                                      col_width: usize,
+                                      skip_right_fill: bool)
+                                      -> Result<bool, Error> {
+        let (image, protocol) = match (self.image.as_ref(), ImageProtocol::detect()) {
+            (Some(image), Some(protocol)) => (image, protocol),
+            _ => return Ok(false),
+        };
+        if idx == 0 && col_width > 0 {
+            let escape = protocol.escape(image.get_data(), col_width, self.get_height());
+            out.write_all(escape.as_bytes())?;
+        }
+        print_align(out, self.align, "", ' ', col_width, skip_right_fill)?;
+        Ok(true)
+    }
+
+    #[cfg(not(feature = "images"))]
+    fn print_image<T: Write + ?Sized>(&self,
+                                      _out: &mut T,
+                                      _idx: usize,
+                                      _col_width: usize,
+                                      _skip_right_fill: bool)
+                                      -> Result<bool, Error> {
+        Ok(false)
+    }
+}
+
+/// Get the specifier of color `c` in style specifier strings
+fn color_spec(c: color::Color) -> String {
+    const LETTERS: &'static str = "drgybmcwDRGYBMCW";
+    match LETTERS.chars().nth(c as usize) {
+        Some(letter) => letter.to_string(),
+        None => format!("{{{}}}", c),
+    }
+}
+
+/// Apply `attr` to `out`, ignoring unsupported attributes. Colors out of the range supported
+/// by the terminal are replaced by the closest basic color
+fn print_attr<T: Terminal + ?Sized>(out: &mut T, attr: Attr) -> Result<(), Error> {
+    let fallback = match attr {
+        Attr::ForegroundColor(c) => Attr::ForegroundColor(basic_color(c)),
+        Attr::BackgroundColor(c) => Attr::BackgroundColor(basic_color(c)),
+        _ => return ignore_unsupported(out.attr(attr)),
+    };
+    match out.attr(attr) {
+        Err(::term::Error::ColorOutOfRange) => ignore_unsupported(out.attr(fallback)),
+        res => ignore_unsupported(res),
+    }
+}
+
+/// Convert `res` into an io result, ignoring errors caused by unsupported attributes
+fn ignore_unsupported(res: ::term::Result<()>) -> Result<(), Error> {
+    match res {
+        Ok(..) |
+        Err(::term::Error::NotSupported) |
+        Err(::term::Error::ColorOutOfRange) => Ok(()),
+        Err(e) => Err(term_error_to_io_error(e)),
+    }
+}
+
+/// Apply a 24-bit color to `out`, either directly with an escape sequence if the terminal
+/// supports truecolor, or by downgrading it to the closest 256 or 8 colors palette entry
+fn print_rgb<T: Terminal + ?Sized>(out: &mut T, rgb: Rgb, foreground: bool) -> Result<(), Error> {
+    if !out.supports_color() {
+        return Ok(());
+    }
+    if *TRUECOLOR {
+        let code = if foreground { 38 } else { 48 };
+        return write!(out, "\x1b[{};2;{};{};{}m", code, rgb.0, rgb.1, rgb.2);
+    }
+    for &color in &[rgb.to_256(), rgb.to_8()] {
+        let attr = if foreground {
+            Attr::ForegroundColor(color)
+        } else {
+            Attr::BackgroundColor(color)
+        };
+        match out.attr(attr) {
+            Ok(..) => return Ok(()),
             Err(::term::Error::NotSupported) |
-            Err(::term::Error::ColorOutOfRange) => Ok(()),
-            Err(e) => Err(term_error_to_io_error(e)),
+            Err(::term::Error::ColorOutOfRange) => (),
+            Err(e) => return Err(term_error_to_io_error(e)),
         }
     }
+    Ok(())
 }
 
 fn term_error_to_io_error(te: ::term::Error) -> Error {
@@ -236,8 +1093,18 @@ impl Default for Cell {
         Cell {
             content: vec!["".to_string(); 1],
             width: 0,
+            ambiguous_width: AmbiguousWidth::Narrow,
             align: Alignment::LEFT,
+            line_align: Vec::new(),
+            valign: VerticalAlignment::Top,
+            fill: ' ',
             style: Vec::new(),
+            fg_rgb: None,
+            bg_rgb: None,
+            spans: None,
+            lazy: None,
+            #[cfg(feature = "images")]
+            image: None,
         }
     }
 }
@@ -278,9 +1145,10 @@ macro_rules! cell {
 
 #[cfg(test)]
 mod tests {
-    use cell::Cell;
+    use cell::{Cell, Placeholder, Rgb, StyleSpecError, basic_color};
+    use span::{SpanRecorder, StyledSpan, StyledText};
     use utils::StringWriter;
-    use format::Alignment;
+    use format::{Alignment, AmbiguousWidth, NumberFormat, VerticalAlignment};
     use term::{Attr, color};
 
     #[test]
@@ -318,6 +1186,43 @@ mod tests {
         assert_eq!(out.as_string(), "由系统自动更新      ");
     }
 
+    #[test]
+    fn line_width() {
+        let cell = Cell::new("由系统\nCafe\u{301} crème\n");
+        assert_eq!(cell.get_height(), 2);
+        assert_eq!(cell.get_line_width(0), 6);
+        assert_eq!(cell.get_line_width(1), 10);
+        assert_eq!(cell.get_line_width(2), 0);
+        assert_eq!(cell.get_width(), 10);
+        let mut out = StringWriter::new();
+        let _ = cell.print(&mut out, 1, 12, false);
+        assert_eq!(out.as_string(), "Cafe\u{301} crème  ");
+    }
+
+    #[test]
+    fn ambiguous_width() {
+        let mut cell = Cell::new("±5\n°");
+        assert_eq!(cell.get_ambiguous_width(), AmbiguousWidth::Narrow);
+        assert_eq!(cell.get_width(), 2);
+        cell.set_ambiguous_width(AmbiguousWidth::Wide);
+        assert_eq!(cell.get_width(), 3);
+        assert_eq!(cell.get_line_width(1), 2);
+        let mut out = StringWriter::new();
+        let _ = cell.print(&mut out, 0, 4, false);
+        assert_eq!(out.as_string(), "±5 ");
+        let mut out = StringWriter::new();
+        let _ = cell.print(&mut out, 0, 2, false);
+        assert_eq!(out.as_string(), "±");
+    }
+
+    #[test]
+    fn print_truncated() {
+        let cell = Cell::new("hello world");
+        let mut out = StringWriter::new();
+        let _ = cell.print(&mut out, 0, 5, false);
+        assert_eq!(out.as_string(), "hello");
+    }
+
     #[test]
     fn align_left() {
         let cell = Cell::new_align("test", Alignment::LEFT);
@@ -370,6 +1275,179 @@ mod tests {
         assert!(cell.style.is_empty());
     }
 
+    #[test]
+    fn get_style_spec() {
+        let cell = Cell::new("test").style_spec("FrBWbuiDRkshF{19}l");
+        assert_eq!(cell.get_style_spec(), "FrBWbuiDRkshF{19}");
+        assert_eq!(Cell::new("").style_spec(&cell.get_style_spec()).style, cell.style);
+        let mut cell = Cell::new("test").with_style(Attr::Italic(false));
+        cell.align(Alignment::RIGHT);
+        assert_eq!(cell.get_style_spec(), "r");
+    }
+
+    #[test]
+    fn style_spec_valign() {
+        assert_eq!(Cell::new("test").style_spec("bv").valign, VerticalAlignment::Middle);
+        assert_eq!(Cell::new("test").style_spec("ot").valign, VerticalAlignment::Top);
+        let cell = Cell::new("test").style_spec("rob");
+        assert_eq!(cell.valign, VerticalAlignment::Bottom);
+        assert_eq!(cell.get_style_spec(), "bro");
+    }
+
+    #[test]
+    fn style_spec_attributes() {
+        let cell = Cell::new("test").style_spec("DRkshFR");
+        assert_eq!(cell.style,
+                   vec![Attr::Dim,
+                        Attr::Reverse,
+                        Attr::Blink,
+                        Attr::Standout(true),
+                        Attr::Secure,
+                        Attr::ForegroundColor(color::BRIGHT_RED)]);
+    }
+
+    #[test]
+    fn try_style_spec() {
+        let cell = Cell::new("test").try_style_spec("FrB#0a0b0cF{196}dbc").unwrap();
+        assert_eq!(cell.align, Alignment::CENTER);
+        assert_eq!(Cell::new("test").try_style_spec("bx").unwrap_err(),
+                   StyleSpecError::UnknownSpecifier { position: 1, specifier: 'x' });
+        assert_eq!(Cell::new("test").try_style_spec("FrBq").unwrap_err(),
+                   StyleSpecError::UnknownColor { position: 3, specifier: 'q' });
+        assert_eq!(Cell::new("test").try_style_spec("B{256}").unwrap_err(),
+                   StyleSpecError::InvalidColorIndex { position: 1, index: "256".to_string() });
+        let err = Cell::new("test").try_style_spec("bF").unwrap_err();
+        assert_eq!(err, StyleSpecError::DanglingPrefix { position: 1, prefix: 'F' });
+        assert_eq!(err.to_string(), "'F' at position 1 is not followed by a color");
+        // Only the first error is reported, and lenient parsing still applies the rest
+        assert_eq!(Cell::new("test").try_style_spec("xyF").unwrap_err(),
+                   StyleSpecError::UnknownSpecifier { position: 0, specifier: 'x' });
+        assert_eq!(Cell::new("test").style_spec("xbF").style, vec![Attr::Bold]);
+    }
+
+    #[test]
+    fn style_spec_256_colors() {
+        let cell = Cell::new("test").style_spec("F#1968B{22}b");
+        assert_eq!(cell.style,
+                   vec![Attr::ForegroundColor(196),
+                        Attr::BackgroundColor(22),
+                        Attr::Bold]);
+        let cell = Cell::new("test").style_spec("F#B{256}F{}c");
+        assert!(cell.style.is_empty());
+        assert_eq!(cell.align, Alignment::CENTER);
+    }
+
+    #[test]
+    fn style_spec_rgb_colors() {
+        let cell = Cell::new("test").style_spec("F#ff8800B#0a0B0cb");
+        assert_eq!(cell.get_fg_rgb(), Some(Rgb(255, 136, 0)));
+        assert_eq!(cell.get_bg_rgb(), Some(Rgb(10, 11, 12)));
+        assert_eq!(cell.style, vec![Attr::Bold]);
+        let cell = Cell::new("test").style_spec("F#19bc");
+        assert_eq!(cell.get_fg_rgb(), None);
+        assert_eq!(cell.style, vec![Attr::ForegroundColor(19), Attr::Bold]);
+        assert_eq!(cell.clone().style_spec("").get_fg_rgb(), None);
+    }
+
+    #[test]
+    fn override_colors() {
+        let mut cell = Cell::new("foo").style_spec("bFrBy").with_bg_rgb(Rgb(0, 0, 0));
+        cell.override_colors(|c| if c == color::RED || c == color::YELLOW {
+                                 Some(Rgb(1, 2, 3))
+                             } else {
+                                 None
+                             });
+        assert_eq!(cell.get_style(), &[Attr::Bold]);
+        assert_eq!(cell.get_fg_rgb(), Some(Rgb(1, 2, 3)));
+        assert_eq!(cell.get_bg_rgb(), Some(Rgb(0, 0, 0)));
+    }
+
+    #[test]
+    fn rgb_blend() {
+        let (black, white) = (Rgb(0, 0, 0), Rgb(255, 255, 255));
+        assert_eq!(black.blend(white, 0.0), black);
+        assert_eq!(black.blend(white, 1.0), white);
+        assert_eq!(black.blend(white, 0.5), Rgb(128, 128, 128));
+        assert_eq!(Rgb(10, 200, 0).blend(Rgb(20, 100, 0), 2.0), Rgb(20, 100, 0));
+    }
+
+    #[test]
+    fn basic_colors() {
+        assert_eq!(basic_color(color::RED), color::RED);
+        assert_eq!(basic_color(color::BRIGHT_CYAN), color::CYAN);
+        assert_eq!(basic_color(196), color::RED);
+        assert_eq!(basic_color(22), color::BLACK);
+        assert_eq!(basic_color(46), color::GREEN);
+        assert_eq!(basic_color(236), color::BLACK);
+        assert_eq!(basic_color(254), color::WHITE);
+        assert_eq!(Rgb::from_256(231), Rgb(255, 255, 255));
+        assert_eq!(Rgb::from_256(1000), Rgb(238, 238, 238));
+    }
+
+    #[test]
+    fn print_8_colors() {
+        let cell = Cell::new("x").style_spec("F{196}B{12}b");
+        let mut out = SpanRecorder::with_colors(8);
+        cell.print_term(&mut out, 0, 1, false).unwrap();
+        let style = vec![Attr::ForegroundColor(color::RED),
+                         Attr::BackgroundColor(color::BLUE),
+                         Attr::Bold];
+        assert_eq!(out.into_lines(), vec![vec![StyledSpan::new("x", style)]]);
+    }
+
+    #[test]
+    fn print_styled() {
+        let text = StyledText::new()
+            .with("ok", vec![Attr::ForegroundColor(color::GREEN)])
+            .with(", ", vec![])
+            .with("ko", vec![Attr::ForegroundColor(color::RED)]);
+        let mut cell = Cell::new_styled(&text).style_spec("bc");
+        assert_eq!(cell.get_width(), 6);
+        let mut out = SpanRecorder::new();
+        cell.print_term(&mut out, 0, 8, false).unwrap();
+        let bold = vec![Attr::Bold];
+        assert_eq!(out.into_lines(),
+                   vec![vec![StyledSpan::new(" ", bold.clone()),
+                             StyledSpan::new("ok", vec![Attr::Bold,
+                                                        Attr::ForegroundColor(color::GREEN)]),
+                             StyledSpan::new(", ", bold.clone()),
+                             StyledSpan::new("ko", vec![Attr::Bold,
+                                                        Attr::ForegroundColor(color::RED)]),
+                             StyledSpan::new(" ", bold.clone())]]);
+        // Truncated content and plain printing
+        cell.align(Alignment::LEFT);
+        let mut out = SpanRecorder::new();
+        cell.print_term(&mut out, 0, 3, false).unwrap();
+        assert_eq!(out.into_lines()[0][1], StyledSpan::new(",", bold));
+        let mut out = StringWriter::new();
+        cell.print(&mut out, 0, 8, false).unwrap();
+        assert_eq!(out.as_string(), "ok, ko  ");
+    }
+
+    #[test]
+    fn markup_spec() {
+        let cell = Cell::new("{red}ko{/} **ok**").style_spec("mc");
+        assert_eq!(cell.get_content(), "ko ok");
+        assert_eq!(cell.get_width(), 5);
+        assert_eq!(cell.get_alignment(), Alignment::CENTER);
+        let mut out = SpanRecorder::new();
+        cell.print_term(&mut out, 0, 5, false).unwrap();
+        assert_eq!(out.into_lines(),
+                   vec![vec![StyledSpan::new("ko", vec![Attr::ForegroundColor(color::RED)]),
+                             StyledSpan::new(" ", vec![]),
+                             StyledSpan::new("ok", vec![Attr::Bold])]]);
+        assert_eq!(Cell::new("**a**").style_spec("b").get_content(), "**a**");
+    }
+
+    #[test]
+    fn rgb_downgrade() {
+        assert_eq!(Rgb(255, 0, 0).to_256(), 196);
+        assert_eq!(Rgb(95, 135, 175).to_256(), 67);
+        assert_eq!(Rgb(128, 128, 128).to_256(), 244);
+        assert_eq!(Rgb(255, 255, 0).to_8(), color::YELLOW);
+        assert_eq!(Rgb(0, 0, 200).to_8(), color::BLUE);
+    }
+
     #[test]
     fn reset_style() {
         let mut cell = Cell::new("test")
@@ -385,6 +1463,52 @@ mod tests {
         assert_eq!(cell.align, Alignment::LEFT);
     }
 
+    #[test]
+    fn line_alignment() {
+        let mut cell = Cell::new_align("name\nid", Alignment::CENTER)
+            .with_line_align(0, Alignment::LEFT);
+        cell.align_line(1, Alignment::RIGHT);
+        assert_eq!(cell.get_line_alignment(0), Alignment::LEFT);
+        assert_eq!(cell.get_line_alignment(1), Alignment::RIGHT);
+        assert_eq!(cell.get_line_alignment(2), Alignment::CENTER);
+        let mut out = StringWriter::new();
+        let _ = cell.print(&mut out, 0, 6, false);
+        let _ = cell.print(&mut out, 1, 6, false);
+        assert_eq!(out.as_string(), "name      id");
+        cell.reset_style();
+        assert_eq!(cell.get_line_alignment(1), Alignment::LEFT);
+    }
+
+    #[test]
+    fn from_option() {
+        let placeholder = Placeholder::default();
+        assert_eq!(placeholder.get_text(), "-");
+        assert!(!placeholder.is_dim());
+        let cell = Cell::from_option(None::<u32>, &placeholder);
+        assert_eq!(cell.get_content(), "-");
+        assert!(cell.get_style().is_empty());
+        let cell = Cell::from_option(Some("x"), &Placeholder::new("").dim(true));
+        assert_eq!(cell.get_content(), "x");
+        assert!(cell.get_style().is_empty());
+        let cell = Cell::from_option(None::<&str>, &Placeholder::new("").dim(true));
+        assert_eq!(cell.get_width(), 0);
+        assert_eq!(cell.get_style(), &[Attr::Dim]);
+    }
+
+    #[test]
+    fn numbers() {
+        let format = NumberFormat::new().decimals(1);
+        let cell = Cell::from_f64_with(1234.56, &format);
+        assert_eq!(cell.get_content(), "1,234.6");
+        assert_eq!(cell.get_alignment(), Alignment::RIGHT);
+        assert_eq!(Cell::from_i64_with(-999, &format).get_content(), "-999.0");
+        let format = NumberFormat::new().thousands_separator(None);
+        assert_eq!(Cell::from_i64_with(1234567, &format).get_content(), "1234567");
+        assert_eq!(Cell::from_f64_with(0.25, &format).get_content(), "0.25");
+        let cell = Cell::from_f64_with(-123456.0, &NumberFormat::new());
+        assert_eq!(cell.get_content(), "-123,456");
+    }
+
     #[test]
     fn default_empty_cell() {
         let cell = Cell::default();
@@ -395,4 +1519,14 @@ mod tests {
         assert_eq!(cell.get_height(), 1);
         assert_eq!(cell.get_width(), 0);
     }
+
+    #[cfg(feature = "images")]
+    #[test]
+    fn with_image() {
+        let cell = Cell::new("[logo]").with_image(vec![1, 2, 3]);
+        assert_eq!(cell.get_image().map(|i| i.get_data()), Some(&[1, 2, 3][..]));
+        let mut out = Vec::new();
+        cell.print(&mut out, 0, 8, false).unwrap();
+        assert_eq!(out, b"[logo]  ");
+    }
 }
@@ -0,0 +1,283 @@
+//! This module contains definition of column-major tables, suited for analytics-sized tables
+use std::io::{self, Write, Error};
+use std::slice::Iter;
+use std::fmt;
+
+use term::{Terminal, stdout};
+
+use super::{Table, print_rows_with_width};
+use super::row::Row;
+use super::cell::Cell;
+use super::format::{TableFormat, PrintLimits, consts};
+use super::utils::{StringWriter, LineCounter, stdout_colorized, write_lines};
+
+/// A printable table storing its cells column by column.
+///
+/// Cells of a same column are contiguous in memory, which makes column width computation
+/// and column iteration cache-friendly on big tables. Appending rows is slightly more expensive
+/// than with `Table` though. All columns always have the same length : short rows are padded
+/// with empty cells.
+///
+/// # Examples
+/// ```rust
+/// # #[macro_use] extern crate prettytable;
+/// use prettytable::columnar::ColumnTable;
+/// # fn main() {
+/// let mut table = ColumnTable::new();
+/// table.add_row(row!["a", 1]);
+/// table.add_row(row!["b", 2]);
+/// let total: i32 = table.column_iter(1).map(|c| c.get_content().parse::<i32>().unwrap()).sum();
+/// assert_eq!(total, 3);
+/// table.printstd();
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct ColumnTable {
+    format: TableFormat,
+    titles: Option<Row>,
+    columns: Vec<Vec<Cell>>,
+    len: usize,
+}
+
+impl ColumnTable {
+    /// Create an empty table
+    pub fn new() -> ColumnTable {
+        ColumnTable {
+            format: *consts::FORMAT_DEFAULT,
+            titles: None,
+            columns: Vec::new(),
+            len: 0,
+        }
+    }
+
+    /// Change the table format. Eg : Separators
+    pub fn set_format(&mut self, format: TableFormat) {
+        self.format = format;
+    }
+
+    /// Get a mutable reference to the internal format
+    pub fn get_format(&mut self) -> &mut TableFormat {
+        &mut self.format
+    }
+
+    /// Set the optional title lines
+    pub fn set_titles(&mut self, titles: Row) {
+        self.titles = Some(titles);
+    }
+
+    /// Unset the title line
+    pub fn unset_titles(&mut self) {
+        self.titles = None;
+    }
+
+    /// Get the number of rows
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Check if the table is empty
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Get the number of column
+    pub fn get_column_num(&self) -> usize {
+        self.columns.len()
+    }
+
+    /// Append a row in the table, splitting its cells into the columns.
+    /// If the row is longer than the number of columns, new columns are added
+    pub fn add_row(&mut self, row: Row) {
+        let len = self.len;
+        while self.columns.len() < row.len() {
+            self.columns.push(vec![Cell::default(); len]);
+        }
+        let mut cells = row.iter();
+        for column in &mut self.columns {
+            column.push(cells.next().cloned().unwrap_or_else(Cell::default));
+        }
+        self.len += 1;
+    }
+
+    /// Append a column in the table. If the column is longer than the number of rows,
+    /// all other columns are padded with empty cells, and if it is shorter, it is padded itself
+    pub fn add_column(&mut self, mut cells: Vec<Cell>) {
+        if cells.len() > self.len {
+            self.len = cells.len();
+            for column in &mut self.columns {
+                column.resize(cells.len(), Cell::default());
+            }
+        }
+        cells.resize(self.len, Cell::default());
+        self.columns.push(cells);
+    }
+
+    /// Get the cell at `(row, column)`
+    pub fn get_cell(&self, row: usize, column: usize) -> Option<&Cell> {
+        self.columns.get(column).and_then(|c| c.get(row))
+    }
+
+    /// Get the mutable cell at `(row, column)`
+    pub fn get_mut_cell(&mut self, row: usize, column: usize) -> Option<&mut Cell> {
+        self.columns.get_mut(column).and_then(|c| c.get_mut(row))
+    }
+
+    /// Get all the cells of column `column` as a slice
+    pub fn get_column(&self, column: usize) -> Option<&[Cell]> {
+        self.columns.get(column).map(|c| &c[..])
+    }
+
+    /// Returns an iterator over the immutable cells of the column specified by `column`.
+    /// The iterator is empty if the column does not exist
+    pub fn column_iter(&self, column: usize) -> Iter<Cell> {
+        self.get_column(column).unwrap_or(&[]).iter()
+    }
+
+    /// Internal only. Build row `idx` from the columns
+    fn build_row(&self, idx: usize) -> Row {
+        Row::new(self.columns.iter().map(|c| c[idx].clone()).collect())
+    }
+
+    /// Get the width of all columns, and return a vector
+    /// with the result for each column
+    fn get_all_column_width(&self) -> Vec<usize> {
+        self.columns
+            .iter()
+            .enumerate()
+            .map(|(i, column)| {
+                let title = match self.titles {
+                    Some(ref t) => t.get_cell_width(i),
+                    None => 0,
+                };
+                column.iter().map(|c| c.get_width()).fold(title, ::std::cmp::max)
+            })
+            .collect()
+    }
+
+    /// Convert this table into a row-major `Table`
+    pub fn to_table(&self) -> Table {
+        let mut table = Table::init((0..self.len).map(|i| self.build_row(i)).collect());
+        table.set_format(self.format);
+        if let Some(ref t) = self.titles {
+            table.set_titles(t.clone());
+        }
+        table
+    }
+
+    /// Internal only
+    fn __print<'w, T: Write + ?Sized, F>(&self,
+                                         out: &'w mut T,
+                                         limits: &PrintLimits,
+                                         f: F)
+                                         -> Result<(), Error>
+        where F: Fn(&Row, &mut LineCounter<'w, T>, &TableFormat, &[usize]) -> Result<(), Error>
+    {
+        print_rows_with_width(&mut LineCounter::new(out),
+                              &self.format,
+                              &self.titles,
+                              &None,
+                              &None,
+                              (0..self.len).map(|i| self.build_row(i)),
+                              self.get_all_column_width(),
+                              limits,
+                              f)
+    }
+
+    /// Print the table to `out`
+    pub fn print<T: Write + ?Sized>(&self, out: &mut T) -> Result<(), Error> {
+        self.__print(out, &PrintLimits::new(), Row::print)
+    }
+
+    /// Print the table to terminal `out`, applying styles when needed
+    pub fn print_term<T: Terminal + ?Sized>(&self, out: &mut T) -> Result<(), Error> {
+        self.__print(out, &PrintLimits::new(), Row::print_term)
+    }
+
+    /// Print the table to standard output. See `TableSlice::print_tty` for details
+    /// # Panic
+    /// Panic if writing to standard output fails
+    pub fn print_tty(&self, force_colorize: bool) {
+        let limits = self.format.get_limits();
+        let r = match (stdout(), stdout_colorized() || force_colorize) {
+            (Some(mut o), true) => self.__print(&mut *o, &limits, Row::print_term),
+            _ => self.__print(&mut io::stdout(), &limits, Row::print),
+        };
+        if let Err(e) = r {
+            panic!("Cannot print table to standard output : {}", e);
+        }
+    }
+
+    /// Print the table to standard output. See `TableSlice::printstd` for details
+    /// # Panic
+    /// Panic if writing to standard output fails
+    pub fn printstd(&self) {
+        self.print_tty(false);
+    }
+}
+
+impl Default for ColumnTable {
+    fn default() -> ColumnTable {
+        ColumnTable::new()
+    }
+}
+
+impl<'a> From<&'a Table> for ColumnTable {
+    fn from(table: &'a Table) -> ColumnTable {
+        let mut ct = ColumnTable::new();
+        ct.set_format(table.format);
+        if let Some(ref t) = table.titles {
+            ct.set_titles(t.clone());
+        }
+        for row in table.row_iter() {
+            ct.add_row(row.clone());
+        }
+        ct
+    }
+}
+
+impl fmt::Display for ColumnTable {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        let mut writer = StringWriter::new();
+        if self.print(&mut writer).is_err() {
+            return Err(fmt::Error);
+        }
+        write_lines(fmt, writer.as_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn add_row_and_column() {
+        let mut table = ColumnTable::new();
+        assert!(table.is_empty());
+        table.add_row(Row::new(vec![Cell::new("a")]));
+        table.add_row(Row::new(vec![Cell::new("b"), Cell::new("c")]));
+        assert_eq!(table.len(), 2);
+        assert_eq!(table.get_column_num(), 2);
+        assert_eq!(table.get_cell(0, 1).unwrap().get_content(), "");
+        assert_eq!(table.get_cell(1, 1).unwrap().get_content(), "c");
+        table.add_column(vec![Cell::new("d"), Cell::new("e"), Cell::new("f")]);
+        assert_eq!(table.len(), 3);
+        assert_eq!(table.get_column(0).unwrap().len(), 3);
+        assert_eq!(table.column_iter(2).map(|c| c.get_content()).collect::<Vec<_>>(),
+                   vec!["d", "e", "f"]);
+        assert_eq!(table.column_iter(12).count(), 0);
+        table.get_mut_cell(2, 0).unwrap().align(::format::Alignment::RIGHT);
+        assert!(table.get_mut_cell(3, 0).is_none());
+    }
+
+    #[test]
+    fn same_output_as_table() {
+        let mut table = Table::new();
+        table.set_titles(Row::new(vec![Cell::new("t1"), Cell::new("t2"), Cell::new("t3")]));
+        table.add_row(Row::new(vec![Cell::new("a"), Cell::new("bc"), Cell::new("def")]));
+        table.add_row(Row::new(vec![Cell::new("def"), Cell::new("bc")]));
+        let ct = ColumnTable::from(&table);
+        assert_eq!(table.to_string(), ct.to_string());
+        assert_eq!(table.to_string(), ct.to_table().to_string());
+        assert_eq!(format!("{:*<20.8}", table), format!("{:*<20.8}", ct));
+    }
+}
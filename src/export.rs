@@ -0,0 +1,107 @@
+//! This module contains options to customise the CSV export of tables
+use std::io::Write;
+
+use csv::{self, QuoteStyle, Terminator, WriterBuilder};
+
+/// Options for exporting a table to CSV with `to_csv_opts`
+///
+/// # Examples
+/// ```rust
+/// # extern crate csv;
+/// # #[macro_use] extern crate prettytable;
+/// use prettytable::export::CsvOptions;
+/// # fn main() {
+/// let mut table = table!(["1", "2"]);
+/// table.set_titles(row!["a", "b c"]);
+/// let options = CsvOptions::new().delimiter(b';').quote_style(csv::QuoteStyle::Always);
+/// let writer = table.to_csv_opts(Vec::new(), &options).unwrap();
+/// let out = String::from_utf8(writer.into_inner().unwrap()).unwrap();
+/// assert_eq!(out, "\"a\";\"b c\"\n\"1\";\"2\"\n");
+///
+/// let writer = table.to_csv_opts(Vec::new(), &options.titles(false)).unwrap();
+/// assert_eq!(writer.into_inner().unwrap(), b"\"1\";\"2\"\n");
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct CsvOptions {
+    /// Write the titles before the rows
+    titles: bool,
+    /// Field delimiter
+    delimiter: u8,
+    /// When fields are quoted
+    quote_style: QuoteStyle,
+    /// Record terminator
+    terminator: Terminator,
+}
+
+impl Default for CsvOptions {
+    fn default() -> CsvOptions {
+        CsvOptions {
+            titles: true,
+            delimiter: b',',
+            quote_style: QuoteStyle::Necessary,
+            terminator: Terminator::Any(b'\n'),
+        }
+    }
+}
+
+impl CsvOptions {
+    /// Create new `CsvOptions`, writing titles, comma delimited fields quoted when
+    /// necessary, and `\n` terminated records
+    pub fn new() -> CsvOptions {
+        CsvOptions::default()
+    }
+
+    /// Set whether the titles are written before the rows. Can be chained
+    pub fn titles(mut self, titles: bool) -> CsvOptions {
+        self.titles = titles;
+        self
+    }
+
+    /// Set the field delimiter. Can be chained
+    pub fn delimiter(mut self, delimiter: u8) -> CsvOptions {
+        self.delimiter = delimiter;
+        self
+    }
+
+    /// Set when fields are quoted. Can be chained
+    pub fn quote_style(mut self, style: QuoteStyle) -> CsvOptions {
+        self.quote_style = style;
+        self
+    }
+
+    /// Set the record terminator, eg: `Terminator::CRLF`. Can be chained
+    pub fn terminator(mut self, terminator: Terminator) -> CsvOptions {
+        self.terminator = terminator;
+        self
+    }
+
+    /// Check whether the titles are written
+    pub fn get_titles(&self) -> bool {
+        self.titles
+    }
+
+    /// Get the field delimiter
+    pub fn get_delimiter(&self) -> u8 {
+        self.delimiter
+    }
+
+    /// Get when fields are quoted
+    pub fn get_quote_style(&self) -> QuoteStyle {
+        self.quote_style
+    }
+
+    /// Get the record terminator
+    pub fn get_terminator(&self) -> Terminator {
+        self.terminator
+    }
+
+    /// Build a `csv::Writer` writing to `out` with these options
+    pub fn writer<W: Write>(&self, out: W) -> csv::Writer<W> {
+        WriterBuilder::new()
+            .delimiter(self.delimiter)
+            .quote_style(self.quote_style)
+            .terminator(self.terminator)
+            .from_writer(out)
+    }
+}
@@ -0,0 +1,228 @@
+//! This module contains utilities to import and export tables as fixed-width text files
+use std::cmp;
+use std::io::{Write, Error};
+
+
+use super::{Table, TableSlice};
+use super::row::Row;
+use super::cell::Cell;
+use super::format::{Alignment, line_width};
+use super::import::{ImportOptions, ImportError};
+use super::utils::{print_align, truncate, NEWLINE};
+
+/// Position and width of a column in a fixed-width text file
+#[derive(Clone, Debug, PartialEq)]
+pub struct FixedWidthColumn {
+    name: Option<String>,
+    offset: usize,
+    width: usize,
+}
+
+impl FixedWidthColumn {
+    /// Create a new column starting at `offset` in each line, `width` characters wide,
+    /// with an optional title
+    pub fn new(name: Option<&str>, offset: usize, width: usize) -> FixedWidthColumn {
+        FixedWidthColumn {
+            name: name.map(|n| n.to_string()),
+            offset: offset,
+            width: width,
+        }
+    }
+
+    /// Get the title of the column, if the table has titles
+    pub fn get_name(&self) -> Option<&str> {
+        self.name.as_ref().map(|n| &n[..])
+    }
+
+    /// Get the offset of the first character of the column in each line
+    pub fn get_offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Get the width of the column
+    pub fn get_width(&self) -> usize {
+        self.width
+    }
+}
+
+/// Layout of a fixed-width text file written by `to_fixed_width`, so that parsers
+/// of the file can be configured without measuring it
+#[derive(Clone, Debug, PartialEq)]
+pub struct FixedWidthSpec {
+    columns: Vec<FixedWidthColumn>,
+    header: bool,
+}
+
+impl FixedWidthSpec {
+    /// Create a new layout made of `columns`, with titles on the first line if `header` is true
+    pub fn new(columns: Vec<FixedWidthColumn>, header: bool) -> FixedWidthSpec {
+        FixedWidthSpec {
+            columns: columns,
+            header: header,
+        }
+    }
+
+    /// Get the layout of each column
+    pub fn get_columns(&self) -> &[FixedWidthColumn] {
+        &self.columns
+    }
+
+    /// Check if the first line of the file contains the titles
+    pub fn has_header(&self) -> bool {
+        self.header
+    }
+
+    /// Get the width of each line, without the line ending
+    pub fn get_record_width(&self) -> usize {
+        self.columns.last().map_or(0, |c| c.offset + c.width)
+    }
+}
+
+impl Table {
+    /// Create a table from the content of a fixed-width text file laid out according to `spec`.
+    /// Offsets and widths are counted in bytes. Padding spaces are removed, and cells are
+    /// decoded and cleaned up according to `options`. Empty lines are ignored
+    ///
+    /// # Examples
+    /// ```rust
+    /// use prettytable::Table;
+    /// use prettytable::fixed::{FixedWidthColumn, FixedWidthSpec};
+    /// use prettytable::import::ImportOptions;
+    ///
+    /// let spec = FixedWidthSpec::new(vec![FixedWidthColumn::new(None, 0, 3),
+    ///                                     FixedWidthColumn::new(None, 4, 5)],
+    ///                                true);
+    /// let data = b"id  name\n  1 foo  \n 12 \xffbar\n";
+    /// let err = Table::from_fixed_width(data, &spec, &ImportOptions::new()).unwrap_err();
+    /// assert!(err.to_string().starts_with("row 2, column 1: invalid UTF-8"));
+    /// ```
+    pub fn from_fixed_width(data: &[u8],
+                            spec: &FixedWidthSpec,
+                            options: &ImportOptions)
+                            -> Result<Table, ImportError> {
+        let mut table = Table::new();
+        for (i, line) in data.split(|&b| b == b'\n').enumerate() {
+            let line = if line.last() == Some(&b'\r') { &line[..line.len() - 1] } else { line };
+            if line.is_empty() {
+                continue;
+            }
+            let mut cells = Vec::with_capacity(spec.columns.len());
+            for (j, column) in spec.columns.iter().enumerate() {
+                let start = cmp::min(column.offset, line.len());
+                let end = cmp::min(column.offset + column.width, line.len());
+                let content = options.decode(&line[start..end], i, j)?;
+                cells.push(Cell::new(content.trim_matches(' ')));
+            }
+            if spec.header && i == 0 {
+                table.set_titles(Row::new(cells));
+            } else {
+                table.add_row(Row::new(cells));
+            }
+        }
+        Ok(table)
+    }
+
+    /// Write the table as a fixed-width text file. See `TableSlice::to_fixed_width`
+    pub fn to_fixed_width<W: Write + ?Sized>(&self, out: &mut W) -> Result<FixedWidthSpec, Error> {
+        self.as_slice().to_fixed_width(out)
+    }
+}
+
+impl<'a> TableSlice<'a> {
+    /// Write the table as a fixed-width text file, and return the layout of its columns.
+    /// Each row is written on a single line, starting with the titles if any, and columns
+    /// are padded with spaces and separated by a single space. Multi-line cells are joined
+    /// on a single line. Offsets and widths are counted in characters, which are also bytes
+    /// as long as the content is ASCII. Column widths set with `set_column_width` are applied,
+    /// and content wider than a fixed width column is truncated
+    pub fn to_fixed_width<W: Write + ?Sized>(&self, out: &mut W) -> Result<FixedWidthSpec, Error> {
+        let rows: Vec<Vec<(String, _)>> = self.titles
+            .iter()
+            .chain(self.rows.iter())
+            .map(|r| {
+                     r.iter()
+                         .map(|c| (c.get_content().replace('\n', " "), c.get_alignment()))
+                         .collect()
+                 })
+            .collect();
+        let colnum = rows.iter().map(|r| r.len()).max().unwrap_or(0);
+        let mut columns = Vec::with_capacity(colnum);
+        let mut offset = 0;
+        for i in 0..colnum {
+            let width = rows.iter()
+                .filter_map(|r| r.get(i))
+                .fold(1, |w, &(ref c, _)| cmp::max(w, line_width(&c[..])));
+            let width = self.widths.get(i).map_or(width, |w| w.apply(width));
+            columns.push(FixedWidthColumn {
+                             name: self.titles
+                                 .as_ref()
+                                 .and_then(|t| t.get_cell(i))
+                                 .map(|c| c.get_content()),
+                             offset: offset,
+                             width: width,
+                         });
+            offset += width + 1;
+        }
+        for row in &rows {
+            for (i, column) in columns.iter().enumerate() {
+                if i > 0 {
+                    out.write_all(b" ")?;
+                }
+                match row.get(i) {
+                    Some(&(ref content, align)) => {
+                        let content = truncate(content, column.width);
+                        print_align(out, align, content, ' ', column.width, false)?
+                    }
+                    None => print_align(out, Alignment::LEFT, "", ' ', column.width, false)?,
+                }
+            }
+            out.write_all(NEWLINE)?;
+        }
+        Ok(FixedWidthSpec {
+               columns: columns,
+               header: self.titles.is_some(),
+           })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use row::Row;
+    use cell::Cell;
+
+    #[test]
+    fn to_fixed_width() {
+        let mut table = Table::new();
+        table.set_titles(Row::from(vec!["id", "name"]));
+        table.add_row(Row::new(vec![Cell::new_align("1", Alignment::RIGHT), Cell::new("a\nb")]));
+        table.add_row(Row::new(vec![Cell::new("123")]));
+        let mut out = Vec::new();
+        let spec = table.to_fixed_width(&mut out).unwrap();
+        let text = String::from_utf8(out).unwrap().replace("\r\n", "\n");
+        assert_eq!(text, "id  name\n  1 a b \n123     \n");
+        assert!(spec.has_header());
+        assert_eq!(spec.get_record_width(), 8);
+        let columns = spec.get_columns();
+        assert_eq!((columns[0].get_name(), columns[0].get_offset(), columns[0].get_width()),
+                   (Some("id"), 0, 3));
+        assert_eq!((columns[1].get_name(), columns[1].get_offset(), columns[1].get_width()),
+                   (Some("name"), 4, 4));
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut table = Table::new();
+        table.set_titles(Row::from(vec!["id", "name"]));
+        table.add_row(Row::from(vec!["1", "foo bar"]));
+        table.add_row(Row::from(vec!["", "baz"]));
+        let mut out = Vec::new();
+        let spec = table.to_fixed_width(&mut out).unwrap();
+        let imported = Table::from_fixed_width(&out, &spec, &ImportOptions::new()).unwrap();
+        assert_eq!(imported.to_string(), table.to_string());
+        let spec = FixedWidthSpec::new(vec![FixedWidthColumn::new(None, 0, 3)], false);
+        let imported = Table::from_fixed_width(b"a\n\nbcdef", &spec, &ImportOptions::new());
+        let expected = Table::init(vec![Row::from(vec!["a"]), Row::from(vec!["bcd"])]);
+        assert_eq!(imported.unwrap().to_string(), expected.to_string());
+    }
+}
@@ -1,10 +1,16 @@
 //! Define table formatting utilities
 
 use std::io::{Write, Error};
+use std::ops::Range;
 
 use encode_unicode::Utf8Char;
+use unicode_width::{UnicodeWidthStr, UnicodeWidthChar};
+#[cfg(feature = "unicode-segmentation")]
+use unicode_segmentation::UnicodeSegmentation;
+use term::{Attr, color};
 
 use super::utils::NEWLINE;
+use super::cell::Rgb;
 
 /// Alignment for cell's content
 #[derive(Clone, Debug, PartialEq, Copy)]
@@ -17,6 +23,113 @@ pub enum Alignment {
     RIGHT,
 }
 
+/// Vertical alignment for cell's content, when the cell is shorter than its row
+#[derive(Clone, Debug, PartialEq, Copy)]
+pub enum VerticalAlignment {
+    /// Align on the first line of the row
+    Top,
+    /// Align in the middle of the row
+    Middle,
+    /// Align on the last line of the row
+    Bottom,
+}
+
+impl VerticalAlignment {
+    /// Return the number of empty lines to print above a content of `height` lines
+    /// in a row of `row_height` lines
+    pub fn offset(&self, height: usize, row_height: usize) -> usize {
+        let free = if row_height > height { row_height - height } else { 0 };
+        match *self {
+            VerticalAlignment::Top => 0,
+            VerticalAlignment::Middle => free / 2,
+            VerticalAlignment::Bottom => free,
+        }
+    }
+}
+
+impl Default for VerticalAlignment {
+    fn default() -> VerticalAlignment {
+        VerticalAlignment::Top
+    }
+}
+
+/// Position of a table caption, relatively to the table frame
+#[derive(Clone, Debug, PartialEq, Copy)]
+pub enum CaptionPosition {
+    /// Caption is printed above the table
+    Above,
+    /// Caption is printed below the table
+    Below,
+}
+
+/// Width constraint of a column
+#[derive(Clone, Debug, PartialEq, Copy)]
+pub enum Width {
+    /// Width is computed from the cells content
+    Auto,
+    /// Width is at least the given number of characters,
+    /// or more if required by the cells content
+    Min(usize),
+    /// Width is exactly the given number of characters.
+    /// Content wider than the column is truncated
+    Fixed(usize),
+    /// Width is computed from the cells content, but is at most the given number
+    /// of characters. Content wider than the column is truncated
+    Max(usize),
+}
+
+impl Width {
+    /// Apply this constraint to the width `natural` computed from the cells content
+    pub fn apply(&self, natural: usize) -> usize {
+        match *self {
+            Width::Auto => natural,
+            Width::Min(min) => ::std::cmp::max(min, natural),
+            Width::Fixed(width) => width,
+            Width::Max(max) => ::std::cmp::min(max, natural),
+        }
+    }
+}
+
+impl Default for Width {
+    fn default() -> Width {
+        Width::Auto
+    }
+}
+
+/// Policy telling which columns absorb the remainder when extra width is distributed evenly
+/// among columns but cannot be divided exactly
+#[derive(Clone, Debug, PartialEq, Copy)]
+pub enum RemainderPolicy {
+    /// The leftmost column absorbs the whole remainder
+    Leftmost,
+    /// The rightmost column absorbs the whole remainder
+    Rightmost,
+    /// The remainder is spread one character per column, starting from the left
+    RoundRobin,
+}
+
+impl Default for RemainderPolicy {
+    fn default() -> RemainderPolicy {
+        RemainderPolicy::RoundRobin
+    }
+}
+
+/// Side towards which centered content is shifted when the free space around it is odd,
+/// and cannot be split evenly
+#[derive(Clone, Debug, PartialEq, Copy)]
+pub enum CenterBias {
+    /// The extra space goes after the content
+    Left,
+    /// The extra space goes before the content
+    Right,
+}
+
+impl Default for CenterBias {
+    fn default() -> CenterBias {
+        CenterBias::Left
+    }
+}
+
 /// Position of a line separator in a table
 #[derive(Clone, Debug, PartialEq, Copy)]
 pub enum LinePosition {
@@ -112,6 +225,321 @@ impl Default for LineSeparator {
     }
 }
 
+/// Hard limits applied when printing a table to standard output, so that printing
+/// a huge table by accident does not flood the terminal.
+///
+/// Limits are only enforced by `printstd()` and `print_tty()`. Explicit exports
+/// (`print()`, `print_term()`, CSV, ...) always render the whole table.
+#[derive(Clone, Debug, PartialEq, Copy, Default)]
+pub struct PrintLimits {
+    /// Maximum number of data rows
+    max_rows: Option<usize>,
+    /// Maximum number of columns
+    max_columns: Option<usize>,
+    /// Maximum number of bytes of the rendered table
+    max_bytes: Option<usize>,
+    /// Maximum width of the table
+    max_width: Option<usize>,
+}
+
+impl PrintLimits {
+    /// Create a new `PrintLimits` with no limit set
+    pub fn new() -> PrintLimits {
+        PrintLimits::default()
+    }
+
+    /// Set the maximum number of data rows to print. Can be chained
+    pub fn rows(mut self, max: usize) -> PrintLimits {
+        self.max_rows = Some(max);
+        self
+    }
+
+    /// Set the maximum number of columns to print. Can be chained
+    pub fn columns(mut self, max: usize) -> PrintLimits {
+        self.max_columns = Some(max);
+        self
+    }
+
+    /// Set the maximum number of bytes of the rendered table, borders, titles and caption
+    /// included. No more data row is printed once `max` bytes have been written: the row
+    /// reaching the limit is completed, and the bottom border, caption and truncation marker
+    /// are still printed. Styles are not counted. Can be chained
+    pub fn bytes(mut self, max: usize) -> PrintLimits {
+        self.max_bytes = Some(max);
+        self
+    }
+
+    /// Set the maximum width of the table, including borders. Widest columns are
+    /// shrunk until the table fits, and their content is truncated. Can be chained
+    pub fn width(mut self, max: usize) -> PrintLimits {
+        self.max_width = Some(max);
+        self
+    }
+
+    /// Get the maximum number of data rows, if any
+    pub fn get_rows(&self) -> Option<usize> {
+        self.max_rows
+    }
+
+    /// Get the maximum number of columns, if any
+    pub fn get_columns(&self) -> Option<usize> {
+        self.max_columns
+    }
+
+    /// Get the maximum number of bytes of the rendered table, if any
+    pub fn get_bytes(&self) -> Option<usize> {
+        self.max_bytes
+    }
+
+    /// Get the maximum width of the table, if any
+    pub fn get_width(&self) -> Option<usize> {
+        self.max_width
+    }
+
+    /// Print the truncation marker telling how many rows and columns were not printed.
+    /// Nothing is printed if nothing has been truncated
+    pub fn print_marker<T: Write + ?Sized>(&self,
+                                           out: &mut T,
+                                           indent: usize,
+                                           hidden_rows: usize,
+                                           hidden_cols: usize)
+                                           -> Result<(), Error> {
+        let mut parts = Vec::new();
+        if hidden_rows > 0 {
+            parts.push(format!("{} more row{}", hidden_rows, if hidden_rows > 1 { "s" } else { "" }));
+        }
+        if hidden_cols > 0 {
+            parts.push(format!("{} more column{}", hidden_cols, if hidden_cols > 1 { "s" } else { "" }));
+        }
+        if parts.is_empty() {
+            return Ok(());
+        }
+        out.write_all(&vec![b' '; indent])?;
+        out.write_all(format!("... {} not shown", parts.join(", ")).as_bytes())?;
+        out.write_all(NEWLINE)
+    }
+}
+
+/// How numbers are rendered by `Cell::from_f64_with`, `Cell::from_i64_with`
+/// and `Table::format_numbers`
+///
+/// # Examples
+/// ```rust
+/// use prettytable::format::NumberFormat;
+///
+/// let format = NumberFormat::new().decimals(2);
+/// assert_eq!(format.format(-1234567.891), "-1,234,567.89");
+/// assert_eq!(format.format_int(1000), "1,000.00");
+/// let format = NumberFormat::new().thousands_separator(Some(' ')).decimal_point(',');
+/// assert_eq!(format.format(12345.5), "12 345,5");
+/// ```
+#[derive(Clone, Debug, PartialEq, Copy)]
+pub struct NumberFormat {
+    /// Character inserted between groups of 3 digits of the integer part
+    thousands_separator: Option<char>,
+    /// Character separating the integer part from the decimals
+    decimal_point: char,
+    /// Number of decimal places
+    decimals: Option<usize>,
+}
+
+impl Default for NumberFormat {
+    fn default() -> NumberFormat {
+        NumberFormat {
+            thousands_separator: Some(','),
+            decimal_point: '.',
+            decimals: None,
+        }
+    }
+}
+
+impl NumberFormat {
+    /// Create a new `NumberFormat`, separating thousands with `,` and decimals with `.`,
+    /// and printing as many decimal places as needed
+    pub fn new() -> NumberFormat {
+        NumberFormat::default()
+    }
+
+    /// Set the character inserted between groups of 3 digits, or `None` to not group
+    /// digits. Can be chained
+    pub fn thousands_separator(mut self, separator: Option<char>) -> NumberFormat {
+        self.thousands_separator = separator;
+        self
+    }
+
+    /// Set the character separating the integer part from the decimals. Can be chained
+    pub fn decimal_point(mut self, point: char) -> NumberFormat {
+        self.decimal_point = point;
+        self
+    }
+
+    /// Set the number of decimal places, values being rounded. Can be chained
+    pub fn decimals(mut self, decimals: usize) -> NumberFormat {
+        self.decimals = Some(decimals);
+        self
+    }
+
+    /// Get the character inserted between groups of 3 digits, if any
+    pub fn get_thousands_separator(&self) -> Option<char> {
+        self.thousands_separator
+    }
+
+    /// Get the character separating the integer part from the decimals
+    pub fn get_decimal_point(&self) -> char {
+        self.decimal_point
+    }
+
+    /// Get the number of decimal places, if fixed
+    pub fn get_decimals(&self) -> Option<usize> {
+        self.decimals
+    }
+
+    /// Render `value`. Infinite and NaN values are rendered as is
+    pub fn format(&self, value: f64) -> String {
+        if !value.is_finite() {
+            return value.to_string();
+        }
+        let text = match self.decimals {
+            Some(decimals) => format!("{:.*}", decimals, value),
+            None => value.to_string(),
+        };
+        self.render(&text)
+    }
+
+    /// Render the integer `value`, followed by zeros if decimal places are set
+    pub fn format_int(&self, value: i64) -> String {
+        let mut text = value.to_string();
+        match self.decimals {
+            Some(decimals) if decimals > 0 => {
+                text.push('.');
+                text.extend(::std::iter::repeat('0').take(decimals));
+            }
+            _ => (),
+        }
+        self.render(&text)
+    }
+
+    /// Group the digits and replace the decimal point of `text`, a number printed by Rust
+    fn render(&self, text: &str) -> String {
+        let (sign, text) = if text.starts_with('-') { ("-", &text[1..]) } else { ("", text) };
+        let (int, frac) = match text.find('.') {
+            Some(i) => (&text[..i], Some(&text[i + 1..])),
+            None => (text, None),
+        };
+        let mut out = sign.to_string();
+        for (i, c) in int.chars().enumerate() {
+            if i > 0 && (int.len() - i) % 3 == 0 {
+                if let Some(separator) = self.thousands_separator {
+                    out.push(separator);
+                }
+            }
+            out.push(c);
+        }
+        if let Some(frac) = frac {
+            out.push(self.decimal_point);
+            out.push_str(frac);
+        }
+        out
+    }
+}
+
+/// Policy telling when styles are applied to printed tables
+#[derive(Clone, Debug, PartialEq, Copy)]
+pub enum ColorPolicy {
+    /// Apply styles only when printing to a tty terminal
+    Auto,
+    /// Always apply styles
+    Always,
+    /// Never apply styles
+    Never,
+}
+
+impl Default for ColorPolicy {
+    fn default() -> ColorPolicy {
+        ColorPolicy::Auto
+    }
+}
+
+/// Options for a single print call, so that a table can be printed differently
+/// in different contexts without being cloned and modified
+#[derive(Clone, Debug, Default)]
+pub struct PrintOptions {
+    /// Format used instead of the table's own format
+    format: Option<TableFormat>,
+    /// When to apply styles
+    color: ColorPolicy,
+    /// Maximum width of the table
+    max_width: Option<usize>,
+    /// Range of data rows to print
+    rows: Option<Range<usize>>,
+    /// Do not print the titles
+    hide_titles: bool,
+}
+
+impl PrintOptions {
+    /// Create new `PrintOptions`, printing the table as it is
+    pub fn new() -> PrintOptions {
+        PrintOptions::default()
+    }
+
+    /// Set the format to use instead of the table's own format. Can be chained
+    pub fn format(mut self, format: TableFormat) -> PrintOptions {
+        self.format = Some(format);
+        self
+    }
+
+    /// Set when to apply styles. Can be chained
+    pub fn color(mut self, policy: ColorPolicy) -> PrintOptions {
+        self.color = policy;
+        self
+    }
+
+    /// Set the maximum width of the table. See `PrintLimits::width` for details. Can be chained
+    pub fn max_width(mut self, max: usize) -> PrintOptions {
+        self.max_width = Some(max);
+        self
+    }
+
+    /// Set the range of data rows to print. The range is clamped to the number of rows.
+    /// Can be chained
+    pub fn rows(mut self, range: Range<usize>) -> PrintOptions {
+        self.rows = Some(range);
+        self
+    }
+
+    /// Set whether the titles are hidden, eg : when printing the continuation of
+    /// a previously printed table. Can be chained
+    pub fn hide_titles(mut self, hide: bool) -> PrintOptions {
+        self.hide_titles = hide;
+        self
+    }
+
+    /// Get the format overriding the table's own format, if any
+    pub fn get_format(&self) -> Option<&TableFormat> {
+        self.format.as_ref()
+    }
+
+    /// Get when to apply styles
+    pub fn get_color(&self) -> ColorPolicy {
+        self.color
+    }
+
+    /// Get the maximum width of the table, if any
+    pub fn get_max_width(&self) -> Option<usize> {
+        self.max_width
+    }
+
+    /// Get the range of data rows to print, if any
+    pub fn get_rows(&self) -> Option<Range<usize>> {
+        self.rows.clone()
+    }
+
+    /// Check whether the titles are hidden
+    pub fn get_hide_titles(&self) -> bool {
+        self.hide_titles
+    }
+}
+
 /// Contains the table formatting rules
 #[derive(Clone, Debug, Copy)]
 pub struct TableFormat {
@@ -135,6 +563,30 @@ pub struct TableFormat {
     pad_right: usize,
     /// Global indentation when rendering the table
     indent: usize,
+    /// Limits applied when printing to standard output
+    limits: PrintLimits,
+    /// Merge horizontally adjacent cells with identical content
+    merge_equal: bool,
+    /// Number of data rows after which titles are printed again
+    repeat_titles: Option<usize>,
+    /// Style specifier applied to title cells
+    title_style: Option<&'static str>,
+    /// Style attribute applied to every other data row
+    stripe: Option<Attr>,
+    /// Column whose content changes delimit groups of rows striped together
+    stripe_column: Option<usize>,
+    /// Minimum width of the table, including borders
+    min_width: Option<usize>,
+    /// Columns absorbing the remainder when widening the table
+    remainder: RemainderPolicy,
+    /// Side towards which centered content is shifted
+    center_bias: CenterBias,
+    /// Character filling the blank space around the content of cells
+    leader: Option<char>,
+    /// Width of East Asian ambiguous characters when laying the table out
+    ambiguous_width: AmbiguousWidth,
+    /// 24-bit colors replacing the 16 basic and bright colors when printing
+    color_overrides: [Option<Rgb>; 16],
 }
 
 impl TableFormat {
@@ -151,6 +603,18 @@ impl TableFormat {
             pad_left: 0,
             pad_right: 0,
             indent: 0,
+            limits: PrintLimits::new(),
+            merge_equal: false,
+            repeat_titles: None,
+            title_style: None,
+            stripe: None,
+            stripe_column: None,
+            min_width: None,
+            remainder: RemainderPolicy::RoundRobin,
+            center_bias: CenterBias::Left,
+            leader: None,
+            ambiguous_width: AmbiguousWidth::Narrow,
+            color_overrides: [None; 16],
         }
     }
 
@@ -217,6 +681,175 @@ impl TableFormat {
         self.indent
     }
 
+    /// Set the limits applied when printing the table to standard output
+    pub fn limits(&mut self, limits: PrintLimits) {
+        self.limits = limits;
+    }
+
+    /// Get the limits applied when printing the table to standard output
+    pub fn get_limits(&self) -> PrintLimits {
+        self.limits
+    }
+
+    /// Set whether horizontally adjacent cells with identical, non-empty content
+    /// are merged into a single cell, without column separator between them
+    pub fn merge_equal_adjacent(&mut self, merge: bool) {
+        self.merge_equal = merge;
+    }
+
+    /// Check whether horizontally adjacent cells with identical content are merged
+    pub fn get_merge_equal_adjacent(&self) -> bool {
+        self.merge_equal
+    }
+
+    /// Print the titles and their separator again every `rows` data rows,
+    /// so they stay visible in long tables. `0` disables repetition
+    pub fn repeat_titles(&mut self, rows: usize) {
+        self.repeat_titles = if rows > 0 { Some(rows) } else { None };
+    }
+
+    /// Get the number of data rows after which titles are printed again, if any
+    pub fn get_repeat_titles(&self) -> Option<usize> {
+        self.repeat_titles
+    }
+
+    /// Set a style specifier, with the syntax of `Cell::style_spec`, applied to title cells
+    /// when printing. Cells with their own style attributes or colors are left unchanged,
+    /// and only cells aligned to the left are realigned. An empty specifier removes the title style
+    pub fn title_style(&mut self, spec: &'static str) {
+        self.title_style = if spec.is_empty() { None } else { Some(spec) };
+    }
+
+    /// Get the style specifier applied to title cells, if any
+    pub fn get_title_style(&self) -> Option<&'static str> {
+        self.title_style
+    }
+
+    /// Set a style attribute, usually a background color, applied when printing to the cells
+    /// of every other data row, starting with the second one, to make wide tables easier to read.
+    /// Cells with their own background color keep it
+    pub fn stripe(&mut self, attr: Attr) {
+        self.stripe = Some(attr);
+    }
+
+    /// Remove the style attribute applied to every other data row
+    pub fn unset_stripe(&mut self) {
+        self.stripe = None;
+    }
+
+    /// Get the style attribute applied to every other data row, if any
+    pub fn get_stripe(&self) -> Option<Attr> {
+        self.stripe
+    }
+
+    /// Apply the stripe style to every other group of rows instead of every other row.
+    /// Consecutive data rows with the same content in column `column` form a group,
+    /// like with `TableSlice::subtotals`. Spanned rows belong to the group of the previous row
+    pub fn stripe_by_column(&mut self, column: usize) {
+        self.stripe_column = Some(column);
+    }
+
+    /// Apply the stripe style to every other row again
+    pub fn unset_stripe_by_column(&mut self) {
+        self.stripe_column = None;
+    }
+
+    /// Get the column delimiting groups of rows striped together, if any
+    pub fn get_stripe_column(&self) -> Option<usize> {
+        self.stripe_column
+    }
+
+    /// Set the minimum width of the table, including borders. Narrower tables are widened
+    /// by distributing the extra width evenly among columns, according to the remainder policy
+    pub fn min_width(&mut self, min: usize) {
+        self.min_width = Some(min);
+    }
+
+    /// Remove the minimum width of the table
+    pub fn unset_min_width(&mut self) {
+        self.min_width = None;
+    }
+
+    /// Get the minimum width of the table, if any
+    pub fn get_min_width(&self) -> Option<usize> {
+        self.min_width
+    }
+
+    /// Set which columns absorb the remainder when extra width cannot be distributed evenly.
+    /// Default is `RemainderPolicy::RoundRobin`
+    pub fn remainder_policy(&mut self, policy: RemainderPolicy) {
+        self.remainder = policy;
+    }
+
+    /// Get the policy telling which columns absorb the remainder of extra width
+    pub fn get_remainder_policy(&self) -> RemainderPolicy {
+        self.remainder
+    }
+
+    /// Set the side towards which centered content is shifted when it cannot be centered
+    /// exactly, in titles and data rows alike. Defaults to `CenterBias::Left`
+    pub fn center_bias(&mut self, bias: CenterBias) {
+        self.center_bias = bias;
+    }
+
+    /// Get the side towards which centered content is shifted
+    pub fn get_center_bias(&self) -> CenterBias {
+        self.center_bias
+    }
+
+    /// Set the width of East Asian ambiguous characters, like `±` or `°`, used to measure
+    /// cells when laying the table out. Use `AmbiguousWidth::Wide` when the table is printed
+    /// to a terminal using a CJK locale. Defaults to `AmbiguousWidth::Narrow`
+    pub fn ambiguous_width(&mut self, width: AmbiguousWidth) {
+        self.ambiguous_width = width;
+    }
+
+    /// Get the width of East Asian ambiguous characters
+    pub fn get_ambiguous_width(&self) -> AmbiguousWidth {
+        self.ambiguous_width
+    }
+
+    /// Fill the blank space around the content of the cells with `leader`, and align the
+    /// last column to the right, to print leader lines as in a table of contents.
+    /// Cells with a fill character of their own keep it
+    pub fn leader(&mut self, leader: char) {
+        self.leader = Some(leader);
+    }
+
+    /// Print columns without leader lines
+    pub fn unset_leader(&mut self) {
+        self.leader = None;
+    }
+
+    /// Get the character of the leader lines, if any
+    pub fn get_leader(&self) -> Option<char> {
+        self.leader
+    }
+
+    /// Print `color`, one of the 16 basic and bright colors, as the 24-bit color `rgb` wherever
+    /// it is used as a foreground or background color attribute, including in style specifiers,
+    /// title style and stripes. Other colors are ignored
+    pub fn color_override(&mut self, color: color::Color, rgb: Rgb) {
+        if let Some(c) = self.color_overrides.get_mut(color as usize) {
+            *c = Some(rgb);
+        }
+    }
+
+    /// Remove all color overrides
+    pub fn clear_color_overrides(&mut self) {
+        self.color_overrides = [None; 16];
+    }
+
+    /// Get the 24-bit color printed instead of `color`, if any
+    pub fn get_color_override(&self, color: color::Color) -> Option<Rgb> {
+        self.color_overrides.get(color as usize).and_then(|c| *c)
+    }
+
+    /// Check if any color is overridden
+    pub fn has_color_overrides(&self) -> bool {
+        self.color_overrides.iter().any(|c| c.is_some())
+    }
+
     /// Print a full line separator to `out`. `col_width` is a slice containing the width of each column
     pub fn print_line_separator<T: Write + ?Sized>(&self,
                                                    out: &mut T,
@@ -313,6 +946,78 @@ impl FormatBuilder {
         self
     }
 
+    /// Set the limits applied when printing the table to standard output
+    pub fn limits(mut self, limits: PrintLimits) -> Self {
+        self.format.limits(limits);
+        self
+    }
+
+    /// Set whether horizontally adjacent cells with identical content are merged
+    pub fn merge_equal_adjacent(mut self, merge: bool) -> Self {
+        self.format.merge_equal_adjacent(merge);
+        self
+    }
+
+    /// Print the titles again every `rows` data rows
+    pub fn repeat_titles(mut self, rows: usize) -> Self {
+        self.format.repeat_titles(rows);
+        self
+    }
+
+    /// Set a style specifier applied to title cells when printing
+    pub fn title_style(mut self, spec: &'static str) -> Self {
+        self.format.title_style(spec);
+        self
+    }
+
+    /// Set a style attribute applied to every other data row
+    pub fn stripe(mut self, attr: Attr) -> Self {
+        self.format.stripe(attr);
+        self
+    }
+
+    /// Stripe every other group of rows with the same content in `column`
+    pub fn stripe_by_column(mut self, column: usize) -> Self {
+        self.format.stripe_by_column(column);
+        self
+    }
+
+    /// Set the minimum width of the table, including borders
+    pub fn min_width(mut self, min: usize) -> Self {
+        self.format.min_width(min);
+        self
+    }
+
+    /// Set which columns absorb the remainder when extra width cannot be distributed evenly
+    pub fn remainder_policy(mut self, policy: RemainderPolicy) -> Self {
+        self.format.remainder_policy(policy);
+        self
+    }
+
+    /// Set the side towards which centered content is shifted
+    pub fn center_bias(mut self, bias: CenterBias) -> Self {
+        self.format.center_bias(bias);
+        self
+    }
+
+    /// Set the width of East Asian ambiguous characters
+    pub fn ambiguous_width(mut self, width: AmbiguousWidth) -> Self {
+        self.format.ambiguous_width(width);
+        self
+    }
+
+    /// Fill the cells with `leader`, and align the last column to the right
+    pub fn leader(mut self, leader: char) -> Self {
+        self.format.leader(leader);
+        self
+    }
+
+    /// Print `color` as the 24-bit color `rgb`
+    pub fn color_override(mut self, color: color::Color, rgb: Rgb) -> Self {
+        self.format.color_override(color, rgb);
+        self
+    }
+
     /// Return the generated `TableFormat`
     pub fn build(&self) -> TableFormat {
         *self.format
@@ -331,6 +1036,115 @@ impl From<TableFormat> for FormatBuilder {
     }
 }
 
+/// Get the width of `text` as measured by cells to lay tables out : the width in columns
+/// of its widest line, according to the Unicode width of its characters. Escape sequences
+/// and tabulations are not interpreted, and are measured like any other character
+///
+/// # Examples
+/// ```rust
+/// use prettytable::format::display_width;
+/// use prettytable::cell::Cell;
+///
+/// assert_eq!(display_width("foo\n由系统"), 6);
+/// assert_eq!(display_width("foo\n由系统"), Cell::new("foo\n由系统").get_width());
+/// ```
+pub fn display_width(text: &str) -> usize {
+    display_width_with(text, AmbiguousWidth::Narrow)
+}
+
+/// Get the width of `text` like `display_width`, measuring East Asian ambiguous characters
+/// according to `ambiguous`
+///
+/// # Examples
+/// ```rust
+/// use prettytable::format::{AmbiguousWidth, display_width_with};
+///
+/// assert_eq!(display_width_with("±5", AmbiguousWidth::Narrow), 2);
+/// assert_eq!(display_width_with("±5", AmbiguousWidth::Wide), 3);
+/// ```
+pub fn display_width_with(text: &str, ambiguous: AmbiguousWidth) -> usize {
+    text.lines().map(|l| line_width_with(l, ambiguous)).max().unwrap_or(0)
+}
+
+/// Width of the East Asian ambiguous characters, like `±` or `°`,
+/// which terminals using a CJK locale usually display over 2 columns
+#[derive(Clone, Debug, PartialEq, Copy)]
+pub enum AmbiguousWidth {
+    /// 1 column
+    Narrow,
+    /// 2 columns
+    Wide,
+}
+
+impl Default for AmbiguousWidth {
+    fn default() -> AmbiguousWidth {
+        AmbiguousWidth::Narrow
+    }
+}
+
+/// Get the width in columns of character `c`, measuring East Asian ambiguous characters
+/// according to `ambiguous`. Control characters have no width
+pub fn char_width(c: char, ambiguous: AmbiguousWidth) -> usize {
+    let width = match ambiguous {
+        AmbiguousWidth::Narrow => UnicodeWidthChar::width(c),
+        AmbiguousWidth::Wide => UnicodeWidthChar::width_cjk(c),
+    };
+    width.unwrap_or(0)
+}
+
+/// Get the Unicode width in columns of `text`, measuring East Asian ambiguous characters
+/// according to `ambiguous`
+fn str_width(text: &str, ambiguous: AmbiguousWidth) -> usize {
+    match ambiguous {
+        AmbiguousWidth::Narrow => UnicodeWidthStr::width(text),
+        AmbiguousWidth::Wide => UnicodeWidthStr::width_cjk(text),
+    }
+}
+
+/// Get the width in columns of the single line `line`, East Asian ambiguous characters
+/// being 1 column wide.
+///
+/// With the `unicode-segmentation` feature, the line is measured by grapheme clusters,
+/// so that emoji sequences joined with zero width joiners, emoji with a skin tone or
+/// an emoji presentation selector, and flags are always 2 columns wide
+///
+/// # Examples
+/// ```rust
+/// use prettytable::format::line_width;
+///
+/// assert_eq!(line_width("Cafe\u{301}"), 4);
+/// assert_eq!(line_width("由系统"), 6);
+/// ```
+pub fn line_width(line: &str) -> usize {
+    line_width_with(line, AmbiguousWidth::Narrow)
+}
+
+/// Get the width in columns of the single line `line` like `line_width`,
+/// measuring East Asian ambiguous characters according to `ambiguous`
+pub fn line_width_with(line: &str, ambiguous: AmbiguousWidth) -> usize {
+    measure_line(line, ambiguous)
+}
+
+#[cfg(feature = "unicode-segmentation")]
+fn measure_line(line: &str, ambiguous: AmbiguousWidth) -> usize {
+    line.graphemes(true).map(|g| grapheme_width(g, ambiguous)).sum()
+}
+
+#[cfg(not(feature = "unicode-segmentation"))]
+fn measure_line(line: &str, ambiguous: AmbiguousWidth) -> usize {
+    str_width(line, ambiguous)
+}
+
+/// Get the width in columns of the grapheme cluster `grapheme`, measuring East Asian
+/// ambiguous characters according to `ambiguous`
+#[cfg(feature = "unicode-segmentation")]
+pub fn grapheme_width(grapheme: &str, ambiguous: AmbiguousWidth) -> usize {
+    let emoji = grapheme.chars().any(|c| {
+        c == '\u{200D}' || c == '\u{FE0F}' || (c >= '\u{1F3FB}' && c <= '\u{1F3FF}')
+    }) || grapheme.chars().next().map_or(false, |c| c >= '\u{1F1E6}' && c <= '\u{1F1FF}');
+    if emoji { 2 } else { str_width(grapheme, ambiguous) }
+}
+
 /// Predifined formats. Those constants are lazily evaluated when
 /// the corresponding struct is dereferenced
 pub mod consts {
@@ -475,6 +1289,33 @@ pub mod consts {
                                                                     .borders('|')
                                                                     .build();
 
+        /// Format for fixed-width text files : columns are separated by a single space,
+        /// without any padding or line separator
+        ///
+        /// # Example
+        /// ```text
+        /// T1   T2
+        /// a    b
+        /// cccc d
+        /// ```
+        pub static ref FORMAT_FIXED_WIDTH: TableFormat = FormatBuilder::new()
+                                                                    .column_separator(' ')
+                                                                    .build();
+
+        /// Same as `FORMAT_FIXED_WIDTH`, with a top ruler line marking column boundaries
+        ///
+        /// # Example
+        /// ```text
+        /// ----+--
+        /// T1   T2
+        /// a    b
+        /// cccc d
+        /// ```
+        pub static ref FORMAT_FIXED_WIDTH_RULER: TableFormat = FormatBuilder::new()
+                                                                    .column_separator(' ')
+                                                                    .separator(LinePosition::Top, *MINUS_PLUS_SEP)
+                                                                    .build();
+
         /// A table with no external border
         ///
         /// # Example
@@ -506,5 +1347,66 @@ pub mod consts {
                                                                     .separator(LinePosition::Title, *MINUS_PLUS_SEP)
                                                                     .column_separator('|')
                                                                     .build();
+
+        /// Table of contents : no border nor line separator, columns joined by leader dots,
+        /// and the last column aligned to the right
+        ///
+        /// # Example
+        /// ```text
+        /// Introduction.....1
+        /// Getting started.12
+        /// ```
+        pub static ref FORMAT_TOC: TableFormat = FormatBuilder::new()
+                                                                    .column_separator('.')
+                                                                    .leader('.')
+                                                                    .build();
+    }
+    /// List every predefined format along with its name, in the order they are declared.
+    /// Useful to compare how a table renders with each of them
+    pub fn presets() -> Vec<(&'static str, &'static TableFormat)> {
+        vec![("FORMAT_DEFAULT", &*FORMAT_DEFAULT),
+             ("FORMAT_NO_TITLE", &*FORMAT_NO_TITLE),
+             ("FORMAT_NO_LINESEP_WITH_TITLE", &*FORMAT_NO_LINESEP_WITH_TITLE),
+             ("FORMAT_NO_LINESEP", &*FORMAT_NO_LINESEP),
+             ("FORMAT_NO_COLSEP", &*FORMAT_NO_COLSEP),
+             ("FORMAT_CLEAN", &*FORMAT_CLEAN),
+             ("FORMAT_BORDERS_ONLY", &*FORMAT_BORDERS_ONLY),
+             ("FORMAT_FIXED_WIDTH", &*FORMAT_FIXED_WIDTH),
+             ("FORMAT_FIXED_WIDTH_RULER", &*FORMAT_FIXED_WIDTH_RULER),
+             ("FORMAT_NO_BORDER", &*FORMAT_NO_BORDER),
+             ("FORMAT_NO_BORDER_LINE_SEPARATOR", &*FORMAT_NO_BORDER_LINE_SEPARATOR),
+             ("FORMAT_TOC", &*FORMAT_TOC)]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_width_of_text() {
+        assert_eq!(display_width(""), 0);
+        assert_eq!(display_width("foo"), 3);
+        assert_eq!(display_width("由系统"), 6);
+        assert_eq!(display_width("Cafe\u{301}"), 4);
+        assert_eq!(display_width("foo\n由系统\n"), 6);
+        assert_eq!(line_width("a\u{301}\u{302}b"), 2);
+    }
+
+    #[test]
+    fn display_width_with_ambiguous() {
+        assert_eq!(display_width_with("", AmbiguousWidth::Wide), 0);
+        assert_eq!(display_width_with("±5", AmbiguousWidth::Narrow), 2);
+        assert_eq!(display_width_with("±5", AmbiguousWidth::Wide), 3);
+        assert_eq!(display_width_with("由系统", AmbiguousWidth::Wide), 6);
+        assert_eq!(display_width_with("Cafe\u{301}", AmbiguousWidth::Wide), 4);
+    }
+
+    #[cfg(feature = "unicode-segmentation")]
+    #[test]
+    fn grapheme_width_of_emoji() {
+        assert_eq!(grapheme_width("\u{1F44D}\u{1F3FD}", AmbiguousWidth::Narrow), 2);
+        assert_eq!(grapheme_width("\u{1F1EB}\u{1F1F7}", AmbiguousWidth::Narrow), 2);
+        assert_eq!(line_width("e\u{301}\u{1F1EB}\u{1F1F7}"), 3);
     }
 }
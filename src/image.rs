@@ -0,0 +1,185 @@
+//! This module contains support for images displayed inline in cells, on terminals implementing
+//! the iTerm2 or Kitty graphics protocols. It requires the `images` feature
+use std::env;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::Path;
+use std::sync::Arc;
+
+/// Terminal protocol used to display images inline
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ImageProtocol {
+    /// The inline images protocol of iTerm2, also implemented by WezTerm
+    Iterm2,
+    /// The graphics protocol of Kitty, which only accepts PNG images here
+    Kitty,
+}
+
+lazy_static! {
+    static ref PROTOCOL: Option<ImageProtocol> = {
+        let var = |name| env::var(name).ok();
+        ImageProtocol::from_env(var("TERM_PROGRAM").as_ref().map(|v| &v[..]),
+                                var("TERM").as_ref().map(|v| &v[..]),
+                                var("KITTY_WINDOW_ID").is_some())
+    };
+}
+
+/// Maximum length of the base64 payload of a single Kitty escape sequence
+const KITTY_CHUNK: usize = 4096;
+
+impl ImageProtocol {
+    /// Detect the protocol supported by the terminal from the `TERM_PROGRAM`, `TERM` and
+    /// `KITTY_WINDOW_ID` environment variables. Return `None` if images are not supported,
+    /// in which case image cells are printed as text
+    pub fn detect() -> Option<ImageProtocol> {
+        *PROTOCOL
+    }
+
+    /// Detect the protocol from the values of the environment variables
+    fn from_env(term_program: Option<&str>,
+                term: Option<&str>,
+                kitty_window: bool)
+                -> Option<ImageProtocol> {
+        match (term_program, term) {
+            (Some("iTerm.app"), _) |
+            (Some("WezTerm"), _) => Some(ImageProtocol::Iterm2),
+            (_, Some("xterm-kitty")) => Some(ImageProtocol::Kitty),
+            _ if kitty_window => Some(ImageProtocol::Kitty),
+            _ => None,
+        }
+    }
+
+    /// Build the escape sequence displaying `data` stretched over `cols` columns and `rows` lines
+    /// of cells, starting at the cursor position. The cursor is not moved
+    pub fn escape(&self, data: &[u8], cols: usize, rows: usize) -> String {
+        let payload = base64(data);
+        match *self {
+            ImageProtocol::Iterm2 => {
+                format!("\x1b]1337;File=inline=1;size={};width={};height={};\
+                         preserveAspectRatio=0;doNotMoveCursor=1:{}\x07",
+                        data.len(),
+                        cols,
+                        rows,
+                        payload)
+            }
+            ImageProtocol::Kitty => {
+                let chunks: Vec<&[u8]> = payload.as_bytes().chunks(KITTY_CHUNK).collect();
+                let mut out = String::new();
+                for (i, chunk) in chunks.iter().enumerate() {
+                    let more = if i + 1 < chunks.len() { 1 } else { 0 };
+                    let chunk = String::from_utf8_lossy(chunk);
+                    if i == 0 {
+                        out.push_str(&format!("\x1b_Ga=T,f=100,c={},r={},C=1,m={};{}\x1b\\",
+                                              cols,
+                                              rows,
+                                              more,
+                                              chunk));
+                    } else {
+                        out.push_str(&format!("\x1b_Gm={};{}\x1b\\", more, chunk));
+                    }
+                }
+                out
+            }
+        }
+    }
+}
+
+/// Encode `data` in base64, with padding
+fn base64(data: &[u8]) -> String {
+    const ALPHABET: &'static [u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ\
+                                      abcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity((data.len() + 2) / 3 * 4);
+    for chunk in data.chunks(3) {
+        let b = [chunk[0], *chunk.get(1).unwrap_or(&0), *chunk.get(2).unwrap_or(&0)];
+        let n = (b[0] as usize) << 16 | (b[1] as usize) << 8 | b[2] as usize;
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(ALPHABET[(n >> (18 - 6 * i)) & 0x3f] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+/// The content of an image file, like a PNG file, displayed in a cell with `Cell::with_image`
+#[derive(Clone)]
+pub struct Image {
+    data: Arc<Vec<u8>>,
+}
+
+impl Image {
+    /// Create an image from the content of an image file
+    pub fn from_bytes(data: Vec<u8>) -> Image {
+        Image { data: Arc::new(data) }
+    }
+
+    /// Read an image from the file at `path`
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Image> {
+        let mut data = Vec::new();
+        File::open(path)?.read_to_end(&mut data)?;
+        Ok(Image::from_bytes(data))
+    }
+
+    /// Get the content of the image file
+    pub fn get_data(&self) -> &[u8] {
+        &self.data
+    }
+}
+
+impl From<Vec<u8>> for Image {
+    fn from(data: Vec<u8>) -> Image {
+        Image::from_bytes(data)
+    }
+}
+
+impl<'a> From<&'a [u8]> for Image {
+    fn from(data: &'a [u8]) -> Image {
+        Image::from_bytes(data.to_vec())
+    }
+}
+
+impl fmt::Debug for Image {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(fmt, "Image({} bytes)", self.data.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encode() {
+        assert_eq!(base64(b""), "");
+        assert_eq!(base64(b"f"), "Zg==");
+        assert_eq!(base64(b"fo"), "Zm8=");
+        assert_eq!(base64(b"foobar"), "Zm9vYmFy");
+        assert_eq!(base64(&[0xfb, 0xff]), "+/8=");
+    }
+
+    #[test]
+    fn detect() {
+        assert_eq!(ImageProtocol::from_env(Some("iTerm.app"), Some("xterm"), false),
+                   Some(ImageProtocol::Iterm2));
+        assert_eq!(ImageProtocol::from_env(None, Some("xterm-kitty"), false),
+                   Some(ImageProtocol::Kitty));
+        assert_eq!(ImageProtocol::from_env(None, Some("screen"), true),
+                   Some(ImageProtocol::Kitty));
+        assert_eq!(ImageProtocol::from_env(Some("Apple_Terminal"), None, false), None);
+    }
+
+    #[test]
+    fn escape() {
+        assert_eq!(ImageProtocol::Iterm2.escape(b"foo", 4, 2),
+                   "\x1b]1337;File=inline=1;size=3;width=4;height=2;preserveAspectRatio=0;\
+                    doNotMoveCursor=1:Zm9v\x07");
+        assert_eq!(ImageProtocol::Kitty.escape(b"foo", 4, 2),
+                   "\x1b_Ga=T,f=100,c=4,r=2,C=1,m=0;Zm9v\x1b\\");
+        let escape = ImageProtocol::Kitty.escape(&[0; 4000], 1, 1);
+        assert_eq!(escape.matches("\x1b_G").count(), 2);
+        assert!(escape.contains(",m=1;") && escape.contains("\x1b_Gm=0;"));
+    }
+}
@@ -0,0 +1,414 @@
+//! This module contains options to clean up the content of imported tables
+use std::{error, fmt, str};
+use std::str::Utf8Error;
+
+#[cfg(feature = "unicode-normalization")]
+use unicode_normalization::UnicodeNormalization;
+#[cfg(feature = "csv")]
+use csv;
+
+use super::Table;
+use super::row::Row;
+use super::cell::Cell;
+
+/// Policy applied when imported bytes are not valid UTF-8
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Utf8Policy {
+    /// Fail with an `ImportError` telling where the invalid content is
+    Strict,
+    /// Replace invalid sequences with the replacement character `�`
+    Lossy,
+}
+
+impl Default for Utf8Policy {
+    fn default() -> Utf8Policy {
+        Utf8Policy::Strict
+    }
+}
+
+/// Error returned when importing a table fails.
+/// Row and column indices start at 0, and include the header line if any
+#[derive(Debug)]
+pub enum ImportError {
+    /// The content of a cell is not valid UTF-8
+    InvalidUtf8 {
+        /// Index of the row in the source
+        row: usize,
+        /// Index of the column in the row
+        column: usize,
+        /// The decoding error, giving the position of the invalid bytes in the cell
+        error: Utf8Error,
+    },
+    /// A record has more fields than the first one, or than the header line
+    RaggedRecord {
+        /// Index of the row in the source
+        row: usize,
+        /// Number of fields of the first record
+        expected: usize,
+        /// Number of fields of this record
+        found: usize,
+    },
+    /// The CSV source could not be read
+    #[cfg(feature = "csv")]
+    Csv(csv::Error),
+    /// The sidecar describing the titles and styles of a CSV source is not valid
+    #[cfg(feature = "csv")]
+    InvalidSidecar(String),
+}
+
+impl fmt::Display for ImportError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match *self {
+            ImportError::InvalidUtf8 { row, column, ref error } => {
+                write!(fmt, "row {}, column {}: invalid UTF-8 ({})", row, column, error)
+            }
+            ImportError::RaggedRecord { row, expected, found } => {
+                write!(fmt, "row {}: {} fields instead of {}", row, found, expected)
+            }
+            #[cfg(feature = "csv")]
+            ImportError::Csv(ref error) => write!(fmt, "CSV error: {}", error),
+            #[cfg(feature = "csv")]
+            ImportError::InvalidSidecar(ref message) => write!(fmt, "invalid sidecar: {}", message),
+        }
+    }
+}
+
+impl error::Error for ImportError {
+    fn description(&self) -> &str {
+        match *self {
+            ImportError::InvalidUtf8 { .. } => "invalid UTF-8",
+            ImportError::RaggedRecord { .. } => "ragged record",
+            #[cfg(feature = "csv")]
+            ImportError::Csv(_) => "CSV error",
+            #[cfg(feature = "csv")]
+            ImportError::InvalidSidecar(_) => "invalid sidecar",
+        }
+    }
+
+    fn cause(&self) -> Option<&error::Error> {
+        match *self {
+            ImportError::InvalidUtf8 { ref error, .. } => Some(error),
+            ImportError::RaggedRecord { .. } => None,
+            #[cfg(feature = "csv")]
+            ImportError::Csv(ref error) => Some(error),
+            #[cfg(feature = "csv")]
+            ImportError::InvalidSidecar(_) => None,
+        }
+    }
+}
+
+#[cfg(feature = "csv")]
+impl From<csv::Error> for ImportError {
+    fn from(error: csv::Error) -> ImportError {
+        ImportError::Csv(error)
+    }
+}
+
+/// Cleanup applied to the content of each cell of an imported table, whatever its source
+/// (CSV, records, Markdown, org-mode, ...)
+///
+/// # Examples
+/// ```rust
+/// use prettytable::Table;
+/// use prettytable::import::ImportOptions;
+///
+/// let mut table = Table::from_markdown("| a |\n|---|\n|  b   c |").unwrap();
+/// ImportOptions::new().trim(true).collapse_whitespace(true).apply_to(&mut table);
+/// assert_eq!(table[0][0].get_content(), "b c");
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ImportOptions {
+    /// Remove leading and trailing whitespace
+    trim: bool,
+    /// Replace runs of whitespace with a single space
+    collapse: bool,
+    /// Normalize content to Unicode normalization form C
+    nfc: bool,
+    /// Policy applied to content which is not valid UTF-8
+    utf8: Utf8Policy,
+}
+
+impl ImportOptions {
+    /// Create new `ImportOptions`, leaving content unchanged
+    pub fn new() -> ImportOptions {
+        ImportOptions::default()
+    }
+
+    /// Set whether leading and trailing whitespace is removed from each line of the cells.
+    /// Can be chained
+    pub fn trim(mut self, trim: bool) -> ImportOptions {
+        self.trim = trim;
+        self
+    }
+
+    /// Set whether runs of whitespace inside lines are replaced with a single space.
+    /// Can be chained
+    pub fn collapse_whitespace(mut self, collapse: bool) -> ImportOptions {
+        self.collapse = collapse;
+        self
+    }
+
+    /// Set whether content is normalized to Unicode normalization form C (NFC), so that
+    /// composed and decomposed characters are compared and measured the same way.
+    /// Can be chained
+    #[cfg(feature = "unicode-normalization")]
+    pub fn nfc(mut self, nfc: bool) -> ImportOptions {
+        self.nfc = nfc;
+        self
+    }
+
+    /// Set the policy applied when imported bytes are not valid UTF-8.
+    /// Default is `Utf8Policy::Strict`. Can be chained
+    pub fn utf8(mut self, policy: Utf8Policy) -> ImportOptions {
+        self.utf8 = policy;
+        self
+    }
+
+    /// Decode the `bytes` of the cell in `column` of `row` according to the UTF-8 policy,
+    /// then apply the options to the decoded content
+    pub fn decode(&self, bytes: &[u8], row: usize, column: usize) -> Result<String, ImportError> {
+        let content = match str::from_utf8(bytes) {
+            Ok(content) => content.to_string(),
+            Err(_) if self.utf8 == Utf8Policy::Lossy => {
+                String::from_utf8_lossy(bytes).into_owned()
+            }
+            Err(error) => {
+                return Err(ImportError::InvalidUtf8 {
+                               row: row,
+                               column: column,
+                               error: error,
+                           })
+            }
+        };
+        Ok(self.apply(&content))
+    }
+
+    /// Apply the options to `content`. Each line is processed separately
+    pub fn apply(&self, content: &str) -> String {
+        content.lines()
+            .map(|line| {
+                let line = if self.collapse {
+                    let mut collapsed = String::with_capacity(line.len());
+                    let mut space = false;
+                    for c in line.chars() {
+                        if c.is_whitespace() {
+                            if !space {
+                                collapsed.push(' ');
+                            }
+                            space = true;
+                        } else {
+                            collapsed.push(c);
+                            space = false;
+                        }
+                    }
+                    collapsed
+                } else {
+                    line.to_string()
+                };
+                let line = if self.trim { line.trim().to_string() } else { line };
+                self.normalize(line)
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[cfg(feature = "unicode-normalization")]
+    fn normalize(&self, line: String) -> String {
+        if self.nfc { line.nfc().collect() } else { line }
+    }
+
+    #[cfg(not(feature = "unicode-normalization"))]
+    fn normalize(&self, line: String) -> String {
+        line
+    }
+
+    /// Apply the options to the content of every cell of `table`, including titles.
+    /// Alignment and style of the cells are kept
+    pub fn apply_to(&self, table: &mut Table) {
+        let apply_row = |row: &mut Row| {
+            for cell in row.iter_mut() {
+                let content = self.apply(&cell.get_content());
+                let mut new = Cell::new_align(&content, cell.get_alignment());
+                new.valign(cell.get_valign());
+                new.inherit_style(cell);
+                *cell = new;
+            }
+        };
+        if let Some(ref mut titles) = table.titles {
+            apply_row(titles);
+        }
+        for row in table.rows.iter_mut() {
+            apply_row(row);
+        }
+    }
+}
+
+impl Table {
+    /// Create a table from rows of raw cell content, decoded and cleaned up according
+    /// to `options`. Fail on the first cell which is not valid UTF-8 when the
+    /// UTF-8 policy is `Utf8Policy::Strict`
+    ///
+    /// # Examples
+    /// ```rust
+    /// use prettytable::Table;
+    /// use prettytable::import::{ImportOptions, Utf8Policy};
+    ///
+    /// let rows = vec![vec![&b"foo"[..], &b"bar"[..]], vec![&b"b\xffz"[..]]];
+    /// let err = Table::from_byte_rows(rows.clone(), &ImportOptions::new()).unwrap_err();
+    /// assert!(err.to_string().starts_with("row 1, column 0: invalid UTF-8"));
+    /// let options = ImportOptions::new().utf8(Utf8Policy::Lossy);
+    /// let table = Table::from_byte_rows(rows, &options).unwrap();
+    /// assert_eq!(table[1][0].get_content(), "b\u{FFFD}z");
+    /// ```
+    pub fn from_byte_rows<I, R, B>(rows: I, options: &ImportOptions) -> Result<Table, ImportError>
+        where I: IntoIterator<Item = R>,
+              R: IntoIterator<Item = B>,
+              B: AsRef<[u8]>
+    {
+        let mut table = Table::new();
+        for (i, row) in rows.into_iter().enumerate() {
+            let mut cells = Vec::new();
+            for (j, cell) in row.into_iter().enumerate() {
+                cells.push(Cell::new(&options.decode(cell.as_ref(), i, j)?));
+            }
+            table.add_row(Row::new(cells));
+        }
+        Ok(table)
+    }
+
+    /// Create a table from a CSV reader, decoding and cleaning up cells according to
+    /// `options`. Unlike `from_csv`, cells are not required to be valid UTF-8 up front,
+    /// so that invalid content is reported with its row and column, or replaced.
+    /// If the reader has headers, they become the titles of the table
+    #[cfg(feature = "csv")]
+    pub fn from_csv_with<R: ::std::io::Read>(reader: &mut csv::Reader<R>,
+                                             options: &ImportOptions)
+                                             -> Result<Table, ImportError> {
+        let mut rows = Vec::new();
+        if reader.has_headers() {
+            rows.push(reader.byte_headers()?.clone());
+        }
+        for record in reader.byte_records() {
+            rows.push(record?);
+        }
+        let mut table = Table::from_byte_rows(rows.iter(), options)?;
+        if reader.has_headers() {
+            let titles = table[0].clone();
+            table.remove_row(0);
+            table.set_titles(titles);
+        }
+        Ok(table)
+    }
+
+    /// Create a table from a CSV reader like `from_csv`, skipping malformed records instead
+    /// of failing, and return it along with the errors describing the skipped records.
+    /// Reading stops at the first I/O error.
+    ///
+    /// Records with more fields than the header line, or than the first record if there are
+    /// no headers, are skipped. Shorter records are padded with empty cells if the reader has
+    /// been built with `csv::ReaderBuilder::flexible(true)`, and skipped otherwise
+    ///
+    /// # Examples
+    /// ```rust
+    /// # extern crate csv;
+    /// # extern crate prettytable;
+    /// use prettytable::Table;
+    /// # fn main() {
+    /// let data = "id,name\n1,apple\n2\n3,pear,extra\n4,plum";
+    /// let mut reader = csv::ReaderBuilder::new().flexible(true).from_reader(data.as_bytes());
+    /// let (table, errors) = Table::from_csv_lenient(&mut reader);
+    /// assert_eq!(table.len(), 3);
+    /// assert_eq!(table[1][1].get_content(), "");
+    /// assert_eq!(errors.len(), 1);
+    /// assert_eq!(errors[0].to_string(), "row 3: 3 fields instead of 2");
+    /// # }
+    /// ```
+    #[cfg(feature = "csv")]
+    pub fn from_csv_lenient<R: ::std::io::Read>(reader: &mut csv::Reader<R>)
+                                                -> (Table, Vec<ImportError>) {
+        let mut table = Table::new();
+        let mut errors = Vec::new();
+        let mut fields = None;
+        let offset = if reader.has_headers() { 1 } else { 0 };
+        if reader.has_headers() {
+            match reader.headers() {
+                Ok(headers) => {
+                    fields = Some(headers.len());
+                    table.set_titles(Row::from(headers));
+                }
+                Err(error) => return (table, vec![ImportError::Csv(error)]),
+            }
+        }
+        for (i, record) in reader.records().enumerate() {
+            let record = match record {
+                Ok(record) => record,
+                Err(error) => {
+                    let fatal = error.is_io_error();
+                    errors.push(ImportError::Csv(error));
+                    if fatal {
+                        break;
+                    }
+                    continue;
+                }
+            };
+            let expected = match fields {
+                Some(expected) => expected,
+                None => {
+                    fields = Some(record.len());
+                    record.len()
+                }
+            };
+            if record.len() > expected {
+                errors.push(ImportError::RaggedRecord {
+                                row: i + offset,
+                                expected: expected,
+                                found: record.len(),
+                            });
+                continue;
+            }
+            let mut row = Row::from(&record);
+            for _ in record.len()..expected {
+                row.add_cell(Cell::default());
+            }
+            table.add_row(row);
+        }
+        (table, errors)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn apply() {
+        let text = "  a \t b  \n c";
+        assert_eq!(ImportOptions::new().apply(text), "  a \t b  \n c");
+        assert_eq!(ImportOptions::new().trim(true).apply(text), "a \t b\nc");
+        assert_eq!(ImportOptions::new().collapse_whitespace(true).apply(text), " a b \n c");
+        let options = ImportOptions::new().trim(true).collapse_whitespace(true);
+        assert_eq!(options.apply(text), "a b\nc");
+    }
+
+    #[test]
+    fn decode() {
+        let options = ImportOptions::new().trim(true);
+        assert_eq!(options.decode(b" a ", 0, 0).unwrap(), "a");
+        let err = options.decode(b"ab\xc3", 1832, 4).unwrap_err();
+        assert!(err.to_string().starts_with("row 1832, column 4: invalid UTF-8 ("));
+        assert!(error::Error::cause(&err).is_some());
+        let options = options.utf8(Utf8Policy::Lossy);
+        assert_eq!(options.decode(b"ab\xc3 ", 0, 0).unwrap(), "ab\u{FFFD}");
+    }
+
+    #[test]
+    fn apply_to() {
+        let mut table = Table::new();
+        table.set_titles(Row::from(vec![" id "]));
+        table.add_row(Row::new(vec![Cell::new(" 1 ").style_spec("br")]));
+        ImportOptions::new().trim(true).apply_to(&mut table);
+        assert_eq!(table.to_string().replace("\r\n", "\n"),
+                   "+----+\n| id |\n+====+\n|  1 |\n+----+\n");
+        assert!(!table[0][0].get_style().is_empty());
+    }
+}
@@ -0,0 +1,264 @@
+//! This module contains the column sizing algorithm used when printing tables, so that
+//! other renderers can size their columns exactly the same way
+use std::cmp;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, Read, Write};
+use std::path::Path;
+use std::str::FromStr;
+
+use unicode_width::UnicodeWidthChar;
+
+use super::format::{TableFormat, ColumnPosition, Width, RemainderPolicy};
+
+/// Compute the width of each column from the `natural` width of its content and its
+/// `constraints`, then, if `max_total` is given, shrink the widest columns one character
+/// at a time until the sum of the widths is not greater than `max_total`, or all columns are
+/// empty. The limit takes precedence over constraints.
+/// Columns without constraint are sized according to their content.
+///
+/// The returned widths exclude padding, borders and column separators. Use `table_width`
+/// to get the total width of a table printed with a given format.
+///
+/// # Examples
+/// ```rust
+/// use prettytable::format::Width;
+/// use prettytable::layout::distribute_widths;
+///
+/// let widths = distribute_widths(&[10, 3, 6], &[Width::Auto, Width::Min(5)], Some(12));
+/// assert_eq!(widths, vec![4, 4, 4]);
+/// ```
+pub fn distribute_widths(natural: &[usize],
+                         constraints: &[Width],
+                         max_total: Option<usize>)
+                         -> Vec<usize> {
+    let mut widths = natural.to_vec();
+    for (w, constraint) in widths.iter_mut().zip(constraints) {
+        *w = constraint.apply(*w);
+    }
+    if let Some(max) = max_total {
+        let mut total = widths.iter().sum::<usize>();
+        while total > max {
+            let widest = match widths.iter().enumerate().max_by_key(|&(_, w)| *w) {
+                Some((i, &w)) if w > 0 => i,
+                _ => break,
+            };
+            widths[widest] -= 1;
+            total -= 1;
+        }
+    }
+    widths
+}
+
+/// Widen columns until the sum of their widths is `min_total`, distributing the extra width
+/// evenly among columns. When it cannot be divided exactly, the remainder is given to columns
+/// according to `policy`. Widths are returned unchanged if their sum is already at least
+/// `min_total`, or if there is no column
+///
+/// # Examples
+/// ```rust
+/// use prettytable::format::RemainderPolicy;
+/// use prettytable::layout::expand_widths;
+///
+/// assert_eq!(expand_widths(&[1, 2, 3], 11, RemainderPolicy::Rightmost), vec![2, 3, 6]);
+/// assert_eq!(expand_widths(&[1, 2, 3], 11, RemainderPolicy::RoundRobin), vec![3, 4, 4]);
+/// ```
+pub fn expand_widths(widths: &[usize], min_total: usize, policy: RemainderPolicy) -> Vec<usize> {
+    let mut widths = widths.to_vec();
+    let total = widths.iter().sum::<usize>();
+    let cols = widths.len();
+    if total >= min_total || cols == 0 {
+        return widths;
+    }
+    let extra = min_total - total;
+    let remainder = extra % cols;
+    for w in widths.iter_mut() {
+        *w += extra / cols;
+    }
+    match policy {
+        RemainderPolicy::Leftmost => widths[0] += remainder,
+        RemainderPolicy::Rightmost => widths[cols - 1] += remainder,
+        RemainderPolicy::RoundRobin => {
+            for w in widths.iter_mut().take(remainder) {
+                *w += 1;
+            }
+        }
+    }
+    widths
+}
+
+/// Get the total width of a table printed with `format` whose columns have the given
+/// content widths, including borders, padding and column separators, but excluding indentation
+pub fn table_width(format: &TableFormat, col_width: &[usize]) -> usize {
+    let (lp, rp) = format.get_padding();
+    let sep_width = |pos| {
+        format.get_column_separator(pos).map_or(0, |c| UnicodeWidthChar::width(c).unwrap_or(0))
+    };
+    let cols = col_width.len();
+    col_width.iter().sum::<usize>() + cols * (lp + rp) +
+    cols.saturating_sub(1) * sep_width(ColumnPosition::Intern) +
+    sep_width(ColumnPosition::Left) + sep_width(ColumnPosition::Right)
+}
+
+/// Get the sum of column widths available for content, for a table of `columns` columns
+/// printed with `format` which must not be wider than `max_width`.
+/// Can be used as `max_total` for `distribute_widths`
+pub fn content_budget(format: &TableFormat, columns: usize, max_width: usize) -> usize {
+    let overhead = table_width(format, &vec![0; columns]);
+    cmp::max(max_width, overhead) - overhead
+}
+
+/// Width constraints of the columns of a table, which can be saved to a file and loaded back,
+/// so that interactive tools can keep the column widths chosen by the user between runs.
+/// Get them with `Table::get_column_widths` and apply them with `Table::set_column_widths`.
+///
+/// Widths are saved as text, one column per line : `auto`, `min N`, `fixed N` or `max N`
+///
+/// # Examples
+/// ```rust
+/// use prettytable::format::Width;
+/// use prettytable::layout::ColumnWidths;
+///
+/// let mut widths = ColumnWidths::new();
+/// widths.set(1, Width::Fixed(12));
+/// assert_eq!(widths.to_string(), "auto\nfixed 12\n");
+/// assert_eq!("auto\nfixed 12\n".parse::<ColumnWidths>(), Ok(widths));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ColumnWidths {
+    widths: Vec<Width>,
+}
+
+impl ColumnWidths {
+    /// Create empty column widths, where every column is `Width::Auto`
+    pub fn new() -> ColumnWidths {
+        ColumnWidths::default()
+    }
+
+    /// Set the width constraint of the column at position `column`
+    pub fn set(&mut self, column: usize, width: Width) {
+        if column >= self.widths.len() {
+            self.widths.resize(column + 1, Width::Auto);
+        }
+        self.widths[column] = width;
+    }
+
+    /// Get the width constraint of the column at position `column`
+    pub fn get(&self, column: usize) -> Width {
+        self.widths.get(column).cloned().unwrap_or(Width::Auto)
+    }
+
+    /// Get the width constraints of all the columns having one
+    pub fn as_slice(&self) -> &[Width] {
+        &self.widths
+    }
+
+    /// Save the widths in the file at `path`, replacing its content
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        File::create(path)?.write_all(self.to_string().as_bytes())
+    }
+
+    /// Load widths saved with `save` from the file at `path`.
+    /// An error of kind `InvalidData` is returned if the file content is invalid
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<ColumnWidths> {
+        let mut content = String::new();
+        File::open(path)?.read_to_string(&mut content)?;
+        content.parse().map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+}
+
+impl From<Vec<Width>> for ColumnWidths {
+    fn from(widths: Vec<Width>) -> ColumnWidths {
+        ColumnWidths { widths: widths }
+    }
+}
+
+impl fmt::Display for ColumnWidths {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        for width in &self.widths {
+            match *width {
+                Width::Auto => writeln!(fmt, "auto")?,
+                Width::Min(w) => writeln!(fmt, "min {}", w)?,
+                Width::Fixed(w) => writeln!(fmt, "fixed {}", w)?,
+                Width::Max(w) => writeln!(fmt, "max {}", w)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for ColumnWidths {
+    type Err = String;
+
+    /// Parse widths written by `Display`. Blank lines are ignored
+    fn from_str(s: &str) -> Result<ColumnWidths, String> {
+        let mut widths = Vec::new();
+        for (i, line) in s.lines().enumerate().filter(|&(_, l)| !l.trim().is_empty()) {
+            let mut words = line.split_whitespace();
+            let kind = words.next().unwrap_or("");
+            let value = words.next().map(|w| w.parse::<usize>());
+            let width = match (kind, value, words.next()) {
+                ("auto", None, None) => Width::Auto,
+                ("min", Some(Ok(w)), None) => Width::Min(w),
+                ("fixed", Some(Ok(w)), None) => Width::Fixed(w),
+                ("max", Some(Ok(w)), None) => Width::Max(w),
+                _ => return Err(format!("Invalid column width at line {} : {}", i + 1, line)),
+            };
+            widths.push(width);
+        }
+        Ok(ColumnWidths { widths: widths })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use format::consts::{FORMAT_DEFAULT, FORMAT_CLEAN};
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    #[test]
+    fn distribute() {
+        let constraints = [Width::Fixed(2), Width::Min(8), Width::Max(4), Width::Auto];
+        assert_eq!(distribute_widths(&[5, 5, 5, 5], &constraints, None), vec![2, 8, 4, 5]);
+        assert_eq!(distribute_widths(&[5, 5, 5, 5], &constraints, Some(15)), vec![2, 5, 4, 4]);
+        assert_eq!(distribute_widths(&[5, 5], &[], Some(0)), vec![0, 0]);
+        assert_eq!(distribute_widths(&[], &constraints, Some(3)), Vec::<usize>::new());
+    }
+
+    #[test]
+    fn expand() {
+        let widths = [4, 0, 1, 2];
+        assert_eq!(expand_widths(&widths, 14, RemainderPolicy::Leftmost), vec![8, 1, 2, 3]);
+        assert_eq!(expand_widths(&widths, 14, RemainderPolicy::Rightmost), vec![5, 1, 2, 6]);
+        assert_eq!(expand_widths(&widths, 14, RemainderPolicy::RoundRobin), vec![6, 2, 3, 3]);
+        assert_eq!(expand_widths(&widths, 16, RemainderPolicy::Leftmost), vec![7, 2, 3, 4]);
+        assert_eq!(expand_widths(&widths, 5, RemainderPolicy::Leftmost), vec![4, 0, 1, 2]);
+        assert_eq!(expand_widths(&[], 5, RemainderPolicy::Leftmost), Vec::<usize>::new());
+    }
+
+    #[test]
+    fn budget() {
+        assert_eq!(table_width(&FORMAT_DEFAULT, &[3, 4]), 3 + 4 + 4 + 3);
+        assert_eq!(content_budget(&FORMAT_DEFAULT, 2, 20), 13);
+        assert_eq!(content_budget(&FORMAT_DEFAULT, 2, 5), 0);
+        assert_eq!(content_budget(&FORMAT_CLEAN, 2, 20), 16);
+    }
+    #[test]
+    fn save_load() {
+        let widths = ColumnWidths::from(vec![Width::Min(3), Width::Auto, Width::Max(40)]);
+        // `std::process::id` is not available with Rust 1.13, so the current time makes the
+        // file name unique instead, in case several test runs happen at the same time
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
+        let name = format!("prettytable-column-widths-{}-{}", now.as_secs(), now.subsec_nanos());
+        let path = ::std::env::temp_dir().join(name);
+        widths.save(&path).unwrap();
+        assert_eq!(ColumnWidths::load(&path).unwrap(), widths);
+        File::create(&path).unwrap().write_all(b"auto\n\nmin x\n").unwrap();
+        let err = ColumnWidths::load(&path).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(err.to_string(), "Invalid column width at line 3 : min x");
+        let _ = ::std::fs::remove_file(&path);
+        assert!("fixed 2 3".parse::<ColumnWidths>().is_err());
+        assert_eq!(" max 4 \n".parse::<ColumnWidths>().unwrap().get(0), Width::Max(4));
+    }
+}
@@ -8,43 +8,115 @@ extern crate term;
 extern crate atty;
 #[cfg(feature = "csv")]
 extern crate csv;
+#[cfg(feature = "unicode-normalization")]
+extern crate unicode_normalization;
+#[cfg(feature = "regex")]
+extern crate regex;
+#[cfg(feature = "unicode-segmentation")]
+extern crate unicode_segmentation;
 #[macro_use]
 extern crate lazy_static;
 extern crate encode_unicode;
 
-use std::io::{self, Write, Error};
+use std::io::{self, BufWriter, Write, Error};
 #[cfg(feature = "csv")]
 use std::io::Read;
 use std::fmt;
+use std::cmp;
 #[cfg(feature = "csv")]
 use std::path::Path;
 use std::iter::{FromIterator, IntoIterator};
 use std::slice::{Iter, IterMut};
-use std::ops::{Index, IndexMut};
-use std::mem::transmute;
+use std::ops::{Index, IndexMut, Range};
+use std::mem;
+use std::borrow::Borrow;
+use std::sync::Arc;
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
 
-use term::{Terminal, stdout};
+use term::{Attr, Terminal, TerminfoTerminal, color, stdout};
 
+#[macro_use]
 pub mod cell;
+#[macro_use]
 pub mod row;
 pub mod format;
+pub mod sparse;
+pub mod columnar;
+pub mod borrowed;
+pub mod span;
+pub mod records;
+pub mod markdown;
+pub mod org;
+pub mod fixed;
+pub mod import;
+#[cfg(feature = "csv")]
+pub mod export;
+#[cfg(feature = "csv")]
+pub mod sidecar;
+pub mod sort;
+pub mod validate;
+pub mod theme;
+pub mod testing;
+pub mod layout;
+pub mod stream;
+#[cfg(feature = "images")]
+pub mod image;
 mod utils;
 
 use row::Row;
-use cell::Cell;
-use format::{TableFormat, LinePosition, consts};
-use utils::StringWriter;
+use cell::{Cell, Rgb};
+use format::{TableFormat, LinePosition, CaptionPosition, PrintLimits, PrintOptions,
+             ColorPolicy, Alignment, VerticalAlignment, Width, FormatBuilder, NumberFormat,
+             AmbiguousWidth, consts, display_width_with, line_width_with};
+#[cfg(feature = "csv")]
+use export::CsvOptions;
+use utils::{StringWriter, MeasureWriter, XorShift, LineFilter, LineFilterWriter, CellFormatter,
+            RowValidator, LineCounter, TermBuffer, NEWLINE, parse_number, print_align_measured,
+            escape_debug, stdout_colorized, write_lines};
+use span::{StyledSpan, SpanRecorder};
+use layout::ColumnWidths;
 
 /// An owned printable table
 #[derive(Clone, Debug)]
 pub struct Table {
-    format: Box<TableFormat>,
-    titles: Box<Option<Row>>,
+    format: TableFormat,
+    titles: Option<Row>,
+    widths: Vec<Width>,
+    line_filter: Option<LineFilter>,
+    caption: Option<(Row, CaptionPosition)>,
+    computed: Vec<ComputedColumn>,
+    groups: Vec<(String, Range<usize>)>,
+    column_styles: Vec<(usize, Cell)>,
+    cell_formatter: Option<CellFormatter>,
+    row_validator: Option<RowValidator>,
+    row_ids: Option<HashMap<String, usize>>,
     rows: Vec<Row>,
 }
 
+/// Internal only. Fail to compile if tables and their components cannot be sent to or shared
+/// with other threads, so that tables can be built on worker threads and printed elsewhere.
+/// Functions stored in tables must be `Send + Sync` for this to hold
+#[allow(dead_code)]
+fn assert_send_sync() {
+    fn check<T: Send + Sync>() {}
+    check::<Table>();
+    check::<TableSlice>();
+    check::<TableView>();
+    check::<ColumnSlice>();
+    check::<Row>();
+    check::<Cell>();
+    check::<TableFormat>();
+    check::<PrintOptions>();
+    check::<sparse::SparseTable>();
+    check::<columnar::ColumnTable>();
+    check::<borrowed::StrTable>();
+    check::<theme::Theme>();
+}
+
 /// A borrowed immutable `Table` slice
-/// A `TableSlice` is obtained by slicing a `Table` with the `Slice::slice` method.
+/// A `TableSlice` is obtained by slicing a `Table` with the `Slice::slice` method,
+/// or over the whole table with `Table::as_slice`.
 ///
 /// # Examples
 /// ```rust
@@ -59,6 +131,7 @@ pub struct Table {
 /// table.slice(..);
 /// table.slice(..2);
 /// table.slice(1..3);
+/// assert_eq!(table.as_slice().len(), 3);
 /// # }
 /// ```
 ///
@@ -66,20 +139,20 @@ pub struct Table {
 pub struct TableSlice<'a> {
     format: &'a TableFormat,
     titles: &'a Option<Row>,
+    widths: &'a Vec<Width>,
+    line_filter: &'a Option<LineFilter>,
+    caption: &'a Option<(Row, CaptionPosition)>,
+    computed: &'a Vec<ComputedColumn>,
+    groups: &'a Vec<(String, Range<usize>)>,
+    column_styles: &'a Vec<(usize, Cell)>,
+    cell_formatter: &'a Option<CellFormatter>,
     rows: &'a [Row],
 }
 
 impl<'a> TableSlice<'a> {
     /// Compute and return the number of column
     pub fn get_column_num(&self) -> usize {
-        let mut cnum = 0;
-        for r in self.rows {
-            let l = r.len();
-            if l > cnum {
-                cnum = l;
-            }
-        }
-        cnum
+        get_column_num(self.rows.iter())
     }
 
     /// Get the number of rows
@@ -97,31 +170,9 @@ impl<'a> TableSlice<'a> {
         self.rows.get(row)
     }
 
-    /// Get the width of the column at position `col_idx`.
-    /// Return 0 if the column does not exists;
-    fn get_column_width(&self, col_idx: usize) -> usize {
-        let mut width = match *self.titles {
-            Some(ref t) => t.get_cell_width(col_idx),
-            None => 0,
-        };
-        for r in self.rows {
-            let l = r.get_cell_width(col_idx);
-            if l > width {
-                width = l;
-            }
-        }
-        width
-    }
-
-    /// Get the width of all columns, and return a slice
-    /// with the result for each column
-    fn get_all_column_width(&self) -> Vec<usize> {
-        let colnum = self.get_column_num();
-        let mut col_width = vec![0usize; colnum];
-        for i in 0..colnum {
-            col_width[i] = self.get_column_width(i);
-        }
-        col_width
+    /// Get an immutable reference to the cell at `(row, column)`, or `None` if it does not exist
+    pub fn get(&self, (row, column): (usize, usize)) -> Option<&Cell> {
+        self.rows.get(row).and_then(|r| r.get_cell(column))
     }
 
     /// Returns an iterator over the immutable cells of the column specified by `column`
@@ -134,261 +185,877 @@ impl<'a> TableSlice<'a> {
         self.rows.iter()
     }
 
-    /// Internal only
-    fn __print<T: Write + ?Sized, F>(&self, out: &mut T, f: F) -> Result<(), Error>
-        where F: Fn(&Row, &mut T, &TableFormat, &[usize]) -> Result<(), Error>
-    {
-        // Compute columns width
-        let col_width = self.get_all_column_width();
-        self.format
-            .print_line_separator(out, &col_width, LinePosition::Top)?;
-        if let Some(ref t) = *self.titles {
-            f(t, out, self.format, &col_width)?;
-            self.format
-                .print_line_separator(out, &col_width, LinePosition::Title)?;
-        }
-        // Print rows
-        let mut iter = self.rows.into_iter().peekable();
-        while let Some(r) = iter.next() {
-            f(r, out, self.format, &col_width)?;
-            if iter.peek().is_some() {
-                self.format
-                    .print_line_separator(out, &col_width, LinePosition::Intern)?;
-            }
-        }
-        self.format
-            .print_line_separator(out, &col_width, LinePosition::Bottom)?;
-        out.flush()
+    /// Get a view of `n` rows randomly selected in this table slice.
+    /// Selected rows keep their relative order, and the selection is stable for a given `seed`.
+    /// If `n` is greater than the number of rows, all rows are selected
+    pub fn sample(&self, n: usize, seed: u64) -> TableView<'a> {
+        let mut rng = XorShift::new(seed);
+        let len = self.rows.len();
+        let n = cmp::min(n, len);
+        let mut indices = Vec::with_capacity(n);
+        // Selection sampling : each row is selected with probability (needed / remaining)
+        for i in 0..len {
+            let needed = n - indices.len();
+            if needed == 0 {
+                break;
+            }
+            if rng.next_below(len - i) < needed {
+                indices.push(i);
+            }
+        }
+        self.view(indices)
     }
 
-    /// Print the table to `out`
-    pub fn print<T: Write + ?Sized>(&self, out: &mut T) -> Result<(), Error> {
-        self.__print(out, Row::print)
+    /// Get a view of the rows of this table slice for which `f` returns `true`.
+    /// The view is printed with the format, titles and settings of the table,
+    /// without copying any row
+    ///
+    /// # Examples
+    /// ```rust
+    /// # #[macro_use] extern crate prettytable;
+    /// # fn main() {
+    /// let table = table![["build", "ok"], ["test", "failed"], ["lint", "failed"]];
+    /// let failed = table.filter_view(|row| row[1].get_content() == "failed");
+    /// assert_eq!(failed.get_indices(), &[1, 2]);
+    /// failed.printstd();
+    /// # }
+    /// ```
+    pub fn filter_view<F: FnMut(&Row) -> bool>(&self, mut f: F) -> TableView<'a> {
+        let indices = (0..self.rows.len()).filter(|&i| f(&self.rows[i])).collect();
+        self.view(indices)
     }
 
-    /// Print the table to terminal `out`, applying styles when needed
-    pub fn print_term<T: Terminal + ?Sized>(&self, out: &mut T) -> Result<(), Error> {
-        self.__print(out, Row::print_term)
+    /// Split this table slice into consecutive slices of `size` rows, the last one
+    /// being shorter if the number of rows is not a multiple of `size`. Each chunk is printed
+    /// with the format, titles and settings of the table, for instance to print one page at a time
+    ///
+    /// # Panics
+    /// Panics if `size` is 0
+    ///
+    /// # Examples
+    /// ```rust
+    /// # #[macro_use] extern crate prettytable;
+    /// # fn main() {
+    /// let table = table!(["Name", "Value"]; => ["a", 1], ["b", 2], ["c", 3]);
+    /// let pages: Vec<_> = table.chunks(2).collect();
+    /// assert_eq!(pages.len(), 2);
+    /// assert_eq!(pages[1].len(), 1);
+    /// for page in pages {
+    ///     page.printstd(); // Each page starts with the titles
+    /// }
+    /// # }
+    /// ```
+    pub fn chunks(&self, size: usize) -> Chunks<'a> {
+        assert!(size != 0, "chunk size must be non-zero");
+        Chunks {
+            slice: self.clone(),
+            size: size,
+        }
     }
 
-    /// Print the table to standard output. Colors won't be displayed unless
-    /// stdout is a tty terminal, or `force_colorize` is set to `true`.
-    /// In ANSI terminals, colors are displayed using ANSI escape characters. When for example the
-    /// output is redirected to a file, or piped to another program, the output is considered
-    /// as not beeing tty, and ANSI escape characters won't be displayed unless `force colorize`
-    /// is set to `true`.
-    /// # Panic
-    /// Panic if writing to standard output fails
-    pub fn print_tty(&self, force_colorize: bool) {
-        let r = match (stdout(), atty::is(atty::Stream::Stdout) || force_colorize) {
-            (Some(mut o), true) => self.print_term(&mut *o),
-            _ => self.print(&mut io::stdout()),
-        };
-        if let Err(e) = r {
-            panic!("Cannot print table to standard output : {}", e);
+    /// Get a view printing only the columns of this table slice in the `columns` range,
+    /// titles included. Computed columns count as the last columns of the table
+    ///
+    /// # Panics
+    /// Panics if the range is out of the columns of the table
+    ///
+    /// # Examples
+    /// ```rust
+    /// # #[macro_use] extern crate prettytable;
+    /// use prettytable::Slice;
+    /// # fn main() {
+    /// let table = table!(["id", "name", "email"]; => [1, "alice", "a@b.c"], [2, "bob", "b@c.d"]);
+    /// let view = table.slice_columns(1..);
+    /// assert!(view.to_string().starts_with("+-------+-------+\n| name  | email |"));
+    /// // Rows and columns can be sliced together
+    /// assert_eq!(table.slice(1..).slice_columns(..1).len(), 1);
+    /// # }
+    /// ```
+    pub fn slice_columns<E>(&self, columns: E) -> ColumnSlice<'a>
+        where [usize]: Index<E, Output = [usize]>
+    {
+        let all: Vec<usize> = (0..self.get_column_num() + self.computed.len()).collect();
+        ColumnSlice {
+            slice: self.clone(),
+            columns: all[..].index(columns).to_vec(),
         }
     }
 
-    /// Print the table to standard output. Colors won't be displayed unless
-    /// stdout is a tty terminal. This means that if stdout is redirected to a file, or piped
-    /// to another program, no color will be displayed.
-    /// To force colors rendering, use `print_tty()` method.
-    /// Calling `printstd()` is equivalent to calling `print_tty(false)`
-    /// # Panic
-    /// Panic if writing to standard output fails
-    pub fn printstd(&self) {
-        self.print_tty(false);
+    /// Get a view printing only the columns of this table slice for which `f` returns `true`,
+    /// titles included. `f` is called with the title of each column, or an empty string if
+    /// there are no titles, and its index. Computed columns count as the last columns
+    ///
+    /// # Examples
+    /// ```rust
+    /// # #[macro_use] extern crate prettytable;
+    /// use prettytable::Slice;
+    /// # fn main() {
+    /// let table = table!(["id", "name", "email"]; => [1, "alice", "a@b.c"], [2, "bob", "b@c.d"]);
+    /// let wanted = ["email", "id"];
+    /// let view = table.filter_columns(|title, _| wanted.contains(&title));
+    /// assert_eq!(view.get_columns(), &[0, 2]);
+    /// assert!(view.to_string().starts_with("+----+-------+\n| id | email |"));
+    /// # }
+    /// ```
+    pub fn filter_columns<F: FnMut(&str, usize) -> bool>(&self, f: F) -> ColumnSlice<'a> {
+        ColumnSlice {
+            slice: self.clone(),
+            columns: (0..self.get_column_num() + self.computed.len()).collect(),
+        }
+        .filter_columns(f)
     }
 
-    /// Write the table to the specified writer.
-    #[cfg(feature = "csv")]
-    pub fn to_csv<W: Write>(&self, w: W) -> csv::Result<csv::Writer<W>> {
-        self.to_csv_writer(csv::Writer::from_writer(w))
+    /// Internal only. Get a view of the rows of this table slice at `indices`
+    fn view(&self, indices: Vec<usize>) -> TableView<'a> {
+        TableView {
+            format: self.format,
+            titles: self.titles,
+            widths: self.widths,
+            line_filter: self.line_filter,
+            caption: self.caption,
+            computed: self.computed,
+            groups: self.groups,
+            column_styles: self.column_styles,
+            cell_formatter: self.cell_formatter,
+            rows: self.rows,
+            indices: indices,
+        }
     }
 
-    /// Write the table to the specified writer.
-    ///
-    /// This allows for format customisation.
-    #[cfg(feature = "csv")]
-    pub fn to_csv_writer<W: Write>(&self,
-                                   mut writer: csv::Writer<W>)
-                                   -> csv::Result<csv::Writer<W>> {
-        for title in self.titles {
-            writer.write(title.iter().map(|c| c.get_content()))?;
+    /// Generate a new table with statistics about each numeric column of this table slice :
+    /// count, mean, standard deviation, min and max. A column is considered numeric
+    /// if all its non-empty cells can be parsed as numbers. The titles of the new table
+    /// are taken from the titles of this slice, or are the column indices if there are no titles.
+    /// The standard deviation of a column with a single value is left empty
+    pub fn describe(&self) -> Table {
+        let mut titles = Row::new(vec![Cell::default()]);
+        let mut stats = vec![Row::new(vec![Cell::new("count")]),
+                             Row::new(vec![Cell::new("mean")]),
+                             Row::new(vec![Cell::new("std")]),
+                             Row::new(vec![Cell::new("min")]),
+                             Row::new(vec![Cell::new("max")])];
+        for col in 0..self.get_column_num() {
+            if !self.is_numeric_column(col) {
+                continue;
+            }
+            let values: Vec<f64> = self.column_iter(col)
+                .filter_map(|c| parse_number(&c.get_content()))
+                .collect();
+            let title = match *self.titles {
+                Some(ref t) => t.get_cell(col).map(|c| c.get_content()),
+                None => None,
+            };
+            titles.add_cell(Cell::new(&title.unwrap_or_else(|| col.to_string())));
+            let count = values.len() as f64;
+            let mean = values.iter().sum::<f64>() / count;
+            let std = if values.len() < 2 {
+                String::new()
+            } else {
+                let var = values.iter().map(|v| (v - mean) * (v - mean)).sum::<f64>() /
+                          (count - 1.0);
+                format!("{:.6}", var.sqrt())
+            };
+            let min = values.iter().cloned().fold(::std::f64::INFINITY, f64::min);
+            let max = values.iter().cloned().fold(::std::f64::NEG_INFINITY, f64::max);
+            let cells = [values.len().to_string(),
+                         format!("{:.6}", mean),
+                         std,
+                         format!("{:.6}", min),
+                         format!("{:.6}", max)];
+            for (row, content) in stats.iter_mut().zip(cells.iter()) {
+                row.add_cell(Cell::new_align(content, Alignment::RIGHT));
+            }
+        }
+        let mut table = Table::init(stats);
+        table.set_titles(titles);
+        table
+    }
+
+    /// Internal only. Check if all the non-empty cells of column `column` are numbers,
+    /// and there is at least one of them
+    fn is_numeric_column(&self, column: usize) -> bool {
+        let mut numeric = false;
+        for cell in self.column_iter(column) {
+            let content = cell.get_content();
+            if content.trim().is_empty() {
+                continue;
+            }
+            if parse_number(&content).is_none() {
+                return false;
+            }
+            numeric = true;
+        }
+        numeric
+    }
+
+    /// Generate a report table from this table slice, where rows are grouped by
+    /// the content of column `group_column`. Consecutive rows with the same content form
+    /// a group, followed by a subtotal row summing each numeric column of the group.
+    /// A grand total row is added at the end. Subtotal and total rows are styled according
+    /// to the style specifiers `subtotal_spec` and `total_spec` (see `Cell::style_spec`).
+    /// Sums are rounded to the largest number of decimals found in their column
+    pub fn subtotals(&self, group_column: usize, subtotal_spec: &str, total_spec: &str) -> Table {
+        let colnum = self.get_column_num();
+        let numeric: Vec<bool> = (0..colnum)
+            .map(|c| c != group_column && self.is_numeric_column(c))
+            .collect();
+        let mut decimals = vec![0; colnum];
+        for row in self.rows {
+            for (c, cell) in row.iter().enumerate() {
+                let content = cell.get_content();
+                if parse_number(&content).is_some() {
+                    decimals[c] = cmp::max(decimals[c], number_decimals(&content));
+                }
+            }
         }
+        let mut rows = Vec::new();
+        let mut group: Option<String> = None;
+        let mut group_sums = vec![0f64; colnum];
+        let mut total_sums = vec![0f64; colnum];
         for row in self.rows {
-            writer.write(row.iter().map(|c| c.get_content()))?;
+            let key = row.get_cell(group_column).map_or_else(String::new, |c| c.get_content());
+            if let Some(g) = group.take() {
+                if g != key {
+                    let label = format!("{} subtotal", g);
+                    rows.push(summary_row(&label,
+                                          group_column,
+                                          &group_sums,
+                                          &numeric,
+                                          &decimals,
+                                          subtotal_spec));
+                    group_sums = vec![0f64; colnum];
+                }
+            }
+            group = Some(key);
+            for (c, cell) in row.iter().enumerate() {
+                if let Some(v) = parse_number(&cell.get_content()) {
+                    group_sums[c] += v;
+                    total_sums[c] += v;
+                }
+            }
+            rows.push(row.clone());
+        }
+        if let Some(g) = group {
+            let label = format!("{} subtotal", g);
+            rows.push(summary_row(&label,
+                                  group_column,
+                                  &group_sums,
+                                  &numeric,
+                                  &decimals,
+                                  subtotal_spec));
         }
+        rows.push(summary_row("Total", group_column, &total_sums, &numeric, &decimals, total_spec));
+        let mut table = Table::init(rows);
+        table.set_format(*self.format);
+        table.titles = self.titles.clone();
+        table.widths = self.widths.clone();
+        table
+    }
 
-        writer.flush()?;
-        Ok(writer)
+    /// Internal only
+    fn __print<'w, T: Write + ?Sized, F>(&self,
+                                         out: &'w mut T,
+                                         limits: &PrintLimits,
+                                         f: F)
+                                         -> Result<(), Error>
+        where F: Fn(&Row, &mut LineCounter<'w, T>, &TableFormat, &[usize]) -> Result<(), Error>
+    {
+        print_rows(out,
+                   self.format,
+                   self.titles,
+                   self.caption,
+                   self.computed,
+                   self.groups,
+                   self.column_styles,
+                   self.cell_formatter,
+                   self.rows.iter(),
+                   self.widths,
+                   limits,
+                   f)
     }
-}
 
-impl<'a> IntoIterator for &'a TableSlice<'a> {
-    type Item = &'a Row;
-    type IntoIter = Iter<'a, Row>;
-    fn into_iter(self) -> Self::IntoIter {
-        self.row_iter()
+    /// Internal only. Print without styles, through the line filter if any
+    fn __print_plain<T: Write + ?Sized>(&self,
+                                        out: &mut T,
+                                        limits: &PrintLimits)
+                                        -> Result<(), Error> {
+        // Cells, padding and separators are written in many small pieces,
+        // which are gathered before being written to `out`
+        let mut out = BufWriter::new(out);
+        match *self.line_filter {
+            Some(ref filter) => {
+                self.__print(&mut LineFilterWriter::new(&mut out, filter), limits, Row::print)?
+            }
+            None => self.__print(&mut out, limits, Row::print)?,
+        }
+        out.flush()
     }
-}
 
-impl Table {
-    /// Create an empty table
-    pub fn new() -> Table {
-        Self::init(Vec::new())
+    /// Print the table to `out`
+    pub fn print<T: Write + ?Sized>(&self, out: &mut T) -> Result<(), Error> {
+        self.__print_plain(out, &PrintLimits::new())
     }
 
-    /// Create a table initialized with `rows`
-    pub fn init(rows: Vec<Row>) -> Table {
-        Table {
-            rows: rows,
-            titles: Box::new(None),
-            format: Box::new(*consts::FORMAT_DEFAULT),
-        }
+    /// Print the table to terminal `out`, applying styles when needed.
+    /// The line filter is not applied, since styles are written to the terminal directly
+    pub fn print_term<T: Terminal + ?Sized>(&self, out: &mut T) -> Result<(), Error> {
+        // Text between style changes is gathered before being written to `out`
+        let mut out = TermBuffer::new(out);
+        self.__print(&mut out, &PrintLimits::new(), Row::print_term)?;
+        out.flush()
     }
 
-    /// Create a table from a CSV string
-    ///
-    /// For more customisability use `from_csv()`
-    #[cfg(feature = "csv")]
-    pub fn from_csv_string(csv_s: &str) -> csv::Result<Table> {
-        Ok(Table::from_csv(&mut csv::Reader::from_string(csv_s).has_headers(false)))
+    /// Internal only. Get a slice of this slice, printed according to `options`,
+    /// and the limits to apply
+    fn with_options<'b>(&'b self, options: &'b PrintOptions) -> (TableSlice<'b>, PrintLimits) {
+        let rows = match options.get_rows() {
+            Some(range) => {
+                let end = cmp::min(range.end, self.rows.len());
+                &self.rows[cmp::min(range.start, end)..end]
+            }
+            None => self.rows,
+        };
+        let slice = TableSlice {
+            format: options.get_format().unwrap_or(self.format),
+            titles: if options.get_hide_titles() { &NO_TITLES } else { self.titles },
+            widths: self.widths,
+            line_filter: self.line_filter,
+            caption: self.caption,
+            computed: self.computed,
+            groups: self.groups,
+            column_styles: self.column_styles,
+            cell_formatter: self.cell_formatter,
+            rows: rows,
+        };
+        let limits = match options.get_max_width() {
+            Some(max) => PrintLimits::new().width(max),
+            None => PrintLimits::new(),
+        };
+        (slice, limits)
     }
 
-    /// Create a table from a CSV file
-    ///
-    /// For more customisability use `from_csv()`
-    #[cfg(feature = "csv")]
-    pub fn from_csv_file<P: AsRef<Path>>(csv_p: P) -> csv::Result<Table> {
-        Ok(Table::from_csv(&mut csv::Reader::from_file(csv_p)?.has_headers(false)))
+    /// Print the table to `out` according to `options`. Styles are applied only if the
+    /// color policy is `ColorPolicy::Always` and a terminfo database is found
+    pub fn print_with<T: Write + ?Sized>(&self,
+                                         out: &mut T,
+                                         options: &PrintOptions)
+                                         -> Result<(), Error> {
+        let (slice, limits) = self.with_options(options);
+        if options.get_color() == ColorPolicy::Always {
+            if let Some(mut term) = TerminfoTerminal::new(BufWriter::new(&mut *out)) {
+                slice.__print(&mut term, &limits, Row::print_term)?;
+                return term.get_mut().flush();
+            }
+        }
+        slice.__print_plain(out, &limits)
     }
 
-    /// Create a table from a CSV reader
-    #[cfg(feature = "csv")]
-    pub fn from_csv<R: Read>(reader: &mut csv::Reader<R>) -> Table {
-        Table::init(reader
-                        .records()
-                        .map(|row| {
-                                 Row::new(row.unwrap()
-                                              .into_iter()
-                                              .map(|cell| Cell::new(&cell))
-                                              .collect())
-                             })
-                        .collect())
+    /// Lay the table out according to `options` like `print_with`, including column widths,
+    /// spans, indentation, the maximum width and the line filter, without printing it, and return
+    /// the width of its widest line and its number of lines. This allows to decide where and
+    /// how to print the table beforehand
+    ///
+    /// # Examples
+    /// ```rust
+    /// # #[macro_use] extern crate prettytable;
+    /// # fn main() {
+    /// use prettytable::format::PrintOptions;
+    ///
+    /// let table = table![["foo", "bar"], ["foobar", "baz"]];
+    /// assert_eq!(table.measure(&PrintOptions::new()), (16, 5));
+    /// assert_eq!(table.measure(&PrintOptions::new().rows(0..1)), (13, 3));
+    /// # }
+    /// ```
+    pub fn measure(&self, options: &PrintOptions) -> (usize, usize) {
+        let (slice, limits) = self.with_options(options);
+        let mut writer = MeasureWriter::new(slice.format.get_ambiguous_width());
+        // Writing into a MeasureWriter never fails
+        let _ = slice.__print_plain(&mut writer, &limits);
+        writer.into_size()
     }
 
-    /// Change the table format. Eg : Separators
-    pub fn set_format(&mut self, format: TableFormat) {
-        *self.format = format;
+    /// Get the width of the content of each column, as printed with the format and
+    /// settings of the table. Padding and separators are not included
+    ///
+    /// # Examples
+    /// ```rust
+    /// # #[macro_use] extern crate prettytable;
+    /// # fn main() {
+    /// let table = table![["foo", "bar"], ["foobar", "baz"]];
+    /// assert_eq!(table.rendered_column_widths(), vec![6, 3]);
+    /// assert_eq!(table.rendered_width(), 16);
+    /// assert_eq!(table.rendered_height(), 5);
+    /// # }
+    /// ```
+    pub fn rendered_column_widths(&self) -> Vec<usize> {
+        let derived = derive_rows(self.computed,
+                                  self.column_styles,
+                                  self.cell_formatter,
+                                  self.format.get_stripe(),
+                                  self.format.get_stripe_column(),
+                                  self.titles,
+                                  self.rows.iter());
+        let amb = self.format.get_ambiguous_width();
+        let col_width = match derived {
+            Some((titles, rows)) => get_all_column_width(&titles, rows.iter(), self.widths, amb),
+            None => get_all_column_width(self.titles, self.rows.iter(), self.widths, amb),
+        };
+        fit_column_width(self.format, col_width, &PrintLimits::new()).0
     }
 
-    /// Get a mutable reference to the internal format
-    pub fn get_format(&mut self) -> &mut TableFormat {
-        &mut self.format
+    /// Get the width of the widest printed line of the table, including separators,
+    /// padding and indentation. See `measure` to take print options into account
+    pub fn rendered_width(&self) -> usize {
+        self.measure(&PrintOptions::new()).0
     }
 
-    /// Compute and return the number of column
-    pub fn get_column_num(&self) -> usize {
-        self.as_ref().get_column_num()
+    /// Get the number of printed lines of the table, including separators, titles
+    /// and caption. See `measure` to take print options into account
+    pub fn rendered_height(&self) -> usize {
+        self.measure(&PrintOptions::new()).1
     }
 
-    /// Get the number of rows
-    pub fn len(&self) -> usize {
-        self.rows.len()
+    /// Print the table to standard output according to `options`. With the
+    /// `ColorPolicy::Auto` color policy, styles are applied only if stdout is a tty terminal
+    /// # Panic
+    /// Panic if writing to standard output fails
+    pub fn printstd_with(&self, options: &PrintOptions) {
+        let (slice, limits) = self.with_options(options);
+        let colorize = match options.get_color() {
+            ColorPolicy::Auto => stdout_colorized(),
+            ColorPolicy::Always => true,
+            ColorPolicy::Never => false,
+        };
+        let r = match (stdout(), colorize) {
+            (Some(mut o), true) => {
+                let mut out = TermBuffer::new(&mut *o);
+                slice.__print(&mut out, &limits, Row::print_term).and_then(|_| out.flush())
+            }
+            _ => slice.__print_plain(&mut io::stdout(), &limits),
+        };
+        if let Err(e) = r {
+            panic!("Cannot print table to standard output : {}", e);
+        }
     }
 
-    /// Check if the table is empty
-    pub fn is_empty(&self) -> bool {
-        self.rows.is_empty()
+    /// Internal only. Print the table in pages of `page_rows` data rows. Each page is
+    /// a full table with titles, and all pages have the same column widths.
+    /// Pages are separated by an empty line, or by a prompt waiting for the user
+    /// to press enter if `interactive` is `true`
+    fn __print_paged<'w, T: Write + ?Sized, F>(&self,
+                                              out: &'w mut T,
+                                              page_rows: usize,
+                                              interactive: bool,
+                                              f: F)
+                                              -> Result<(), Error>
+        where F: Fn(&Row, &mut LineCounter<'w, T>, &TableFormat, &[usize]) -> Result<(), Error>
+    {
+        let mut out = LineCounter::new(out);
+        let out = &mut out;
+        let header = get_group_header(self.groups);
+        let derived = derive_rows(self.computed,
+                                  self.column_styles,
+                                  self.cell_formatter,
+                                  self.format.get_stripe(),
+                                  self.format.get_stripe_column(),
+                                  self.titles,
+                                  self.rows.iter());
+        let (titles, rows) = match derived {
+            Some((ref titles, ref rows)) => (titles, &rows[..]),
+            None => (self.titles, self.rows),
+        };
+        let col_width = get_all_column_width(titles,
+                                             rows.iter(),
+                                             self.widths,
+                                             self.format.get_ambiguous_width());
+        for (n, page) in rows.chunks(cmp::max(page_rows, 1)).enumerate() {
+            if n > 0 {
+                wait_next_page(out, interactive)?;
+            }
+            print_rows_with_width(out,
+                                  self.format,
+                                  titles,
+                                  self.caption,
+                                  &header,
+                                  page.iter(),
+                                  col_width.clone(),
+                                  &PrintLimits::new(),
+                                  &f)?;
+        }
+        Ok(())
     }
 
-    /// Set the optional title lines
-    pub fn set_titles(&mut self, titles: Row) {
-        *self.titles = Some(titles);
+    /// Internal only. Print the table in pages like `__print_paged`, without styles,
+    /// through the line filter if any
+    fn __print_paged_plain<T: Write + ?Sized>(&self,
+                                              out: &mut T,
+                                              page_rows: usize,
+                                              interactive: bool)
+                                              -> Result<(), Error> {
+        let mut out = BufWriter::new(out);
+        match *self.line_filter {
+            Some(ref filter) => {
+                let mut out = LineFilterWriter::new(&mut out, filter);
+                self.__print_paged(&mut out, page_rows, interactive, Row::print)?
+            }
+            None => self.__print_paged(&mut out, page_rows, interactive, Row::print)?,
+        }
+        out.flush()
     }
 
-    /// Unset the title line
-    pub fn unset_titles(&mut self) {
-        *self.titles = None;
+    /// Print the table to `out` in pages of `page_rows` data rows, separated by an empty line.
+    /// Each page is printed as a full table with its titles, and all pages have
+    /// the same column widths
+    pub fn print_paged<T: Write + ?Sized>(&self,
+                                          out: &mut T,
+                                          page_rows: usize)
+                                          -> Result<(), Error> {
+        self.__print_paged_plain(out, page_rows, false)
     }
 
-    /// Get a mutable reference to a row
-    pub fn get_mut_row(&mut self, row: usize) -> Option<&mut Row> {
-        self.rows.get_mut(row)
+    /// Print the table to standard output in pages of `page_rows` data rows. If `page_rows`
+    /// is `None`, pages are as long as the terminal height given by the `LINES` environment
+    /// variable, or 24 lines. If both standard input and output are tty terminals, the user
+    /// is prompted to press enter between pages.
+    /// # Panic
+    /// Panic if writing to standard output fails
+    pub fn printstd_paged(&self, page_rows: Option<usize>) {
+        let page_rows = page_rows.unwrap_or_else(|| self.get_page_rows());
+        let interactive = atty::is(atty::Stream::Stdout) && atty::is(atty::Stream::Stdin);
+        let r = match (stdout(), stdout_colorized()) {
+            (Some(mut o), true) => {
+                let mut out = TermBuffer::new(&mut *o);
+                self.__print_paged(&mut out, page_rows, interactive, Row::print_term)
+                    .and_then(|_| out.flush())
+            }
+            _ => self.__print_paged_plain(&mut io::stdout(), page_rows, interactive),
+        };
+        if let Err(e) = r {
+            panic!("Cannot print table to standard output : {}", e);
+        }
     }
 
-    /// Get an immutable reference to a row
-    pub fn get_row(&self, row: usize) -> Option<&Row> {
-        self.rows.get(row)
+    /// Internal only. Get the number of single line data rows fitting in the terminal height
+    fn get_page_rows(&self) -> usize {
+        let height: usize = ::std::env::var("LINES")
+            .ok()
+            .and_then(|l| l.parse().ok())
+            .unwrap_or(24);
+        let mut sep = StringWriter::new();
+        let _ = self.format.print_line_separator(&mut sep, &[0], LinePosition::Intern);
+        let row_lines = if sep.as_string().is_empty() { 1 } else { 2 };
+        // Keep room for top and bottom lines, titles and the prompt
+        cmp::max(height.saturating_sub(5) / row_lines, 1)
     }
 
-    /// Append a row in the table, transferring ownership of this row to the table
-    /// and returning a mutable reference to the row
-    pub fn add_row(&mut self, row: Row) -> &mut Row {
-        self.rows.push(row);
-        let l = self.rows.len() - 1;
-        &mut self.rows[l]
+    /// Split the table into text pages of exactly `page_height` lines, for printing
+    /// on line printers or in paginated logs. Each page starts with the header returned by
+    /// `header_fn`, called with the page number (starting at 1) and the number of pages,
+    /// followed by a full table with its titles. All pages have the same column widths,
+    /// and rows are never split across pages. The header must have the same number of lines
+    /// on every page. A single row too high to fit in a page is printed on its own page,
+    /// which is then higher than `page_height`
+    pub fn to_paged_text<F>(&self, page_height: usize, header_fn: F) -> Vec<String>
+        where F: Fn(usize, usize) -> String
+    {
+        let header = get_group_header(self.groups);
+        let derived = derive_rows(self.computed,
+                                  self.column_styles,
+                                  self.cell_formatter,
+                                  self.format.get_stripe(),
+                                  self.format.get_stripe_column(),
+                                  self.titles,
+                                  self.rows.iter());
+        let (titles, rows) = match derived {
+            Some((ref titles, ref rows)) => (titles, &rows[..]),
+            None => (self.titles, self.rows),
+        };
+        let col_width = get_all_column_width(titles,
+                                             rows.iter(),
+                                             self.widths,
+                                             self.format.get_ambiguous_width());
+        let render = |page: &[Row]| {
+            let mut writer = StringWriter::new();
+            // Writing into a StringWriter never fails
+            let _ = print_rows_with_width(&mut LineCounter::new(&mut writer),
+                                          self.format,
+                                          titles,
+                                          self.caption,
+                                          &header,
+                                          page.iter(),
+                                          col_width.clone(),
+                                          &PrintLimits::new(),
+                                          Row::print);
+            writer.as_string().lines().map(|l| l.to_string()).collect::<Vec<_>>()
+        };
+        // Split rows into pages, using the number of lines of each part of a table
+        let available = page_height.saturating_sub(header_fn(1, 1).lines().count());
+        let overhead = render(&[]).len();
+        let mut sep = StringWriter::new();
+        let _ = self.format.print_line_separator(&mut sep, &col_width, LinePosition::Intern);
+        let sep_lines = sep.as_string().lines().count();
+        let mut pages: Vec<&[Row]> = Vec::new();
+        let (mut start, mut used) = (0, overhead);
+        for (i, row) in rows.iter().enumerate() {
+            let lines = row.get_height() + if i > start { sep_lines } else { 0 };
+            if i > start && used + lines > available {
+                pages.push(&rows[start..i]);
+                start = i;
+                used = overhead + row.get_height();
+            } else {
+                used += lines;
+            }
+        }
+        if start < rows.len() || pages.is_empty() {
+            pages.push(&rows[start..]);
+        }
+        let newline = String::from_utf8_lossy(NEWLINE);
+        let count = pages.len();
+        pages.iter()
+            .enumerate()
+            .map(|(n, page)| {
+                let mut lines: Vec<String> = header_fn(n + 1, count)
+                    .lines()
+                    .map(|l| l.to_string())
+                    .collect();
+                lines.extend(render(page));
+                while lines.len() < page_height {
+                    lines.push(String::new());
+                }
+                let mut text = lines.join(&newline);
+                text.push_str(&newline);
+                text
+            })
+            .collect()
     }
 
-    /// Append an empty row in the table. Return a mutable reference to this new row.
-    pub fn add_empty_row(&mut self) -> &mut Row {
-        self.add_row(Row::default())
+    /// Print the table to `out` in expanded mode : each row is printed as a block of
+    /// `field | value` lines, under a `-[ RECORD n ]-` header. Fields names are taken
+    /// from titles, or are the column indices if there are no titles. This is useful for tables
+    /// too wide for the screen
+    pub fn print_expanded<T: Write + ?Sized>(&self, out: &mut T) -> Result<(), Error> {
+        let colnum = self.get_column_num();
+        let fields: Vec<String> = (0..colnum)
+            .map(|i| {
+                     match *self.titles {
+                         Some(ref t) if i < t.len() => t[i].get_content(),
+                         _ => i.to_string(),
+                     }
+                 })
+            .collect();
+        let amb = self.format.get_ambiguous_width();
+        let field_width = fields.iter().map(|f| line_width_with(&f[..], amb)).max().unwrap_or(0);
+        let value_width = (0..colnum)
+            .map(|i| {
+                     self.column_iter(i)
+                         .map(|c| display_width_with(&c.get_content(), amb))
+                         .max()
+                         .unwrap_or(0)
+                 })
+            .max()
+            .unwrap_or(0);
+        let indent = vec![b' '; self.format.get_indent()];
+        for (n, row) in self.rows.iter().enumerate() {
+            out.write_all(&indent)?;
+            let header = format!("-[ RECORD {} ]", n + 1);
+            // Header is filled with at least one dash, up to the width of the block
+            let fill = cmp::max((field_width + 3 + value_width).saturating_sub(header.len()), 1);
+            out.write_all(header.as_bytes())?;
+            out.write_all(&vec![b'-'; fill])?;
+            out.write_all(NEWLINE)?;
+            for (i, field) in fields.iter().enumerate() {
+                let lines = row.get_cell(i).map_or(1, |c| c.get_height());
+                for l in 0..lines {
+                    out.write_all(&indent)?;
+                    let name = if l == 0 { &field[..] } else { "" };
+                    let name_width = line_width_with(name, amb);
+                    print_align_measured(out, Alignment::LEFT, name, name_width, ' ', field_width,
+                                         false)?;
+                    out.write_all(b" | ")?;
+                    if let Some(c) = row.get_cell(i) {
+                        let mut c = c.clone();
+                        c.set_ambiguous_width(amb);
+                        c.print(out, l, value_width, true)?;
+                    }
+                    out.write_all(NEWLINE)?;
+                }
+            }
+        }
+        out.flush()
     }
 
-    /// Insert `row` at the position `index`, and return a mutable reference to this row.
-    /// If index is higher than current numbers of rows, `row` is appended at the end of the table
-    pub fn insert_row(&mut self, index: usize, row: Row) -> &mut Row {
-        if index < self.rows.len() {
-            self.rows.insert(index, row);
-            &mut self.rows[index]
-        } else {
-            self.add_row(row)
+    /// Print the table to standard output in expanded mode. See `print_expanded` for details
+    /// # Panic
+    /// Panic if writing to standard output fails
+    pub fn printstd_expanded(&self) {
+        if let Err(e) = self.print_expanded(&mut io::stdout()) {
+            panic!("Cannot print table to standard output : {}", e);
         }
     }
 
-    /// Modify a single element in the table
-    pub fn set_element(&mut self, element: &str, column: usize, row: usize) -> Result<(), &str> {
-        let rowline = self.get_mut_row(row).ok_or("Cannot find row")?;
-        // TODO: If a cell already exist, copy it's alignment parameter
-        rowline.set_cell(Cell::new(element), column)
+    /// Render the table without styles, and return it line by line, without line terminators.
+    /// This is useful to embed a table into larger text layouts
+    pub fn render_lines(&self) -> Vec<String> {
+        self.to_string().lines().map(|l| l.to_string()).collect()
     }
 
-    /// Remove the row at position `index`. Silently skip if the row does not exist
-    pub fn remove_row(&mut self, index: usize) {
-        if index < self.rows.len() {
-            self.rows.remove(index);
+    /// Render the table as plain text where columns are only aligned with spaces, separated by
+    /// at least one space, without borders nor separator lines, as expected by many Unix tools.
+    /// Column widths are computed like when printing, and trailing spaces are removed
+    ///
+    /// # Examples
+    /// ```rust
+    /// # #[macro_use] extern crate prettytable;
+    /// # fn main() {
+    /// let table = table!(["PID", "CMD"]; => ["1", "init"], ["42", "sh"]);
+    /// assert_eq!(table.to_aligned_text().replace("\r\n", "\n"), "PID CMD\n1   init\n42  sh\n");
+    /// # }
+    /// ```
+    pub fn to_aligned_text(&self) -> String {
+        let format = FormatBuilder::new().column_separator(' ').padding(0, 0).build();
+        let slice = TableSlice { format: &format, ..self.clone() };
+        let mut out = String::new();
+        for line in slice.render_lines() {
+            let end = line.char_indices()
+                .rev()
+                .find(|&(_, c)| c != ' ')
+                .map_or(0, |(i, c)| i + c.len_utf8());
+            out.push_str(&line[..end]);
+            out.push_str(&String::from_utf8_lossy(NEWLINE));
         }
+        out
     }
 
-    /// Return an iterator over the immutable cells of the column specified by `column`
-    pub fn column_iter(&self, column: usize) -> ColumnIter {
-        ColumnIter(self.rows.iter(), column)
+    /// Render the table line by line, as fragments of text associated with their style attributes.
+    /// This is useful to display a table in TUI frameworks or GUI text views
+    /// without parsing ANSI escape sequences
+    pub fn render_spans(&self) -> Vec<Vec<StyledSpan>> {
+        let mut recorder = SpanRecorder::new();
+        // Writing into a SpanRecorder never fails
+        let _ = self.print_term(&mut recorder);
+        recorder.into_lines()
     }
 
-    /// Return an iterator over the mutable cells of the column specified by `column`
-    pub fn column_iter_mut(&mut self, column: usize) -> ColumnIterMut {
-        ColumnIterMut(self.rows.iter_mut(), column)
+    /// Build a copy of the table to debug unexpected column widths : the content of each cell
+    /// is quoted, with line breaks, tabulations, control and zero width characters escaped,
+    /// and trailing spaces replaced by `·`. Each cell is followed by its width between brackets
+    pub fn debug_view(&self) -> Table {
+        let debug_row = |row: &Row| {
+            Row::new(row.iter()
+                         .map(|c| {
+                                  let content = escape_debug(&c.get_content());
+                                  Cell::new(&format!("{} [{}]", content, c.get_width()))
+                              })
+                         .collect())
+        };
+        let mut table = Table::init(self.rows.iter().map(&debug_row).collect());
+        table.titles = self.titles.as_ref().map(&debug_row);
+        table
     }
 
-    /// Returns an iterator over immutable rows
-    pub fn row_iter(&self) -> Iter<Row> {
-        self.rows.iter()
+    /// Print the table to standard output as built by `debug_view`
+    pub fn print_debug(&self) {
+        self.debug_view().printstd();
     }
 
-    /// Returns an iterator over mutable rows
-    pub fn row_iter_mut(&mut self) -> IterMut<Row> {
-        self.rows.iter_mut()
+    /// Build a new table with rows and columns swapped : column `i` of this table becomes row `i`
+    /// of the new one. If `titles_as_column` is true, the titles become the first column of the
+    /// new table, otherwise they are dropped. Missing cells are replaced by empty cells, and
+    /// spanned rows are skipped. The format is kept
+    ///
+    /// # Examples
+    /// ```rust
+    /// # #[macro_use] extern crate prettytable;
+    /// # fn main() {
+    /// let mut table = table![["foo", 1], ["bar", 2]];
+    /// table.set_titles(row!["name", "qty"]);
+    /// let transposed = table.transpose(true);
+    /// assert_eq!(transposed[1][0].get_content(), "qty");
+    /// assert_eq!(transposed[1][2].get_content(), "2");
+    /// # }
+    /// ```
+    pub fn transpose(&self, titles_as_column: bool) -> Table {
+        let titles = if titles_as_column { self.titles.as_ref() } else { None };
+        let rows: Vec<&Row> = titles.into_iter()
+            .chain(self.rows.iter().filter(|r| !r.is_spanned()))
+            .collect();
+        let columns = rows.iter().map(|r| r.len()).max().unwrap_or(0);
+        let transposed = (0..columns)
+            .map(|c| {
+                     Row::new(rows.iter()
+                                  .map(|r| r.get_cell(c).cloned().unwrap_or_else(Cell::default))
+                                  .collect())
+                 })
+            .collect();
+        let mut table = Table::init(transposed);
+        table.format = *self.format;
+        table
     }
 
-    /// Print the table to `out`
-    pub fn print<T: Write + ?Sized>(&self, out: &mut T) -> Result<(), Error> {
-        self.as_ref().print(out)
+    /// Split the table into several tables no wider than `max_width`, excluding indentation,
+    /// for narrow terminals. Each table starts with the `key_columns`, followed by as many
+    /// of the other columns as fit, in their original order. A table always contains at least
+    /// one column besides the key columns, even if it is too wide. Computed columns, column
+    /// styles and the cell formatter are applied like in `materialize`, and spanned rows are
+    /// copied unchanged in each table
+    ///
+    /// # Examples
+    /// ```rust
+    /// # #[macro_use] extern crate prettytable;
+    /// # fn main() {
+    /// let table = table!(["id", "name", "email", "phone"]; =>
+    ///                    [1, "alice", "alice@example.com", "555-0100"]);
+    /// let tables = table.split_vertical(40, &[0]);
+    /// assert_eq!(tables.len(), 2);
+    /// assert_eq!(tables[1].to_string().lines().nth(1), Some("| id | phone    |"));
+    /// # }
+    /// ```
+    pub fn split_vertical(&self, max_width: usize, key_columns: &[usize]) -> Vec<Table> {
+        let table = self.materialize();
+        let col_width = get_all_column_width(&table.titles,
+                                             table.rows.iter(),
+                                             &table.widths,
+                                             table.format.get_ambiguous_width());
+        let others = (0..col_width.len()).filter(|c| !key_columns.contains(c));
+        let mut tables = Vec::new();
+        let mut columns = key_columns.to_vec();
+        for column in others {
+            let mut widths: Vec<usize> =
+                columns.iter().filter_map(|&c| col_width.get(c).cloned()).collect();
+            widths.push(col_width[column]);
+            if columns.len() > key_columns.len() &&
+               layout::table_width(&table.format, &widths) > max_width {
+                tables.push(table.select_columns(&columns));
+                columns.truncate(key_columns.len());
+            }
+            columns.push(column);
+        }
+        tables.push(table.select_columns(&columns));
+        tables
     }
 
-    /// Print the table to terminal `out`, applying styles when needed
-    pub fn print_term<T: Terminal + ?Sized>(&self, out: &mut T) -> Result<(), Error> {
-        self.as_ref().print_term(out)
+    /// Build a copy of the table where computed columns, column styles and the cell formatter
+    /// are replaced by their current result, stored in plain cells. The copy prints the same
+    /// way but does not depend on those functions anymore, so it can be exported or kept as a
+    /// snapshot of the current values. The format, column widths, caption, column groups and
+    /// line filter are kept, but not the row validator
+    pub fn materialize(&self) -> Table {
+        let derived = derive_rows(self.computed,
+                                  self.column_styles,
+                                  self.cell_formatter,
+                                  None,
+                                  None,
+                                  self.titles,
+                                  self.rows.iter());
+        let (titles, rows) = derived.unwrap_or_else(|| (self.titles.clone(), self.rows.to_vec()));
+        let mut table = Table::init(rows);
+        table.titles = titles;
+        table.format = *self.format;
+        table.widths = self.widths.clone();
+        table.line_filter = self.line_filter.clone();
+        table.caption = self.caption.clone();
+        table.groups = self.groups.clone();
+        table
     }
 
     /// Print the table to standard output. Colors won't be displayed unless
@@ -396,11 +1063,55 @@ impl Table {
     /// In ANSI terminals, colors are displayed using ANSI escape characters. When for example the
     /// output is redirected to a file, or piped to another program, the output is considered
     /// as not beeing tty, and ANSI escape characters won't be displayed unless `force colorize`
-    /// is set to `true`.
+    /// is set to `true`. Unless forced, colors are also disabled by the `NO_COLOR` and
+    /// `CLICOLOR=0` environment variables, and `CLICOLOR_FORCE` enables them even when stdout
+    /// is not a tty terminal.
+    ///
+    /// Printing is subject to the `PrintLimits` configured in the table format.
     /// # Panic
-    /// Panic if writing to standard output fails
+    /// Panic if writing to standard output fails. See `try_print_tty` to handle errors instead
     pub fn print_tty(&self, force_colorize: bool) {
-        self.as_ref().print_tty(force_colorize);
+        if let Err(e) = self.try_print_tty(force_colorize) {
+            panic!("Cannot print table to standard output : {}", e);
+        }
+    }
+
+    /// Print the table to standard output like `print_tty`, and return the number of
+    /// printed lines, or the error which stopped printing. For example, an error of kind
+    /// `ErrorKind::BrokenPipe` is returned when the output is piped to a program which has
+    /// exited, like `head`
+    ///
+    /// # Examples
+    /// ```rust
+    /// # #[macro_use] extern crate prettytable;
+    /// # fn main() {
+    /// let table = table![["foo", "bar"], ["foobar", "baz"]];
+    /// match table.try_printstd() {
+    ///     Ok(lines) => assert_eq!(lines, 5),
+    ///     Err(ref e) if e.kind() == std::io::ErrorKind::BrokenPipe => (),
+    ///     Err(e) => panic!("{}", e),
+    /// }
+    /// # }
+    /// ```
+    pub fn try_print_tty(&self, force_colorize: bool) -> Result<usize, Error> {
+        let limits = self.format.get_limits();
+        match (stdout(), stdout_colorized() || force_colorize) {
+            (Some(mut o), true) => {
+                let mut term = TermBuffer::new(&mut *o);
+                let mut out = LineCounter::new(&mut term);
+                self.__print(&mut out, &limits, Row::print_term)?;
+                out.flush()?;
+                Ok(out.lines())
+            }
+            _ => {
+                let stdout = io::stdout();
+                let mut lock = stdout.lock();
+                let mut out = LineCounter::new(&mut lock);
+                self.__print_plain(&mut out, &limits)?;
+                out.flush()?;
+                Ok(out.lines())
+            }
+        }
     }
 
     /// Print the table to standard output. Colors won't be displayed unless
@@ -409,15 +1120,21 @@ impl Table {
     /// To force colors rendering, use `print_tty()` method.
     /// Calling `printstd()` is equivalent to calling `print_tty(false)`
     /// # Panic
-    /// Panic if writing to standard output fails
+    /// Panic if writing to standard output fails. See `try_printstd` to handle errors instead
     pub fn printstd(&self) {
-        self.as_ref().printstd();
+        self.print_tty(false);
+    }
+
+    /// Print the table to standard output like `printstd`, and return the number of printed
+    /// lines, or the error which stopped printing. See `try_print_tty` for details
+    pub fn try_printstd(&self) -> Result<usize, Error> {
+        self.try_print_tty(false)
     }
 
     /// Write the table to the specified writer.
     #[cfg(feature = "csv")]
     pub fn to_csv<W: Write>(&self, w: W) -> csv::Result<csv::Writer<W>> {
-        self.as_ref().to_csv(w)
+        self.to_csv_writer(csv::Writer::from_writer(w))
     }
 
     /// Write the table to the specified writer.
@@ -425,291 +1142,2521 @@ impl Table {
     /// This allows for format customisation.
     #[cfg(feature = "csv")]
     pub fn to_csv_writer<W: Write>(&self, writer: csv::Writer<W>) -> csv::Result<csv::Writer<W>> {
-        self.as_ref().to_csv_writer(writer)
+        self.write_csv(writer, true)
     }
-}
 
-impl Index<usize> for Table {
-    type Output = Row;
-    fn index(&self, idx: usize) -> &Self::Output {
-        &self.rows[idx]
+    /// Write the table to the specified writer, with the CSV format and whether titles
+    /// are written set by `options`
+    #[cfg(feature = "csv")]
+    pub fn to_csv_opts<W: Write>(&self,
+                                 w: W,
+                                 options: &CsvOptions)
+                                 -> csv::Result<csv::Writer<W>> {
+        self.write_csv(options.writer(w), options.get_titles())
     }
-}
 
-impl<'a> Index<usize> for TableSlice<'a> {
-    type Output = Row;
-    fn index(&self, idx: usize) -> &Self::Output {
-        &self.rows[idx]
+    /// Write the rows, and the titles if `with_titles` is true, to `writer`
+    #[cfg(feature = "csv")]
+    fn write_csv<W: Write>(&self,
+                           mut writer: csv::Writer<W>,
+                           with_titles: bool)
+                           -> csv::Result<csv::Writer<W>> {
+        let (titles, rows) = add_computed_columns(self.computed, self.titles, self.rows.iter());
+        for title in titles.iter().filter(|_| with_titles) {
+            writer.write_record(title.iter().map(|c| c.get_content()))?;
+        }
+        for row in &rows {
+            writer.write_record(row.iter().map(|c| c.get_content()))?;
+        }
+
+        writer.flush()?;
+        Ok(writer)
     }
 }
 
-impl IndexMut<usize> for Table {
-    fn index_mut(&mut self, idx: usize) -> &mut Self::Output {
-        &mut self.rows[idx]
-    }
+lazy_static! {
+    /// Internal only. Titles of a table printed without titles
+    static ref NO_TITLES: Option<Row> = None;
 }
 
-impl fmt::Display for Table {
-    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
-        self.as_ref().fmt(fmt)
+/// Internal only. Wait for the user to press enter if `interactive` is `true`,
+/// or print an empty line
+fn wait_next_page<T: Write + ?Sized>(out: &mut T, interactive: bool) -> Result<(), Error> {
+    if !interactive {
+        return out.write_all(NEWLINE);
     }
+    out.write_all(b"-- Press enter to continue --")?;
+    out.flush()?;
+    let mut line = String::new();
+    io::stdin().read_line(&mut line)?;
+    Ok(())
 }
 
-impl<'a> fmt::Display for TableSlice<'a> {
-    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
-        let mut writer = StringWriter::new();
-        if self.print(&mut writer).is_err() {
-            return Err(fmt::Error);
-        }
-        fmt.write_str(writer.as_string())
-    }
+/// Internal only. Compute and return the number of column in `rows`
+fn get_column_num<'r, I: Iterator<Item = &'r Row>>(rows: I) -> usize {
+    rows.map(|r| r.len()).max().unwrap_or(0)
 }
 
-impl<B: ToString, A: IntoIterator<Item = B>> FromIterator<A> for Table {
-    fn from_iter<T>(iterator: T) -> Table
-        where T: IntoIterator<Item = A>
-    {
-        Self::init(iterator.into_iter().map(Row::from).collect())
+/// Internal only. Build a summary row, with `label` in column `label_column`, and the values
+/// of `sums` in numeric columns, rounded to the number of `decimals` of their column.
+/// All cells are styled according to the style specifier `spec`
+fn summary_row(label: &str,
+               label_column: usize,
+               sums: &[f64],
+               numeric: &[bool],
+               decimals: &[usize],
+               spec: &str)
+               -> Row {
+    let mut row = Row::empty();
+    for (c, (sum, &num)) in sums.iter().zip(numeric).enumerate() {
+        let cell = if c == label_column {
+            Cell::new(label).style_spec(spec)
+        } else if num {
+            let mut cell = Cell::new(&format!("{:.*}", decimals[c], sum)).style_spec(spec);
+            cell.align(Alignment::RIGHT);
+            cell
+        } else {
+            Cell::default().style_spec(spec)
+        };
+        row.add_cell(cell);
     }
+    row
 }
 
-impl<T, A, B> From<T> for Table
-    where B: ToString,
-          A: IntoIterator<Item = B>,
-          T: IntoIterator<Item = A>
+/// Internal only. Get the number of digits after the decimal point of the number `text`
+fn number_decimals(text: &str) -> usize {
+    let text = text.trim();
+    text.find('.').map_or(0, |i| text[i + 1..].chars().take_while(|c| c.is_digit(10)).count())
+}
+
+/// Internal only. Get the width of all columns in `titles` and `rows`, constrained by `widths`,
+/// and return a vector with the result for each column. East Asian ambiguous characters are
+/// measured according to `ambiguous`
+fn get_all_column_width<'r, I>(titles: &'r Option<Row>,
+                               rows: I,
+                               widths: &[Width],
+                               ambiguous: AmbiguousWidth)
+                               -> Vec<usize>
+    where I: Iterator<Item = &'r Row> + Clone
 {
-    fn from(it: T) -> Table {
-        Self::from_iter(it)
+    let colnum = get_column_num(rows.clone());
+    let mut col_width = vec![0usize; colnum];
+    for r in titles.iter().chain(rows).filter(|r| !r.is_spanned()) {
+        for (i, w) in col_width.iter_mut().enumerate() {
+            let l = match ambiguous {
+                AmbiguousWidth::Narrow => r.get_cell_width(i),
+                AmbiguousWidth::Wide => {
+                    r.get_cell(i).map_or(0, |c| display_width_with(&c.get_content(), ambiguous))
+                }
+            };
+            if l > *w {
+                *w = l;
+            }
+        }
     }
+    layout::distribute_widths(&col_width, widths, None)
 }
 
-impl<'a> IntoIterator for &'a Table {
-    type Item = &'a Row;
-    type IntoIter = Iter<'a, Row>;
-    fn into_iter(self) -> Self::IntoIter {
-        self.as_ref().row_iter()
+/// Internal only. Print `titles` and `rows` to `out` using `format`,
+/// and stop early if `limits` are reached
+fn print_rows<'r, 'w, T, F, I>(out: &'w mut T,
+                               format: &TableFormat,
+                               titles: &'r Option<Row>,
+                               caption: &Option<(Row, CaptionPosition)>,
+                               computed: &[ComputedColumn],
+                               groups: &[(String, Range<usize>)],
+                               column_styles: &[(usize, Cell)],
+                               cell_formatter: &Option<CellFormatter>,
+                               rows: I,
+                               widths: &[Width],
+                               limits: &PrintLimits,
+                               f: F)
+                               -> Result<(), Error>
+    where T: Write + ?Sized,
+          F: Fn(&Row, &mut LineCounter<'w, T>, &TableFormat, &[usize]) -> Result<(), Error>,
+          I: ExactSizeIterator<Item = &'r Row> + Clone
+{
+    let mut out = LineCounter::new(out);
+    let out = &mut out;
+    let header = get_group_header(groups);
+    let derived = derive_rows(computed,
+                              column_styles,
+                              cell_formatter,
+                              format.get_stripe(),
+                              format.get_stripe_column(),
+                              titles,
+                              rows.clone());
+    if let Some((titles, rows)) = derived {
+        let col_width =
+            get_all_column_width(&titles, rows.iter(), widths, format.get_ambiguous_width());
+        return print_rows_with_width(out,
+                                     format,
+                                     &titles,
+                                     caption,
+                                     &header,
+                                     rows.iter(),
+                                     col_width,
+                                     limits,
+                                     f);
     }
+    // Compute columns width
+    let col_width =
+        get_all_column_width(titles, rows.clone(), widths, format.get_ambiguous_width());
+    print_rows_with_width(out, format, titles, caption, &header, rows, col_width, limits, f)
 }
 
-impl<'a> IntoIterator for &'a mut Table {
-    type Item = &'a mut Row;
-    type IntoIter = IterMut<'a, Row>;
-    fn into_iter(self) -> Self::IntoIter {
-        self.row_iter_mut()
+/// Internal only. Build the header row of column `groups`, with a cell spanning
+/// over the columns of each group. Empty groups, and groups overlapping a previous one,
+/// are ignored. Return `None` if there are no groups
+fn get_group_header(groups: &[(String, Range<usize>)]) -> Option<Row> {
+    if groups.is_empty() {
+        return None;
+    }
+    let mut sorted: Vec<&(String, Range<usize>)> = groups.iter().collect();
+    sorted.sort_by_key(|&&(_, ref r)| r.start);
+    let mut cells = Vec::new();
+    let mut column = 0;
+    for &&(ref name, ref range) in &sorted {
+        if range.start < column || range.end <= range.start {
+            continue;
+        }
+        while column < range.start {
+            cells.push((Cell::default(), 1));
+            column += 1;
+        }
+        cells.push((Cell::new_align(name, Alignment::CENTER), range.end - range.start));
+        column = range.end;
     }
+    Some(Row::spanned(cells))
 }
 
-/// Iterator over immutable cells in a column
-pub struct ColumnIter<'a>(Iter<'a, Row>, usize);
+/// Internal only. A column whose cells are computed from the other cells of each row
+#[derive(Clone)]
+struct ComputedColumn {
+    title: String,
+    compute: Arc<Fn(&Row) -> String + Send + Sync>,
+}
 
-impl<'a> Iterator for ColumnIter<'a> {
-    type Item = &'a Cell;
-    fn next(&mut self) -> Option<&'a Cell> {
-        self.0.next().and_then(|row| row.get_cell(self.1))
+impl fmt::Debug for ComputedColumn {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(fmt, "ComputedColumn({:?})", self.title)
     }
 }
 
-/// Iterator over mutable cells in a column
-pub struct ColumnIterMut<'a>(IterMut<'a, Row>, usize);
+/// Internal only. Return copies of `titles` and `rows` with the `computed` columns appended,
+/// after the last column. Banner rows are left unchanged
+fn add_computed_columns<'r, I>(computed: &[ComputedColumn],
+                               titles: &'r Option<Row>,
+                               rows: I)
+                               -> (Option<Row>, Vec<Row>)
+    where I: Iterator<Item = &'r Row> + Clone
+{
+    let colnum = get_column_num(titles.iter().chain(rows.clone()));
+    let extend = |row: &Row, cells: Vec<Cell>| {
+        let mut row = row.clone();
+        if !row.is_banner() {
+            while row.len() < colnum {
+                row.add_cell(Cell::default());
+            }
+            for cell in cells {
+                row.add_cell(cell);
+            }
+        }
+        row
+    };
+    let titles = titles
+        .as_ref()
+        .map(|t| extend(t, computed.iter().map(|c| Cell::new(&c.title)).collect()));
+    let rows = rows.map(|r| {
+                            let cells = computed.iter().map(|c| Cell::new(&(c.compute)(r)));
+                            extend(r, cells.collect())
+                        })
+        .collect();
+    (titles, rows)
+}
 
-impl<'a> Iterator for ColumnIterMut<'a> {
-    type Item = &'a mut Cell;
-    fn next(&mut self) -> Option<&'a mut Cell> {
-        self.0.next().and_then(|row| row.get_mut_cell(self.1))
+/// Internal only. Return copies of `titles` and `rows` with the `computed` columns appended,
+/// the `column_styles` applied to the data cells without any style of their own,
+/// the attributes returned by `cell_formatter` added to data cells, and the `stripe` attribute
+/// added to the cells of every other data row, or every other group of rows delimited by
+/// `stripe_column`, unless they have their own background color.
+/// Return `None` if there is nothing to change
+fn derive_rows<'r, I>(computed: &[ComputedColumn],
+                      column_styles: &[(usize, Cell)],
+                      cell_formatter: &Option<CellFormatter>,
+                      stripe: Option<Attr>,
+                      stripe_column: Option<usize>,
+                      titles: &'r Option<Row>,
+                      rows: I)
+                      -> Option<(Option<Row>, Vec<Row>)>
+    where I: Iterator<Item = &'r Row> + Clone
+{
+    if computed.is_empty() && column_styles.is_empty() && cell_formatter.is_none() &&
+       stripe.is_none() {
+        return None;
+    }
+    let (titles, mut rows) = if computed.is_empty() {
+        (titles.clone(), rows.cloned().collect::<Vec<_>>())
+    } else {
+        add_computed_columns(computed, titles, rows)
+    };
+    for row in rows.iter_mut().filter(|r| !r.is_spanned()) {
+        for &(column, ref style) in column_styles {
+            if let Some(cell) = row.get_mut_cell(column) {
+                cell.inherit_style(style);
+            }
+        }
     }
+    if let Some(ref formatter) = *cell_formatter {
+        for (i, row) in rows.iter_mut().enumerate().filter(|&(_, ref r)| !r.is_spanned()) {
+            for (j, cell) in row.iter_mut().enumerate() {
+                if let Some(attrs) = formatter.format(i, j, &cell.get_content()) {
+                    for attr in attrs {
+                        cell.style(attr);
+                    }
+                }
+            }
+        }
+    }
+    if let Some(stripe) = stripe {
+        let mut striped = false;
+        // Content of the group column in the previous data row, if any
+        let mut key: Option<Option<String>> = None;
+        for (i, row) in rows.iter_mut().enumerate() {
+            striped = match stripe_column {
+                Some(_) if row.is_spanned() => striped,
+                Some(column) => {
+                    let content = Some(row.get_cell(column).map(|c| c.get_content()));
+                    let changed = key.is_some() && content != key;
+                    key = content;
+                    striped != changed
+                }
+                None => i % 2 == 1,
+            };
+            if !striped {
+                continue;
+            }
+            for cell in row.iter_mut() {
+                let background = cell.get_bg_rgb().is_some() ||
+                                 cell.get_style().iter().any(|a| match *a {
+                                                                 Attr::BackgroundColor(_) => true,
+                                                                 _ => false,
+                                                             });
+                if !background {
+                    cell.style(stripe);
+                }
+            }
+        }
+    }
+    Some((titles, rows))
 }
 
-impl<'a> AsRef<TableSlice<'a>> for TableSlice<'a> {
-    fn as_ref(&self) -> &TableSlice<'a> {
-        self
+/// Internal only. Rows which can be printed by `print_rows_with_width`
+trait PrintRow {
+    /// Print the row to `out` without any style
+    fn print_plain<T: Write + ?Sized>(&self,
+                                      out: &mut T,
+                                      format: &TableFormat,
+                                      col_width: &[usize])
+                                      -> Result<(), Error>;
+
+    /// Return a copy of this titles row styled according to the style specifier `spec`,
+    /// or `None` if this kind of row cannot be styled
+    fn with_title_style(&self, _spec: &str) -> Option<Self>
+        where Self: Sized
+    {
+        None
     }
 }
 
-impl<'a> AsRef<TableSlice<'a>> for Table {
-    fn as_ref(&self) -> &TableSlice<'a> {
-        unsafe {
-            // All this is a bit hacky. Let's try to find something else
-            let s = &mut *((self as *const Table) as *mut Table);
-            s.rows.shrink_to_fit();
-            transmute(self)
+impl PrintRow for Row {
+    fn print_plain<T: Write + ?Sized>(&self,
+                                      out: &mut T,
+                                      format: &TableFormat,
+                                      col_width: &[usize])
+                                      -> Result<(), Error> {
+        self.print(out, format, col_width)
+    }
+
+    fn with_title_style(&self, spec: &str) -> Option<Row> {
+        let style = Cell::default().style_spec(spec);
+        let mut row = self.clone();
+        for cell in row.iter_mut() {
+            let unstyled = cell.get_style().is_empty() && cell.get_fg_rgb().is_none() &&
+                           cell.get_bg_rgb().is_none();
+            if unstyled {
+                cell.inherit_style(&style);
+                if cell.get_alignment() == Alignment::LEFT {
+                    cell.align(style.get_alignment());
+                }
+            }
         }
+        Some(row)
     }
 }
 
-/// Trait implemented by types which can be sliced
-pub trait Slice<'a, E> {
-    /// Type output after slicing
-    type Output: 'a;
-    /// Get a slice from self
-    fn slice(&'a self, arg: E) -> Self::Output;
+/// Internal only. Adjust the columns width computed from the content to the column and width
+/// `limits` and to the minimum width of the table. Return the adjusted widths of the shown
+/// columns and the number of hidden columns
+fn fit_column_width(format: &TableFormat,
+                    mut col_width: Vec<usize>,
+                    limits: &PrintLimits)
+                    -> (Vec<usize>, usize) {
+    let hidden_cols = limits
+        .get_columns()
+        .map_or(0, |max| col_width.len().saturating_sub(max));
+    let shown_cols = col_width.len() - hidden_cols;
+    col_width.truncate(shown_cols);
+    if let Some(max) = limits.get_width() {
+        let budget = layout::content_budget(format, col_width.len(), max);
+        col_width = layout::distribute_widths(&col_width, &[], Some(budget));
+    }
+    if let Some(min) = format.get_min_width() {
+        let budget = layout::content_budget(format, col_width.len(), min);
+        col_width = layout::expand_widths(&col_width, budget, format.get_remainder_policy());
+    }
+    (col_width, hidden_cols)
 }
 
-impl<'a, T, E> Slice<'a, E> for T
-    where T: AsRef<TableSlice<'a>>,
-          [Row]: Index<E, Output = [Row]>
+/// Internal only. Print `titles` and `rows` to `out` using `format` and already computed
+/// columns width, and stop early if `limits` are reached. The bytes written to `out` are
+/// counted to enforce the byte limit
+fn print_rows_with_width<'w, T, F, I, R, P>(out: &mut LineCounter<'w, T>,
+                                            format: &TableFormat,
+                                            titles: &Option<P>,
+                                            caption: &Option<(P, CaptionPosition)>,
+                                            header: &Option<P>,
+                                            rows: I,
+                                            col_width: Vec<usize>,
+                                            limits: &PrintLimits,
+                                            f: F)
+                                            -> Result<(), Error>
+    where T: Write + ?Sized,
+          F: Fn(&P, &mut LineCounter<'w, T>, &TableFormat, &[usize]) -> Result<(), Error>,
+          I: ExactSizeIterator<Item = R>,
+          R: Borrow<P>,
+          P: PrintRow
 {
-    type Output = TableSlice<'a>;
-    fn slice(&'a self, arg: E) -> Self::Output {
-        let sl = self.as_ref();
-        TableSlice {
-            format: sl.format,
-            titles: sl.titles,
-            rows: sl.rows.index(arg),
+    let styled_titles = match (titles.as_ref(), format.get_title_style()) {
+        (Some(t), Some(spec)) => t.with_title_style(spec),
+        _ => None,
+    };
+    let titles = if styled_titles.is_some() {
+        &styled_titles
+    } else {
+        titles
+    };
+    let (col_width, hidden_cols) = fit_column_width(format, col_width, limits);
+    let start = out.bytes();
+    // The caption is printed as a single row, as wide as the whole table
+    let mut caption_format = TableFormat::new();
+    caption_format.indent(format.get_indent());
+    let table_width = [layout::table_width(format, &col_width)];
+    if let Some((ref c, CaptionPosition::Above)) = *caption {
+        f(c, out, &caption_format, &table_width)?;
+    }
+    format.print_line_separator(out, &col_width, LinePosition::Top)?;
+    if let Some(ref h) = *header {
+        f(h, out, format, &col_width)?;
+        format.print_line_separator(out, &col_width, LinePosition::Intern)?;
+    }
+    if let Some(ref t) = *titles {
+        f(t, out, format, &col_width)?;
+        format.print_line_separator(out, &col_width, LinePosition::Title)?;
+    }
+    // Print rows
+    let total_rows = rows.len();
+    let max_rows = limits.get_rows().unwrap_or(total_rows);
+    let mut printed_rows = 0;
+    for r in rows.take(max_rows) {
+        if limits.get_bytes().map_or(false, |max| out.bytes() - start >= max) {
+            break;
+        }
+        let r = r.borrow();
+        if printed_rows > 0 {
+            format.print_line_separator(out, &col_width, LinePosition::Intern)?;
+            if let (Some(ref t), Some(n)) = (titles.as_ref(), format.get_repeat_titles()) {
+                if printed_rows % n == 0 {
+                    f(t, out, format, &col_width)?;
+                    format.print_line_separator(out, &col_width, LinePosition::Title)?;
+                }
+            }
         }
+        f(r, out, format, &col_width)?;
+        printed_rows += 1;
     }
+    format.print_line_separator(out, &col_width, LinePosition::Bottom)?;
+    if let Some((ref c, CaptionPosition::Below)) = *caption {
+        f(c, out, &caption_format, &table_width)?;
+    }
+    limits.print_marker(out, format.get_indent(), total_rows - printed_rows, hidden_cols)?;
+    out.flush()
 }
 
-/// Create a table filled with some values
-///
-/// All the arguments used for elements must implement the `std::string::ToString` trait
-/// # Syntax
-/// ```text
-/// table!([Element1_ row1, Element2_ row1, ...], [Element1_row2, ...], ...);
-/// ```
-///
-/// # Example
-/// ```
-/// # #[macro_use] extern crate prettytable;
-/// # fn main() {
-/// // Create a table initialized with some rows :
-/// let tab = table!(["Element1", "Element2", "Element3"],
-/// 				 [1, 2, 3],
-/// 				 ["A", "B", "C"]
-/// 				 );
-/// # drop(tab);
-/// # }
-/// ```
-///
-/// Some style can also be given in table creation
+impl<'a> IntoIterator for &'a TableSlice<'a> {
+    type Item = &'a Row;
+    type IntoIter = Iter<'a, Row>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.row_iter()
+    }
+}
+
+/// A borrowed immutable view over a selection of rows of a `Table`.
+/// Unlike `TableSlice`, selected rows do not need to be contiguous.
 ///
-/// ```
+/// # Examples
+/// ```rust
 /// # #[macro_use] extern crate prettytable;
 /// # fn main() {
-/// let tab = table!([FrByl->"Element1", Fgc->"Element2", "Element3"],
-/// 				 [FrBy => 1, 2, 3],
-/// 				 ["A", "B", "C"]
-/// 				 );
-/// # drop(tab);
+/// let table = table![[1, 2, 3], [4, 5, 6], [7, 8, 9]];
+/// let view = table.sample(2, 42);
+/// view.printstd(); // Prints 2 randomly selected rows
 /// # }
 /// ```
-///
-/// For details about style specifier syntax, check doc for [`Cell::style_spec`](cell/struct.Cell.html#method.style_spec) method
-#[macro_export]
-macro_rules! table {
-    ($([$($content:tt)*]), *) => (
-        $crate::Table::init(vec![$(row![$($content)*]), *])
-    );
-}
-
-/// Create a table with `table!` macro, print it to standard output, then return this table for future usage.
-///
-/// The syntax is the same that the one for the `table!` macro
-#[macro_export]
-macro_rules! ptable {
-    ($($content:tt)*) => (
-        {
-            let tab = table!($($content)*);
-            tab.printstd();
-            tab
-        }
-    );
+#[derive(Clone, Debug)]
+pub struct TableView<'a> {
+    format: &'a TableFormat,
+    titles: &'a Option<Row>,
+    widths: &'a Vec<Width>,
+    line_filter: &'a Option<LineFilter>,
+    caption: &'a Option<(Row, CaptionPosition)>,
+    computed: &'a Vec<ComputedColumn>,
+    groups: &'a Vec<(String, Range<usize>)>,
+    column_styles: &'a Vec<(usize, Cell)>,
+    cell_formatter: &'a Option<CellFormatter>,
+    rows: &'a [Row],
+    indices: Vec<usize>,
 }
 
-#[cfg(test)]
-mod tests {
-    use Table;
-    use Slice;
-    use row::Row;
-    use cell::Cell;
-    use format;
-    use format::consts::{FORMAT_DEFAULT, FORMAT_NO_LINESEP, FORMAT_NO_COLSEP, FORMAT_CLEAN};
+impl<'a> TableView<'a> {
+    /// Compute and return the number of column
+    pub fn get_column_num(&self) -> usize {
+        get_column_num(self.row_iter())
+    }
 
-    #[test]
-    fn table() {
-        let mut table = Table::new();
-        table.add_row(Row::new(vec![Cell::new("a"), Cell::new("bc"), Cell::new("def")]));
-        table.add_row(Row::new(vec![Cell::new("def"), Cell::new("bc"), Cell::new("a")]));
-        table.set_titles(Row::new(vec![Cell::new("t1"), Cell::new("t2"), Cell::new("t3")]));
-        let out = "\
-+-----+----+-----+
-| t1  | t2 | t3  |
-+=====+====+=====+
-| a   | bc | def |
-+-----+----+-----+
-| def | bc | a   |
-+-----+----+-----+
-";
-        assert_eq!(table.to_string().replace("\r\n", "\n"), out);
-        table.unset_titles();
-        let out = "\
-+-----+----+-----+
-| a   | bc | def |
-+-----+----+-----+
-| def | bc | a   |
-+-----+----+-----+
-";
-        assert_eq!(table.to_string().replace("\r\n", "\n"), out);
+    /// Get the number of rows
+    pub fn len(&self) -> usize {
+        self.indices.len()
     }
 
-    #[test]
-    fn index() {
-        let mut table = Table::new();
-        table.add_row(Row::new(vec![Cell::new("a"), Cell::new("bc"), Cell::new("def")]));
-        table.add_row(Row::new(vec![Cell::new("def"), Cell::new("bc"), Cell::new("a")]));
-        table.set_titles(Row::new(vec![Cell::new("t1"), Cell::new("t2"), Cell::new("t3")]));
-        assert_eq!(table[1][1].get_content(), "bc");
+    /// Check if the table view is empty
+    pub fn is_empty(&self) -> bool {
+        self.indices.is_empty()
+    }
 
-        table[1][1] = Cell::new("newval");
-        assert_eq!(table[1][1].get_content(), "newval");
+    /// Get an immutable reference to a row
+    pub fn get_row(&self, row: usize) -> Option<&'a Row> {
+        self.indices.get(row).map(|&i| &self.rows[i])
+    }
 
-        let out = "\
-+-----+--------+-----+
-| t1  | t2     | t3  |
-+=====+========+=====+
-| a   | bc     | def |
-+-----+--------+-----+
-| def | newval | a   |
-+-----+--------+-----+
-";
-        assert_eq!(table.to_string().replace("\r\n", "\n"), out);
+    /// Get the indices in the original table of the rows selected by this view
+    pub fn get_indices(&self) -> &[usize] {
+        &self.indices
     }
 
-    #[test]
-    fn table_size() {
-        let mut table = Table::new();
-        assert!(table.is_empty());
-        assert!(table.as_ref().is_empty());
-        assert_eq!(table.len(), 0);
-        assert_eq!(table.as_ref().len(), 0);
-        assert_eq!(table.get_column_num(), 0);
-        assert_eq!(table.as_ref().get_column_num(), 0);
-        table.add_empty_row();
-        assert!(!table.is_empty());
-        assert!(!table.as_ref().is_empty());
-        assert_eq!(table.len(), 1);
-        assert_eq!(table.as_ref().len(), 1);
-        assert_eq!(table.get_column_num(), 0);
-        assert_eq!(table.as_ref().get_column_num(), 0);
-        table[0].add_cell(Cell::default());
-        assert_eq!(table.get_column_num(), 1);
-        assert_eq!(table.as_ref().get_column_num(), 1);
+    /// Returns an iterator over immutable rows
+    pub fn row_iter(&self) -> ViewIter {
+        ViewIter(self.indices.iter(), self.rows)
     }
 
-    #[test]
-    fn get_row() {
-        let mut table = Table::new();
-        table.add_row(Row::new(vec![Cell::new("a"), Cell::new("bc"), Cell::new("def")]));
-        table.add_row(Row::new(vec![Cell::new("def"), Cell::new("bc"), Cell::new("a")]));
-        assert!(table.get_row(12).is_none());
-        assert!(table.get_row(1).is_some());
-        assert_eq!(table.get_row(1).unwrap()[0].get_content(), "def");
-        assert!(table.get_mut_row(12).is_none());
-        assert!(table.get_mut_row(1).is_some());
-        table.get_mut_row(1).unwrap().add_cell(Cell::new("z"));
-        assert_eq!(table.get_row(1).unwrap()[3].get_content(), "z");
+    /// Get a view of the rows of this view for which `f` returns `true`
+    pub fn filter_view<F: FnMut(&Row) -> bool>(&self, mut f: F) -> TableView<'a> {
+        let rows = self.rows;
+        TableView {
+            indices: self.indices.iter().cloned().filter(|&i| f(&rows[i])).collect(),
+            ..self.clone()
+        }
     }
 
-    #[test]
+    /// Internal only
+    fn __print<'w, T: Write + ?Sized, F>(&self,
+                                         out: &'w mut T,
+                                         limits: &PrintLimits,
+                                         f: F)
+                                         -> Result<(), Error>
+        where F: Fn(&Row, &mut LineCounter<'w, T>, &TableFormat, &[usize]) -> Result<(), Error>
+    {
+        print_rows(out,
+                   self.format,
+                   self.titles,
+                   self.caption,
+                   self.computed,
+                   self.groups,
+                   self.column_styles,
+                   self.cell_formatter,
+                   self.row_iter(),
+                   self.widths,
+                   limits,
+                   f)
+    }
+
+    /// Internal only. Print without styles, through the line filter if any
+    fn __print_plain<T: Write + ?Sized>(&self,
+                                        out: &mut T,
+                                        limits: &PrintLimits)
+                                        -> Result<(), Error> {
+        // Cells, padding and separators are written in many small pieces,
+        // which are gathered before being written to `out`
+        let mut out = BufWriter::new(out);
+        match *self.line_filter {
+            Some(ref filter) => {
+                self.__print(&mut LineFilterWriter::new(&mut out, filter), limits, Row::print)?
+            }
+            None => self.__print(&mut out, limits, Row::print)?,
+        }
+        out.flush()
+    }
+
+    /// Print the table view to `out`
+    pub fn print<T: Write + ?Sized>(&self, out: &mut T) -> Result<(), Error> {
+        self.__print_plain(out, &PrintLimits::new())
+    }
+
+    /// Print the table view to terminal `out`, applying styles when needed
+    pub fn print_term<T: Terminal + ?Sized>(&self, out: &mut T) -> Result<(), Error> {
+        let mut out = TermBuffer::new(out);
+        self.__print(&mut out, &PrintLimits::new(), Row::print_term)?;
+        out.flush()
+    }
+
+    /// Print the table view to standard output. See `TableSlice::print_tty` for details
+    /// # Panic
+    /// Panic if writing to standard output fails
+    pub fn print_tty(&self, force_colorize: bool) {
+        if let Err(e) = self.try_print_tty(force_colorize) {
+            panic!("Cannot print table to standard output : {}", e);
+        }
+    }
+
+    /// Print the table view to standard output, and return the number of printed lines.
+    /// See `TableSlice::try_print_tty` for details
+    pub fn try_print_tty(&self, force_colorize: bool) -> Result<usize, Error> {
+        let limits = self.format.get_limits();
+        match (stdout(), stdout_colorized() || force_colorize) {
+            (Some(mut o), true) => {
+                let mut term = TermBuffer::new(&mut *o);
+                let mut out = LineCounter::new(&mut term);
+                self.__print(&mut out, &limits, Row::print_term)?;
+                out.flush()?;
+                Ok(out.lines())
+            }
+            _ => {
+                let stdout = io::stdout();
+                let mut lock = stdout.lock();
+                let mut out = LineCounter::new(&mut lock);
+                self.__print_plain(&mut out, &limits)?;
+                out.flush()?;
+                Ok(out.lines())
+            }
+        }
+    }
+
+    /// Print the table view to standard output. See `TableSlice::printstd` for details
+    /// # Panic
+    /// Panic if writing to standard output fails
+    pub fn printstd(&self) {
+        self.print_tty(false);
+    }
+
+    /// Print the table view to standard output, and return the number of printed lines.
+    /// See `TableSlice::try_printstd` for details
+    pub fn try_printstd(&self) -> Result<usize, Error> {
+        self.try_print_tty(false)
+    }
+}
+
+impl<'a> Index<usize> for TableView<'a> {
+    type Output = Row;
+    fn index(&self, idx: usize) -> &Self::Output {
+        &self.rows[self.indices[idx]]
+    }
+}
+
+impl<'a> fmt::Display for TableView<'a> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        let mut writer = StringWriter::new();
+        if self.print(&mut writer).is_err() {
+            return Err(fmt::Error);
+        }
+        write_lines(fmt, writer.as_string())
+    }
+}
+
+impl<'a> IntoIterator for &'a TableView<'a> {
+    type Item = &'a Row;
+    type IntoIter = ViewIter<'a, 'a>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.row_iter()
+    }
+}
+
+/// A borrowed immutable view over some columns of a `Table`,
+/// obtained with the `slice_columns` or `filter_columns` methods
+#[derive(Clone, Debug)]
+pub struct ColumnSlice<'a> {
+    slice: TableSlice<'a>,
+    columns: Vec<usize>,
+}
+
+impl<'a> ColumnSlice<'a> {
+    /// Get the indices of the selected columns in the table, in increasing order
+    pub fn get_columns(&self) -> &[usize] {
+        &self.columns
+    }
+
+    /// Get the number of selected columns
+    pub fn get_column_num(&self) -> usize {
+        self.columns.len()
+    }
+
+    /// Narrow this view down to the selected columns for which `f` returns `true`.
+    /// See `TableSlice::filter_columns` for details. Indices are those of the columns
+    /// in the table
+    pub fn filter_columns<F: FnMut(&str, usize) -> bool>(mut self, mut f: F) -> ColumnSlice<'a> {
+        let no_rows: &[Row] = &[];
+        let (titles, _) =
+            add_computed_columns(self.slice.computed, self.slice.titles, no_rows.iter());
+        self.columns.retain(|&c| {
+            let title = titles.as_ref().and_then(|t| t.get_cell(c)).map(|t| t.get_content());
+            f(&title.unwrap_or_else(String::new), c)
+        });
+        self
+    }
+
+    /// Get the number of rows
+    pub fn len(&self) -> usize {
+        self.slice.len()
+    }
+
+    /// Check if the view has no rows
+    pub fn is_empty(&self) -> bool {
+        self.slice.is_empty()
+    }
+
+    /// Build a new table made of the selected columns, with the results of computed columns,
+    /// column styles and the cell formatter stored in plain cells, like `TableSlice::materialize`.
+    /// Spanned rows are kept unchanged. This is the table printed by this view
+    pub fn to_table(&self) -> Table {
+        let table = self.slice.materialize();
+        let mut selected = table.select_columns(&self.columns);
+        // Selected columns of a group are adjacent once selected, since they are sorted
+        for &(ref label, ref range) in table.groups.iter() {
+            let mut positions = self.columns
+                .iter()
+                .enumerate()
+                .filter(|&(_, &c)| c >= range.start && c < range.end)
+                .map(|(i, _)| i);
+            if let Some(first) = positions.next() {
+                let last = positions.last().unwrap_or(first);
+                selected.groups.push((label.clone(), first..last + 1));
+            }
+        }
+        selected
+    }
+
+    /// Print the selected columns to `out`
+    pub fn print<T: Write + ?Sized>(&self, out: &mut T) -> Result<(), Error> {
+        self.to_table().print(out)
+    }
+
+    /// Print the selected columns to terminal `out`, applying styles when needed
+    pub fn print_term<T: Terminal + ?Sized>(&self, out: &mut T) -> Result<(), Error> {
+        self.to_table().print_term(out)
+    }
+
+    /// Print the selected columns to standard output. See `TableSlice::print_tty` for details
+    /// # Panic
+    /// Panic if writing to standard output fails
+    pub fn print_tty(&self, force_colorize: bool) {
+        self.to_table().print_tty(force_colorize);
+    }
+
+    /// Print the selected columns to standard output, and return the number of printed lines.
+    /// See `TableSlice::try_print_tty` for details
+    pub fn try_print_tty(&self, force_colorize: bool) -> Result<usize, Error> {
+        self.to_table().try_print_tty(force_colorize)
+    }
+
+    /// Print the selected columns to standard output. See `TableSlice::printstd` for details
+    /// # Panic
+    /// Panic if writing to standard output fails
+    pub fn printstd(&self) {
+        self.print_tty(false);
+    }
+
+    /// Print the selected columns to standard output, and return the number of printed lines.
+    /// See `TableSlice::try_printstd` for details
+    pub fn try_printstd(&self) -> Result<usize, Error> {
+        self.try_print_tty(false)
+    }
+}
+
+impl<'a> fmt::Display for ColumnSlice<'a> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        self.to_table().fmt(fmt)
+    }
+}
+
+impl Table {
+    /// Create an empty table
+    pub fn new() -> Table {
+        Self::init(Vec::new())
+    }
+
+    /// Create a table initialized with `rows`
+    pub fn init(rows: Vec<Row>) -> Table {
+        Table {
+            rows: rows,
+            titles: None,
+            widths: Vec::new(),
+            line_filter: None,
+            caption: None,
+            computed: Vec::new(),
+            groups: Vec::new(),
+            column_styles: Vec::new(),
+            cell_formatter: None,
+            row_validator: None,
+            row_ids: None,
+            format: *consts::FORMAT_DEFAULT,
+        }
+    }
+
+    /// Borrow the whole table as a `TableSlice`. This is the same as `table.slice(..)`
+    pub fn as_slice(&self) -> TableSlice {
+        TableSlice {
+            format: &self.format,
+            titles: &self.titles,
+            widths: &self.widths,
+            line_filter: &self.line_filter,
+            caption: &self.caption,
+            computed: &self.computed,
+            groups: &self.groups,
+            column_styles: &self.column_styles,
+            cell_formatter: &self.cell_formatter,
+            rows: &self.rows,
+        }
+    }
+
+    /// Create a table from a CSV string
+    ///
+    /// For more customisability use `from_csv()`
+    #[cfg(feature = "csv")]
+    pub fn from_csv_string(csv_s: &str) -> csv::Result<Table> {
+        Table::from_csv(&mut csv::ReaderBuilder::new()
+                                 .has_headers(false)
+                                 .from_reader(csv_s.as_bytes()))
+    }
+
+    /// Create a table from a CSV file
+    ///
+    /// For more customisability use `from_csv()`
+    #[cfg(feature = "csv")]
+    pub fn from_csv_file<P: AsRef<Path>>(csv_p: P) -> csv::Result<Table> {
+        Table::from_csv(&mut csv::ReaderBuilder::new().has_headers(false).from_path(csv_p)?)
+    }
+
+    /// Create a table from a CSV reader. The CSV format (delimiter, quoting, headers, ...)
+    /// is customised by building the reader with a `csv::ReaderBuilder`.
+    /// If the reader has headers, they become the titles of the table.
+    ///
+    /// Reading stops at the first malformed record, and the returned error tells its position
+    ///
+    /// # Examples
+    /// ```rust
+    /// # extern crate csv;
+    /// # extern crate prettytable;
+    /// use prettytable::Table;
+    /// # fn main() {
+    /// let data = "name;qty\napple;3\npear;5";
+    /// let mut reader = csv::ReaderBuilder::new().delimiter(b';').from_reader(data.as_bytes());
+    /// let table = Table::from_csv(&mut reader).unwrap();
+    /// assert!(table.to_string().contains("| name  | qty |"));
+    /// assert_eq!(table[1][0].get_content(), "pear");
+    ///
+    /// let mut reader = csv::Reader::from_reader("a,b\n1,2\n3".as_bytes());
+    /// let err = Table::from_csv(&mut reader).unwrap_err();
+    /// assert_eq!(err.position().unwrap().line(), 3);
+    /// # }
+    /// ```
+    #[cfg(feature = "csv")]
+    pub fn from_csv<R: Read>(reader: &mut csv::Reader<R>) -> csv::Result<Table> {
+        let mut table = Table::new();
+        if reader.has_headers() {
+            table.set_titles(Row::from(reader.headers()?));
+        }
+        for record in reader.records() {
+            table.add_row(Row::from(&record?));
+        }
+        Ok(table)
+    }
+
+    /// Change the table format. Eg : Separators
+    pub fn set_format(&mut self, format: TableFormat) {
+        self.format = format;
+    }
+
+    /// Get a mutable reference to the internal format
+    pub fn get_format(&mut self) -> &mut TableFormat {
+        &mut self.format
+    }
+
+    /// Replace the color overrides of the table format by `overrides`, so that each of the
+    /// 16 basic and bright colors they contain is printed as the associated 24-bit color,
+    /// wherever it is used. This allows to restyle tables without changing their style
+    /// specifiers. Overrides are part of the format, and are replaced by `set_format`.
+    /// See `TableFormat::color_override`
+    ///
+    /// # Examples
+    /// ```rust
+    /// # #[macro_use] extern crate prettytable;
+    /// # extern crate term;
+    /// # fn main() {
+    /// use prettytable::cell::Rgb;
+    /// use term::color;
+    ///
+    /// let mut table = table![[Fr->"error", "message"]];
+    /// table.set_color_overrides(vec![(color::RED, Rgb(0xd9, 0x6c, 0x6c))]);
+    /// assert_eq!(table.get_format().get_color_override(color::RED), Some(Rgb(0xd9, 0x6c, 0x6c)));
+    /// # }
+    /// ```
+    pub fn set_color_overrides<I>(&mut self, overrides: I)
+        where I: IntoIterator<Item = (color::Color, Rgb)>
+    {
+        self.format.clear_color_overrides();
+        for (color, rgb) in overrides {
+            self.format.color_override(color, rgb);
+        }
+    }
+
+    /// Add a column whose cells are computed by calling `compute` on each row,
+    /// each time the table is printed or exported. This way derived columns stay in sync
+    /// with the other cells. Computed columns are printed after all the other columns,
+    /// and `title` is printed only if the table has titles. They are not stored in the rows
+    pub fn add_computed_column<F>(&mut self, title: &str, compute: F)
+        where F: Fn(&Row) -> String + Send + Sync + 'static
+    {
+        self.computed.push(ComputedColumn {
+                               title: title.to_string(),
+                               compute: Arc::new(compute),
+                           });
+    }
+
+    /// Remove all the computed columns
+    pub fn clear_computed_columns(&mut self) {
+        self.computed.clear();
+    }
+
+    /// Add a style attribute to column `column`, applied when printing to the data cells
+    /// of this column without any style of their own, including rows added later on.
+    /// Titles and spanned rows are not styled
+    pub fn set_column_style(&mut self, column: usize, attr: Attr) {
+        match self.column_styles.iter().position(|&(c, _)| c == column) {
+            Some(i) => self.column_styles[i].1.style(attr),
+            None => self.column_styles.push((column, Cell::default().with_style(attr))),
+        }
+    }
+
+    /// Set the style of column `column` by applying the given specifier string,
+    /// replacing its previous style. Only attributes, colors and vertical alignment are used,
+    /// horizontal alignment specifiers are ignored.
+    /// See `Cell::style_spec` for the syntax and `set_column_style` for details
+    pub fn set_column_style_spec(&mut self, column: usize, spec: &str) {
+        let fill = self.get_column_fill(column);
+        self.column_styles.retain(|&(c, _)| c != column);
+        self.column_styles.push((column, Cell::default().style_spec(spec).with_fill(fill)));
+    }
+
+    /// Set the vertical alignment of the data cells of column `column` aligned on top,
+    /// when they are shorter than their row. See `Cell::valign`
+    pub fn set_column_valign(&mut self, column: usize, valign: VerticalAlignment) {
+        match self.column_styles.iter().position(|&(c, _)| c == column) {
+            Some(i) => self.column_styles[i].1.valign(valign),
+            None => {
+                let mut style = Cell::default();
+                style.valign(valign);
+                self.column_styles.push((column, style));
+            }
+        }
+    }
+
+    /// Get the vertical alignment of the data cells of column `column`
+    pub fn get_column_valign(&self, column: usize) -> VerticalAlignment {
+        self.column_styles
+            .iter()
+            .find(|&&(c, _)| c == column)
+            .map(|&(_, ref style)| style.get_valign())
+            .unwrap_or(VerticalAlignment::Top)
+    }
+
+    /// Set the character filling the blank space around the content of the data cells of
+    /// column `column` filled with spaces, eg : `.` for leader lines. See `Cell::set_fill`
+    pub fn set_column_fill(&mut self, column: usize, fill: char) {
+        match self.column_styles.iter().position(|&(c, _)| c == column) {
+            Some(i) => self.column_styles[i].1.set_fill(fill),
+            None => self.column_styles.push((column, Cell::default().with_fill(fill))),
+        }
+    }
+
+    /// Get the character filling the blank space in the data cells of column `column`
+    pub fn get_column_fill(&self, column: usize) -> char {
+        self.column_styles
+            .iter()
+            .find(|&&(c, _)| c == column)
+            .map(|&(_, ref style)| style.get_fill())
+            .unwrap_or(' ')
+    }
+
+    /// Render the numbers of the data cells of column `column` with `format`, and align
+    /// them to the right. Cells which are not numbers are left unchanged, and the style of
+    /// reformatted cells is kept. Titles and spanned rows are not modified
+    ///
+    /// # Examples
+    /// ```rust
+    /// # #[macro_use] extern crate prettytable;
+    /// use prettytable::format::NumberFormat;
+    /// # fn main() {
+    /// let mut table = table!(["1234.5"], ["n/a"], ["-1000000"]);
+    /// table.format_numbers(0, &NumberFormat::new().decimals(2));
+    /// assert_eq!(table[0][0].get_content(), "1,234.50");
+    /// assert_eq!(table[1][0].get_content(), "n/a");
+    /// assert_eq!(table[2][0].get_content(), "-1,000,000.00");
+    /// # }
+    /// ```
+    pub fn format_numbers(&mut self, column: usize, format: &NumberFormat) {
+        for row in self.rows.iter_mut().filter(|r| !r.is_spanned()) {
+            let formatted = match row.get_cell(column) {
+                Some(cell) => {
+                    let content = cell.get_content();
+                    let mut formatted = match content.trim().parse::<i64>() {
+                        Ok(value) => Cell::from_i64_with(value, format),
+                        Err(_) => {
+                            match parse_number(&content) {
+                                Some(value) => Cell::from_f64_with(value, format),
+                                None => continue,
+                            }
+                        }
+                    };
+                    formatted.inherit_style(cell);
+                    formatted
+                }
+                None => continue,
+            };
+            let _ = row.set_cell(formatted, column);
+        }
+    }
+
+    /// Set a function called at print time with the index of each data row and column,
+    /// and the content of the cell, returning style attributes added to the cell if any.
+    /// This allows value based styling, eg : negative numbers in red, without modifying cells.
+    /// Row indices are relative to the printed slice or view. Titles and spanned rows are not
+    /// formatted
+    pub fn set_cell_formatter<F>(&mut self, formatter: F)
+        where F: Fn(usize, usize, &str) -> Option<Vec<Attr>> + Send + Sync + 'static
+    {
+        self.cell_formatter = Some(CellFormatter::new(formatter));
+    }
+
+    /// Unset the cell formatter
+    pub fn unset_cell_formatter(&mut self) {
+        self.cell_formatter = None;
+    }
+
+    /// Remove the styles of all columns
+    pub fn clear_column_styles(&mut self) {
+        self.column_styles.clear();
+    }
+
+    /// Set the alignment of each column according to the type of its first non-empty cell :
+    /// numbers are aligned right, booleans are centered, and text is aligned left.
+    /// Titles and spanned rows are left unchanged
+    pub fn auto_align(&mut self) {
+        for col in 0..self.get_column_num() {
+            let first = self.rows
+                .iter()
+                .filter(|r| !r.is_spanned())
+                .filter_map(|r| r.get_cell(col))
+                .map(|c| c.get_content())
+                .find(|c| !c.trim().is_empty());
+            let align = match first.map(|c| c.trim().to_lowercase()) {
+                Some(ref c) if parse_number(c).is_some() => Alignment::RIGHT,
+                Some(ref c) if c == "true" || c == "false" => Alignment::CENTER,
+                Some(_) => Alignment::LEFT,
+                None => continue,
+            };
+            for row in self.rows.iter_mut().filter(|r| !r.is_spanned()) {
+                if let Some(cell) = row.get_mut_cell(col) {
+                    cell.align(align);
+                }
+            }
+        }
+    }
+
+    /// Set the background color of the numeric cells of `column` along a gradient, from
+    /// `min_color` for the lowest value of the column to `max_color` for the highest one.
+    /// Cells which are not numbers, titles and spanned rows are left unchanged.
+    /// Colors are computed from the current content, so this must be called again after
+    /// the column is modified
+    ///
+    /// # Examples
+    /// ```rust
+    /// # #[macro_use] extern crate prettytable;
+    /// use prettytable::cell::Rgb;
+    /// # fn main() {
+    /// let mut table = table![["cpu", "12.5"], ["mem", "87"], ["disk", "40"]];
+    /// table.colorize_column_gradient(1, Rgb(0, 128, 0), Rgb(192, 0, 0));
+    /// assert_eq!(table[1][1].get_bg_rgb(), Some(Rgb(192, 0, 0)));
+    /// # }
+    /// ```
+    pub fn colorize_column_gradient(&mut self, column: usize, min_color: Rgb, max_color: Rgb) {
+        let values: Vec<f64> = self.rows
+            .iter()
+            .filter(|r| !r.is_spanned())
+            .filter_map(|r| r.get_cell(column))
+            .filter_map(|c| parse_number(&c.get_content()))
+            .collect();
+        let min = values.iter().fold(::std::f64::INFINITY, |m, &v| m.min(v));
+        let max = values.iter().fold(::std::f64::NEG_INFINITY, |m, &v| m.max(v));
+        for row in self.rows.iter_mut().filter(|r| !r.is_spanned()) {
+            if let Some(cell) = row.get_mut_cell(column) {
+                if let Some(value) = parse_number(&cell.get_content()) {
+                    let ratio = if max > min { (value - min) / (max - min) } else { 0.0 };
+                    cell.bg_rgb(min_color.blend(max_color, ratio));
+                }
+            }
+        }
+    }
+
+    /// Define a named group of `columns`, printed as an extra header row above the titles,
+    /// with a cell spanning over all the columns of the group. Groups must not overlap
+    pub fn add_column_group(&mut self, name: &str, columns: Range<usize>) {
+        self.groups.push((name.to_string(), columns));
+    }
+
+    /// Get the names and column ranges of all column groups
+    pub fn get_column_groups(&self) -> &[(String, Range<usize>)] {
+        &self.groups
+    }
+
+    /// Set a caption printed above the table frame, aligned with `align` relatively to the
+    /// table width. Long captions are truncated to the table width
+    pub fn set_caption(&mut self, caption: &str, align: Alignment) {
+        let position = self.caption.as_ref().map_or(CaptionPosition::Above, |&(_, p)| p);
+        let row = Row::banner(Cell::new_align(caption, align));
+        self.caption = Some((row, position));
+    }
+
+    /// Set the position of the caption, above or below the table frame.
+    /// Has no effect if no caption is set
+    pub fn set_caption_position(&mut self, position: CaptionPosition) {
+        if let Some((_, ref mut p)) = self.caption {
+            *p = position;
+        }
+    }
+
+    /// Unset the caption
+    pub fn unset_caption(&mut self) {
+        self.caption = None;
+    }
+
+    /// Set a function applied to each line of the table when it is printed without styles,
+    /// eg : to prefix lines with a timestamp. Lines are passed without their line terminator
+    pub fn set_line_filter<F>(&mut self, filter: F)
+        where F: Fn(String) -> String + Send + Sync + 'static
+    {
+        self.line_filter = Some(LineFilter::new(filter));
+    }
+
+    /// Unset the line filter
+    pub fn unset_line_filter(&mut self) {
+        self.line_filter = None;
+    }
+
+    /// Set the width constraint of the column at position `column`.
+    /// Constraints are applied on top of the width computed from the cells content,
+    /// so that, for example, columns keep a stable width when a table is printed repeatedly
+    pub fn set_column_width(&mut self, column: usize, width: Width) {
+        if column >= self.widths.len() {
+            self.widths.resize(column + 1, Width::Auto);
+        }
+        self.widths[column] = width;
+    }
+
+    /// Get the width constraint of the column at position `column`
+    pub fn get_column_width(&self, column: usize) -> Width {
+        self.widths.get(column).cloned().unwrap_or(Width::Auto)
+    }
+
+    /// Replace the width constraints of all the columns by `widths`,
+    /// for example loaded with `ColumnWidths::load`
+    pub fn set_column_widths(&mut self, widths: &ColumnWidths) {
+        self.widths = widths.as_slice().to_vec();
+    }
+
+    /// Get the width constraints of all the columns, for example to save them
+    /// with `ColumnWidths::save`
+    pub fn get_column_widths(&self) -> ColumnWidths {
+        ColumnWidths::from(self.widths.clone())
+    }
+
+    /// Compute and return the number of column
+    pub fn get_column_num(&self) -> usize {
+        self.as_slice().get_column_num()
+    }
+
+    /// Get the number of rows
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Check if the table is empty
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    /// Set the optional title lines
+    pub fn set_titles(&mut self, titles: Row) {
+        self.titles = Some(titles);
+    }
+
+    /// Unset the title line
+    pub fn unset_titles(&mut self) {
+        self.titles = None;
+    }
+
+    /// Replace each title cell with the cell returned by `f`, which is given the column index
+    /// and the cell itself. Do nothing if the table has no titles
+    pub fn map_titles<F: FnMut(usize, &Cell) -> Cell>(&mut self, f: F) {
+        if let Some(ref mut titles) = self.titles {
+            titles.map(f);
+        }
+    }
+
+    /// Replace each cell of the table, titles excepted, with the cell returned by `f`,
+    /// which is given the row index, the column index and the cell itself
+    ///
+    /// # Examples
+    /// ```rust
+    /// # #[macro_use] extern crate prettytable;
+    /// # use prettytable::cell::Cell;
+    /// # fn main() {
+    /// let mut table = table!(["Name", "Token"]; => ["alice", "s3cr3t"], ["bob", "hunter2"]);
+    /// // Redact the second column
+    /// table.map_cells(|_, c, cell| if c == 1 { Cell::new("***") } else { cell.clone() });
+    /// assert_eq!(table[1][1].get_content(), "***");
+    /// # }
+    /// ```
+    pub fn map_cells<F: FnMut(usize, usize, &Cell) -> Cell>(&mut self, mut f: F) {
+        for (r, row) in self.rows.iter_mut().enumerate() {
+            row.map(|c, cell| f(r, c, cell));
+        }
+    }
+
+    /// Get a mutable reference to a row
+    pub fn get_mut_row(&mut self, row: usize) -> Option<&mut Row> {
+        self.rows.get_mut(row)
+    }
+
+    /// Get an immutable reference to a row
+    pub fn get_row(&self, row: usize) -> Option<&Row> {
+        self.rows.get(row)
+    }
+
+    /// Get an immutable reference to the cell at `(row, column)`, or `None` if it does not exist.
+    /// Unlike `set_element`, the row index comes first, like with `table[(row, column)]`
+    ///
+    /// # Examples
+    /// ```rust
+    /// # #[macro_use] extern crate prettytable;
+    /// # fn main() {
+    /// let mut table = table![["a", "b"], ["c", "d"]];
+    /// assert_eq!(table[(1, 0)].get_content(), "c");
+    /// assert!(table.get((2, 0)).is_none());
+    /// table[(0, 1)].align(prettytable::format::Alignment::RIGHT);
+    /// # }
+    /// ```
+    pub fn get(&self, (row, column): (usize, usize)) -> Option<&Cell> {
+        self.rows.get(row).and_then(|r| r.get_cell(column))
+    }
+
+    /// Get a mutable reference to the cell at `(row, column)`, or `None` if it does not exist
+    pub fn get_mut(&mut self, (row, column): (usize, usize)) -> Option<&mut Cell> {
+        self.rows.get_mut(row).and_then(|r| r.get_mut_cell(column))
+    }
+
+    /// Append a row in the table, transferring ownership of this row to the table
+    /// and returning a mutable reference to the row
+    /// # Panic
+    /// Panic if the row is rejected by the row validator. See `set_row_validator`
+    pub fn add_row(&mut self, row: Row) -> &mut Row {
+        let l = self.rows.len();
+        self.insert_row(l, row)
+    }
+
+    /// Append a row in the table, transferring ownership of this row to the table
+    /// and returning a mutable reference to the row, or the error message returned
+    /// by the row validator if the row is rejected. See `set_row_validator`
+    pub fn try_add_row(&mut self, row: Row) -> Result<&mut Row, String> {
+        let l = self.rows.len();
+        self.try_insert_row(l, row)
+    }
+
+    /// Append all the rows from `rows` in the table, in order
+    /// # Panic
+    /// Panic if a row is rejected by the row validator. See `set_row_validator`
+    ///
+    /// # Examples
+    /// ```rust
+    /// use prettytable::Table;
+    /// use prettytable::row::Row;
+    ///
+    /// let mut table = Table::new();
+    /// table.add_rows((1..4).map(|i| Row::from(vec![i, i * i])));
+    /// assert_eq!(table.len(), 3);
+    /// ```
+    pub fn add_rows<I: IntoIterator<Item = Row>>(&mut self, rows: I) {
+        let rows = rows.into_iter();
+        self.rows.reserve(rows.size_hint().0);
+        for row in rows {
+            self.add_row(row);
+        }
+    }
+
+    /// Append rows made of the strings yielded by each item of `rows`, like calling `add_row`
+    /// with a row of `Cell::new` cells for each item, but faster when loading many rows.
+    /// The rows vector is pre-sized from the size hint of `rows`, and each row from the
+    /// length of the previous one, so that `Vec<Vec<&str>>` data is loaded without growing
+    /// any vector, and strings are copied into cells without any temporary copy.
+    /// Without a row validator, rows are pushed directly, skipping the checks of `add_row`.
+    /// Run `cargo run --release --example load_rows` to compare it with `add_row` loops
+    /// # Panic
+    /// Panic if a row is rejected by the row validator. See `set_row_validator`
+    ///
+    /// # Examples
+    /// ```rust
+    /// use prettytable::Table;
+    ///
+    /// let data = vec![vec!["a", "b"], vec!["c", "d"]];
+    /// let mut table = Table::new();
+    /// table.load_rows(&data);
+    /// assert_eq!(table.len(), 2);
+    /// assert_eq!(table[1][0].get_content(), "c");
+    /// ```
+    pub fn load_rows<I, R, S>(&mut self, rows: I)
+        where I: IntoIterator<Item = R>,
+              R: IntoIterator<Item = S>,
+              S: AsRef<str>
+    {
+        let rows = rows.into_iter();
+        self.rows.reserve(rows.size_hint().0);
+        let mut len = 0;
+        for row in rows {
+            let mut cells = Vec::with_capacity(len);
+            cells.extend(row.into_iter().map(|s| Cell::new(s.as_ref())));
+            len = cells.len();
+            if self.row_validator.is_some() {
+                self.add_row(Row::new(cells));
+            } else {
+                self.rows.push(Row::new(cells));
+            }
+        }
+    }
+
+    /// Move all the rows of `other` at the end of this table, leaving `other` without rows.
+    /// If this table has no titles, it takes the titles of `other`.
+    /// Column counts and titles are not checked, see `try_append`
+    /// # Panic
+    /// Panic if a row is rejected by the row validator. See `set_row_validator`
+    pub fn append(&mut self, other: &mut Table) {
+        if self.titles.is_none() {
+            self.titles = other.titles.clone();
+        }
+        let rows = mem::replace(&mut other.rows, Vec::new());
+        self.add_rows(rows);
+    }
+
+    /// Move all the rows of `other` at the end of this table like `append`, after checking that
+    /// both tables have the same number of columns and the same titles, if both have some,
+    /// and that all the rows are accepted by the row validator.
+    /// An error message is returned otherwise, and both tables are left unchanged.
+    /// The check of column counts is skipped if one of the tables has neither rows nor titles
+    ///
+    /// # Examples
+    /// ```rust
+    /// # #[macro_use] extern crate prettytable;
+    /// # fn main() {
+    /// let mut table = table![["a", 1]];
+    /// table.try_append(&mut table![["b", 2]]).unwrap();
+    /// assert_eq!(table.len(), 2);
+    /// assert!(table.try_append(&mut table![["c", 3, "extra"]]).is_err());
+    /// # }
+    /// ```
+    pub fn try_append(&mut self, other: &mut Table) -> Result<(), String> {
+        let empty = |t: &Table| t.titles.is_none() && t.rows.is_empty();
+        if !empty(self) && !empty(other) && self.get_column_num() != other.get_column_num() {
+            return Err(format!("Column count mismatch : expected {}, found {}",
+                               self.get_column_num(),
+                               other.get_column_num()));
+        }
+        if let (&Some(ref titles), &Some(ref other_titles)) = (&self.titles, &other.titles) {
+            let contents = |r: &Row| r.iter().map(Cell::get_content).collect::<Vec<_>>();
+            if contents(titles) != contents(other_titles) {
+                return Err("Titles mismatch".to_string());
+            }
+        }
+        if let Some(ref validator) = self.row_validator {
+            for row in other.rows.iter() {
+                validator.validate(row)?;
+            }
+        }
+        self.append(other);
+        Ok(())
+    }
+
+    /// Concatenate `tables` into a single table, like calling `append` on the first table with
+    /// each of the others. The resulting table keeps the format and settings of the first table,
+    /// and the first titles found. Return an empty table if there is no table to concatenate
+    ///
+    /// # Examples
+    /// ```rust
+    /// # #[macro_use] extern crate prettytable;
+    /// # fn main() {
+    /// use prettytable::Table;
+    ///
+    /// let shards = vec![table![["a", 1]], table![["b", 2], ["c", 3]]];
+    /// let table = Table::concat(shards);
+    /// assert_eq!(table.len(), 3);
+    /// # }
+    /// ```
+    pub fn concat<I: IntoIterator<Item = Table>>(tables: I) -> Table {
+        let mut tables = tables.into_iter();
+        let mut table = tables.next().unwrap_or_else(Table::new);
+        for mut other in tables {
+            table.append(&mut other);
+        }
+        table
+    }
+
+    /// Append a banner row in the table, printing `text` in a single cell spanning
+    /// all the columns. `spec` is a style specifier, as described in `Cell::style_spec`,
+    /// eg : `"cFr"` for a red, centered banner. Return a mutable reference to this new row.
+    pub fn add_banner(&mut self, text: &str, spec: &str) -> &mut Row {
+        self.add_row(Row::banner(Cell::new(text).style_spec(spec)))
+    }
+
+    /// Append an empty row in the table. Return a mutable reference to this new row.
+    pub fn add_empty_row(&mut self) -> &mut Row {
+        self.add_row(Row::default())
+    }
+
+    /// Insert `row` at the position `index`, and return a mutable reference to this row.
+    /// If index is higher than current numbers of rows, `row` is appended at the end of the table
+    /// # Panic
+    /// Panic if the row is rejected by the row validator. See `set_row_validator`
+    pub fn insert_row(&mut self, index: usize, row: Row) -> &mut Row {
+        match self.try_insert_row(index, row) {
+            Ok(row) => row,
+            Err(e) => panic!("Invalid row : {}", e),
+        }
+    }
+
+    /// Insert `row` at the position `index` like `insert_row`, and return a mutable reference
+    /// to this row, or the error message returned by the row validator if the row is rejected.
+    /// See `set_row_validator`
+    pub fn try_insert_row(&mut self, index: usize, row: Row) -> Result<&mut Row, String> {
+        if let Some(ref validator) = self.row_validator {
+            validator.validate(&row)?;
+        }
+        let index = cmp::min(index, self.rows.len());
+        if let Some(id) = row.get_id().map(|id| id.to_string()) {
+            self.track_row_id(id, index);
+        }
+        self.rows.insert(index, row);
+        Ok(&mut self.rows[index])
+    }
+
+    /// Internal only. Record that a row identified by `id` is inserted at `index`.
+    /// The index map is dropped, to be rebuilt when needed, unless the row is appended
+    /// with a new identifier
+    fn track_row_id(&mut self, id: String, index: usize) {
+        let appended = index == self.rows.len();
+        if let Some(ref mut ids) = self.row_ids {
+            if appended && !ids.contains_key(&id) {
+                ids.insert(id, index);
+                return;
+            }
+        }
+        self.row_ids = None;
+    }
+
+    /// Internal only. Get the index of the row identified by `id`, rebuilding the index map
+    /// if it does not exist yet, if it does not know `id` or if rows have been moved.
+    /// Identifiers can be set on rows without the table knowing it, so a missing entry
+    /// does not mean there is no such row
+    fn locate_row_id(&mut self, id: &str) -> Option<usize> {
+        match self.row_ids.as_ref().map(|ids| ids.get(id).cloned()) {
+            Some(Some(i)) if self.rows.get(i).and_then(|r| r.get_id()) == Some(id) => {
+                return Some(i)
+            }
+            _ => (),
+        }
+        let mut ids = HashMap::new();
+        for (i, row) in self.rows.iter().enumerate() {
+            if let Some(id) = row.get_id() {
+                ids.entry(id.to_string()).or_insert(i);
+            }
+        }
+        let index = ids.get(id).cloned();
+        self.row_ids = Some(ids);
+        index
+    }
+
+    /// Append `row` in the table, identified by `id`, or replace the row with the same
+    /// identifier if there is one, and return a mutable reference to this row.
+    /// Identified rows can then be found with `row_index`, and replaced with `update_row`,
+    /// wherever they are moved by insertions, removals or sorts.
+    /// Identifiers are kept in an index map, which is rebuilt when rows are moved
+    /// # Panic
+    /// Panic if the row is rejected by the row validator. See `set_row_validator`
+    ///
+    /// # Examples
+    /// ```rust
+    /// use prettytable::Table;
+    /// use prettytable::row::Row;
+    ///
+    /// let mut table = Table::new();
+    /// table.add_row_with_id("web", Row::from(vec!["web", "starting"]));
+    /// table.add_row_with_id("db", Row::from(vec!["db", "starting"]));
+    /// table.insert_row(0, Row::from(vec!["cache", "up"]));
+    /// table.update_row("web", Row::from(vec!["web", "up"]));
+    /// assert_eq!(table.row_index("web"), Some(1));
+    /// assert_eq!(table[1][1].get_content(), "up");
+    /// ```
+    pub fn add_row_with_id(&mut self, id: &str, mut row: Row) -> &mut Row {
+        row.set_id(id);
+        match self.locate_row_id(id) {
+            Some(index) => {
+                self.update_row(id, row);
+                &mut self.rows[index]
+            }
+            None => self.add_row(row),
+        }
+    }
+
+    /// Replace the row identified by `id` with `row`, which takes its identifier and position,
+    /// and return the replaced row, or `None` if there is no such row, in which case `row`
+    /// is dropped. See `add_row_with_id`
+    /// # Panic
+    /// Panic if the row is rejected by the row validator. See `set_row_validator`
+    pub fn update_row(&mut self, id: &str, mut row: Row) -> Option<Row> {
+        let index = match self.locate_row_id(id) {
+            Some(index) => index,
+            None => return None,
+        };
+        row.set_id(id);
+        if let Some(ref validator) = self.row_validator {
+            if let Err(e) = validator.validate(&row) {
+                panic!("Invalid row : {}", e);
+            }
+        }
+        Some(mem::replace(&mut self.rows[index], row))
+    }
+
+    /// Remove the row identified by `id` and return it, or `None` if there is no such row.
+    /// See `add_row_with_id`
+    pub fn remove_row_by_id(&mut self, id: &str) -> Option<Row> {
+        let index = match self.locate_row_id(id) {
+            Some(index) => index,
+            None => return None,
+        };
+        if let Some(ref mut ids) = self.row_ids {
+            ids.remove(id);
+        }
+        Some(self.rows.remove(index))
+    }
+
+    /// Get the current index of the row identified by `id`, if any. See `add_row_with_id`
+    pub fn row_index(&self, id: &str) -> Option<usize> {
+        match self.row_ids.as_ref().map(|ids| ids.get(id).cloned()) {
+            Some(Some(i)) if self.rows.get(i).and_then(|r| r.get_id()) == Some(id) => Some(i),
+            _ => self.rows.iter().position(|r| r.get_id() == Some(id)),
+        }
+    }
+
+    /// Get a reference to the row identified by `id`, if any. See `add_row_with_id`
+    pub fn get_row_by_id(&self, id: &str) -> Option<&Row> {
+        self.row_index(id).map(|i| &self.rows[i])
+    }
+
+    /// Set a function checking every row added to the table with `add_row`, `insert_row`
+    /// and the methods built on them, like `add_banner` or `add_empty_row`. Rows already in the
+    /// table are not checked. When the function returns an error, `try_add_row` and
+    /// `try_insert_row` return it, while `add_row` and `insert_row` panic.
+    /// This allows to enforce a schema at the point data enters the table
+    ///
+    /// # Examples
+    /// ```rust
+    /// # #[macro_use] extern crate prettytable;
+    /// # fn main() {
+    /// let mut table = table![["foo", 1]];
+    /// table.set_row_validator(|row| if row.len() == 2 {
+    ///     Ok(())
+    /// } else {
+    ///     Err(format!("expected 2 cells, found {}", row.len()))
+    /// });
+    /// assert!(table.try_add_row(row!["bar", 2]).is_ok());
+    /// assert_eq!(table.try_add_row(row!["baz"]).unwrap_err(), "expected 2 cells, found 1");
+    /// # }
+    /// ```
+    pub fn set_row_validator<F>(&mut self, validator: F)
+        where F: Fn(&Row) -> Result<(), String> + Send + Sync + 'static
+    {
+        self.row_validator = Some(RowValidator::new(validator));
+    }
+
+    /// Unset the row validator
+    pub fn unset_row_validator(&mut self) {
+        self.row_validator = None;
+    }
+
+    /// Modify a single element in the table
+    pub fn set_element(&mut self, element: &str, column: usize, row: usize) -> Result<(), &str> {
+        let rowline = self.get_mut_row(row).ok_or("Cannot find row")?;
+        // TODO: If a cell already exist, copy it's alignment parameter
+        rowline.set_cell(Cell::new(element), column)
+    }
+
+    /// Remove the row at position `index`. Silently skip if the row does not exist
+    pub fn remove_row(&mut self, index: usize) {
+        if index < self.rows.len() {
+            self.rows.remove(index);
+        }
+    }
+
+    /// Build a copy of this table whose rows alternate between the rows of this table and the
+    /// rows of `other`, starting with this table, which is useful to compare expected and
+    /// actual results. Remaining rows of the longest table are appended at the end.
+    /// If `style` is given, it is added to the cells of the rows coming from `other`.
+    /// The copy keeps the titles and settings of this table, or the titles of `other`
+    /// if this table has none. Rows are not checked by the row validator
+    ///
+    /// # Examples
+    /// ```rust
+    /// # #[macro_use] extern crate prettytable;
+    /// # extern crate term;
+    /// # fn main() {
+    /// use term::{Attr, color};
+    ///
+    /// let expected = table![["a", 1], ["b", 2]];
+    /// let actual = table![["a", 1], ["b", 3]];
+    /// let table = expected.interleave(&actual, Some(Attr::ForegroundColor(color::RED)));
+    /// assert_eq!(table.len(), 4);
+    /// assert_eq!(table[3][1].get_content(), "3");
+    /// # }
+    /// ```
+    pub fn interleave(&self, other: &Table, style: Option<Attr>) -> Table {
+        // Settings are copied, rows are cloned only once below
+        let mut table = Table {
+            format: self.format,
+            titles: self.titles.as_ref().or(other.titles.as_ref()).cloned(),
+            widths: self.widths.clone(),
+            line_filter: self.line_filter.clone(),
+            caption: self.caption.clone(),
+            computed: self.computed.clone(),
+            groups: self.groups.clone(),
+            column_styles: self.column_styles.clone(),
+            cell_formatter: self.cell_formatter.clone(),
+            row_validator: self.row_validator.clone(),
+            // Rows are moved, so the index of identified rows is rebuilt when needed
+            row_ids: None,
+            rows: Vec::with_capacity(self.rows.len() + other.rows.len()),
+        };
+        let mut others = other.rows.iter().map(|row| {
+            let mut row = row.clone();
+            if let Some(attr) = style {
+                for cell in row.iter_mut() {
+                    cell.style(attr);
+                }
+            }
+            row
+        });
+        for row in self.rows.iter() {
+            table.rows.push(row.clone());
+            if let Some(row) = others.next() {
+                table.rows.push(row);
+            }
+        }
+        table.rows.extend(others);
+        table
+    }
+
+    /// Remove all the rows, keeping the titles, format and other settings of the table
+    pub fn clear(&mut self) {
+        self.rows.clear();
+    }
+
+    /// Keep the first `len` rows and remove the others.
+    /// Nothing is removed if the table has `len` rows or less
+    pub fn truncate(&mut self, len: usize) {
+        self.rows.truncate(len);
+    }
+
+    /// Remove the last row and return it, or `None` if the table is empty
+    pub fn pop_row(&mut self) -> Option<Row> {
+        self.rows.pop()
+    }
+
+    /// Remove consecutive rows with the same content as the previous row, like `Vec::dedup`.
+    /// Styles are not compared
+    pub fn dedup(&mut self) {
+        self.dedup_with(false, |row| row.iter().map(Cell::get_content).collect::<Vec<_>>());
+    }
+
+    /// Remove consecutive rows with the same content as the previous row in `column`
+    pub fn dedup_by_key(&mut self, column: usize) {
+        self.dedup_with(false, |row| row.get_cell(column).map(Cell::get_content));
+    }
+
+    /// Remove all the rows with the same content as a previous row, keeping the first ones
+    pub fn dedup_all(&mut self) {
+        self.dedup_with(true, |row| row.iter().map(Cell::get_content).collect::<Vec<_>>());
+    }
+
+    /// Remove all the rows with the same content as a previous row in `column`,
+    /// keeping the first ones
+    ///
+    /// # Examples
+    /// ```rust
+    /// # #[macro_use] extern crate prettytable;
+    /// # fn main() {
+    /// let mut table = table![["disk full", 1], ["timeout", 2], ["disk full", 3]];
+    /// table.dedup_all_by_key(0);
+    /// assert_eq!(table.len(), 2);
+    /// # }
+    /// ```
+    pub fn dedup_all_by_key(&mut self, column: usize) {
+        self.dedup_with(true, |row| row.get_cell(column).map(Cell::get_content));
+    }
+
+    /// Internal only. Remove rows whose `key` is equal to the key of the previous row,
+    /// or of any previous row if `all` is true
+    fn dedup_with<K: Hash + Eq, F: Fn(&Row) -> K>(&mut self, all: bool, key: F) {
+        let mut seen = HashSet::new();
+        let mut previous = None;
+        self.rows.retain(|row| {
+            let k = key(row);
+            if all {
+                return seen.insert(k);
+            }
+            let keep = previous.as_ref() != Some(&k);
+            previous = Some(k);
+            keep
+        });
+    }
+
+    /// Keep only the rows for which `f` returns `true`, in order, like `Vec::retain`
+    ///
+    /// # Examples
+    /// ```rust
+    /// # #[macro_use] extern crate prettytable;
+    /// # fn main() {
+    /// let mut table = table![["build", "ok"], ["test", "failed"], ["lint", "failed"]];
+    /// table.retain(|row| row[1].get_content() == "failed");
+    /// assert_eq!(table.len(), 2);
+    /// # }
+    /// ```
+    pub fn retain<F: FnMut(&Row) -> bool>(&mut self, f: F) {
+        self.rows.retain(f);
+    }
+
+    /// Remove the rows for which `f` returns `true`, and return them in order
+    pub fn remove_rows_where<F: FnMut(&Row) -> bool>(&mut self, mut f: F) -> Vec<Row> {
+        let rows = mem::replace(&mut self.rows, Vec::new());
+        let (removed, kept) = rows.into_iter().partition(|r| f(r));
+        self.rows = kept;
+        removed
+    }
+
+    /// Return an iterator over the immutable cells of the column specified by `column`
+    pub fn column_iter(&self, column: usize) -> ColumnIter {
+        ColumnIter(self.rows.iter(), column)
+    }
+
+    /// Return an iterator over the mutable cells of the column specified by `column`
+    pub fn column_iter_mut(&mut self, column: usize) -> ColumnIterMut {
+        ColumnIterMut(self.rows.iter_mut(), column)
+    }
+
+    /// Append a column at the end of the table, with `title` and `cells`.
+    /// See `insert_column` for details
+    pub fn add_column(&mut self, title: &str, cells: Vec<Cell>) {
+        let columns = cmp::max(self.get_column_num(), self.titles.as_ref().map_or(0, Row::len));
+        self.insert_column(columns, title, cells);
+    }
+
+    /// Insert a column at position `index`, with `title` and `cells`, moving the next columns
+    /// to the right. Cell `i` is inserted in row `i`, rows shorter than `index` are padded with
+    /// empty cells, and rows beyond the last cell get an empty cell. New rows are added if there
+    /// are more cells than rows. `title` is used only if the table has titles, and spanned rows
+    /// are left unchanged and skipped, so that they don't take any cell. Column widths, styles
+    /// and groups are moved along with the columns
+    /// # Panic
+    /// Panic if a new row is rejected by the row validator. See `set_row_validator`
+    pub fn insert_column(&mut self, index: usize, title: &str, cells: Vec<Cell>) {
+        let columns = cmp::max(self.get_column_num(), self.titles.as_ref().map_or(0, Row::len));
+        let index = cmp::min(index, columns);
+        let insert = |row: &mut Row, cell: Cell| {
+            while row.len() < index {
+                row.add_cell(Cell::default());
+            }
+            row.insert_cell(index, cell);
+        };
+        if let Some(ref mut titles) = self.titles {
+            insert(titles, Cell::new(title));
+        }
+        let mut cells = cells.into_iter();
+        for row in self.rows.iter_mut().filter(|r| !r.is_spanned()) {
+            insert(row, cells.next().unwrap_or_else(Cell::default));
+        }
+        for cell in cells {
+            let mut row = Row::empty();
+            insert(&mut row, cell);
+            self.add_row(row);
+        }
+        if index < self.widths.len() {
+            self.widths.insert(index, Width::Auto);
+        }
+        for &mut (ref mut column, _) in self.column_styles.iter_mut() {
+            if *column >= index {
+                *column += 1;
+            }
+        }
+        for &mut (_, ref mut range) in self.groups.iter_mut() {
+            if range.start >= index {
+                range.start += 1;
+            }
+            if range.end > index {
+                range.end += 1;
+            }
+        }
+    }
+
+    /// Internal only. Build a copy of the table with only the given `columns`, in that order,
+    /// keeping the format, caption and line filter. Spanned rows are copied unchanged
+    fn select_columns(&self, columns: &[usize]) -> Table {
+        let select = |row: &Row| if row.is_spanned() {
+            row.clone()
+        } else {
+            Row::new(columns.iter()
+                         .map(|&c| row.get_cell(c).cloned().unwrap_or_else(Cell::default))
+                         .collect())
+        };
+        let mut table = Table::init(self.rows.iter().map(&select).collect());
+        table.titles = self.titles.as_ref().map(&select);
+        table.format = self.format;
+        table.widths = columns.iter().map(|&c| self.get_column_width(c)).collect();
+        table.line_filter = self.line_filter.clone();
+        table.caption = self.caption.clone();
+        table
+    }
+
+    /// Remove the column at position `index` from the titles and all the rows, moving the next
+    /// columns to the left. Spanned rows are left unchanged. Column widths, styles and groups
+    /// are moved along with the columns, and groups left empty are removed
+    pub fn remove_column(&mut self, index: usize) {
+        if let Some(ref mut titles) = self.titles {
+            titles.remove_cell(index);
+        }
+        for row in self.rows.iter_mut().filter(|r| !r.is_spanned()) {
+            row.remove_cell(index);
+        }
+        if index < self.widths.len() {
+            self.widths.remove(index);
+        }
+        self.column_styles.retain(|&(c, _)| c != index);
+        for &mut (ref mut column, _) in self.column_styles.iter_mut() {
+            if *column > index {
+                *column -= 1;
+            }
+        }
+        for &mut (_, ref mut range) in self.groups.iter_mut() {
+            if range.start > index {
+                range.start -= 1;
+            }
+            if range.end > index {
+                range.end -= 1;
+            }
+        }
+        self.groups.retain(|&(_, ref range)| range.start < range.end);
+    }
+
+    /// Returns an iterator over immutable rows
+    pub fn row_iter(&self) -> Iter<Row> {
+        self.rows.iter()
+    }
+
+    /// Returns an iterator over mutable rows
+    pub fn row_iter_mut(&mut self) -> IterMut<Row> {
+        self.rows.iter_mut()
+    }
+
+    /// Get a view of `n` rows randomly selected in this table.
+    /// Selected rows keep their relative order, and the selection is stable for a given `seed`.
+    /// If `n` is greater than the number of rows, all rows are selected
+    pub fn sample(&self, n: usize, seed: u64) -> TableView {
+        self.as_slice().sample(n, seed)
+    }
+
+    /// Get a view of the rows of this table for which `f` returns `true`.
+    /// See `TableSlice::filter_view` for details
+    pub fn filter_view<F: FnMut(&Row) -> bool>(&self, f: F) -> TableView {
+        self.as_slice().filter_view(f)
+    }
+
+    /// Get a view printing only the columns of this table in the `columns` range.
+    /// See `TableSlice::slice_columns` for details
+    pub fn slice_columns<E>(&self, columns: E) -> ColumnSlice
+        where [usize]: Index<E, Output = [usize]>
+    {
+        self.as_slice().slice_columns(columns)
+    }
+
+    /// Get a view printing only the columns of this table for which `f` returns `true`.
+    /// See `TableSlice::filter_columns` for details
+    pub fn filter_columns<F: FnMut(&str, usize) -> bool>(&self, f: F) -> ColumnSlice {
+        self.as_slice().filter_columns(f)
+    }
+
+    /// Split this table into consecutive slices of `size` rows.
+    /// See `TableSlice::chunks` for details
+    pub fn chunks(&self, size: usize) -> Chunks {
+        self.as_slice().chunks(size)
+    }
+
+    /// Generate a new table with statistics about each numeric column of this table.
+    /// See `TableSlice::describe` for details
+    pub fn describe(&self) -> Table {
+        self.as_slice().describe()
+    }
+
+    /// Generate a report table with subtotal rows for each group of rows, and a grand total row.
+    /// See `TableSlice::subtotals` for details
+    pub fn subtotals(&self, group_column: usize, subtotal_spec: &str, total_spec: &str) -> Table {
+        self.as_slice().subtotals(group_column, subtotal_spec, total_spec)
+    }
+
+    /// Print the table to `out`
+    pub fn print<T: Write + ?Sized>(&self, out: &mut T) -> Result<(), Error> {
+        self.as_slice().print(out)
+    }
+
+    /// Print the table to terminal `out`, applying styles when needed
+    pub fn print_term<T: Terminal + ?Sized>(&self, out: &mut T) -> Result<(), Error> {
+        self.as_slice().print_term(out)
+    }
+
+    /// Print the table to `out` according to `options`. See `TableSlice::print_with` for details
+    pub fn print_with<T: Write + ?Sized>(&self,
+                                         out: &mut T,
+                                         options: &PrintOptions)
+                                         -> Result<(), Error> {
+        self.as_slice().print_with(out, options)
+    }
+
+    /// Measure the table printed according to `options`, without printing it.
+    /// See `TableSlice::measure` for details
+    pub fn measure(&self, options: &PrintOptions) -> (usize, usize) {
+        self.as_slice().measure(options)
+    }
+
+    /// Get the width of the content of each column, as printed.
+    /// See `TableSlice::rendered_column_widths` for details
+    pub fn rendered_column_widths(&self) -> Vec<usize> {
+        self.as_slice().rendered_column_widths()
+    }
+
+    /// Get the width of the widest printed line of the table.
+    /// See `TableSlice::rendered_width` for details
+    pub fn rendered_width(&self) -> usize {
+        self.as_slice().rendered_width()
+    }
+
+    /// Get the number of printed lines of the table.
+    /// See `TableSlice::rendered_height` for details
+    pub fn rendered_height(&self) -> usize {
+        self.as_slice().rendered_height()
+    }
+
+    /// Print the table to standard output according to `options`.
+    /// See `TableSlice::printstd_with` for details
+    /// # Panic
+    /// Panic if writing to standard output fails
+    pub fn printstd_with(&self, options: &PrintOptions) {
+        self.as_slice().printstd_with(options);
+    }
+
+    /// Print the table to `out` in pages of `page_rows` data rows.
+    /// See `TableSlice::print_paged` for details
+    pub fn print_paged<T: Write + ?Sized>(&self,
+                                          out: &mut T,
+                                          page_rows: usize)
+                                          -> Result<(), Error> {
+        self.as_slice().print_paged(out, page_rows)
+    }
+
+    /// Print the table to standard output in pages. See `TableSlice::printstd_paged` for details
+    /// # Panic
+    /// Panic if writing to standard output fails
+    pub fn printstd_paged(&self, page_rows: Option<usize>) {
+        self.as_slice().printstd_paged(page_rows);
+    }
+
+    /// Split the table into text pages of exactly `page_height` lines, each starting with
+    /// the header returned by `header_fn`. See `TableSlice::to_paged_text` for details
+    pub fn to_paged_text<F>(&self, page_height: usize, header_fn: F) -> Vec<String>
+        where F: Fn(usize, usize) -> String
+    {
+        self.as_slice().to_paged_text(page_height, header_fn)
+    }
+
+    /// Print the table to `out` in expanded mode, one block of `field | value` lines per row.
+    /// See `TableSlice::print_expanded` for details
+    pub fn print_expanded<T: Write + ?Sized>(&self, out: &mut T) -> Result<(), Error> {
+        self.as_slice().print_expanded(out)
+    }
+
+    /// Print the table to standard output in expanded mode. See `TableSlice::print_expanded`
+    /// for details
+    /// # Panic
+    /// Panic if writing to standard output fails
+    pub fn printstd_expanded(&self) {
+        self.as_slice().printstd_expanded();
+    }
+
+    /// Render the table without styles, and return it line by line, without line terminators
+    pub fn render_lines(&self) -> Vec<String> {
+        self.as_slice().render_lines()
+    }
+
+    /// Render the table as plain text where columns are only aligned with spaces.
+    /// See `TableSlice::to_aligned_text` for details
+    pub fn to_aligned_text(&self) -> String {
+        self.as_slice().to_aligned_text()
+    }
+
+    /// Render the table line by line, as fragments of text associated with their style attributes.
+    /// See `TableSlice::render_spans` for details
+    pub fn render_spans(&self) -> Vec<Vec<StyledSpan>> {
+        self.as_slice().render_spans()
+    }
+
+    /// Build a copy of the table showing escaped cell content and widths.
+    /// See `TableSlice::debug_view` for details
+    pub fn debug_view(&self) -> Table {
+        self.as_slice().debug_view()
+    }
+
+    /// Print the table to standard output with escaped cell content and widths,
+    /// to debug unexpected column widths caused by invisible characters
+    pub fn print_debug(&self) {
+        self.as_slice().print_debug();
+    }
+
+    /// Split the table into several tables no wider than `max_width`, each one repeating
+    /// the `key_columns`. See `TableSlice::split_vertical` for details
+    pub fn split_vertical(&self, max_width: usize, key_columns: &[usize]) -> Vec<Table> {
+        self.as_slice().split_vertical(max_width, key_columns)
+    }
+
+    /// Build a new table with rows and columns swapped. See `TableSlice::transpose` for details
+    pub fn transpose(&self, titles_as_column: bool) -> Table {
+        self.as_slice().transpose(titles_as_column)
+    }
+
+    /// Build a copy of the table where computed columns, column styles and the cell formatter
+    /// are replaced by their current result. See `TableSlice::materialize` for details
+    pub fn materialize(&self) -> Table {
+        self.as_slice().materialize()
+    }
+
+    /// Print the table to standard output. Colors won't be displayed unless
+    /// stdout is a tty terminal, or `force_colorize` is set to `true`.
+    /// In ANSI terminals, colors are displayed using ANSI escape characters. When for example the
+    /// output is redirected to a file, or piped to another program, the output is considered
+    /// as not beeing tty, and ANSI escape characters won't be displayed unless `force colorize`
+    /// is set to `true`. Unless forced, colors are also disabled by the `NO_COLOR` and
+    /// `CLICOLOR=0` environment variables, and `CLICOLOR_FORCE` enables them even when stdout
+    /// is not a tty terminal.
+    ///
+    /// Printing is subject to the `PrintLimits` configured in the table format.
+    /// # Panic
+    /// Panic if writing to standard output fails. See `try_print_tty` to handle errors instead
+    pub fn print_tty(&self, force_colorize: bool) {
+        self.as_slice().print_tty(force_colorize);
+    }
+
+    /// Print the table to standard output like `print_tty`, and return the number of
+    /// printed lines, or the error which stopped printing.
+    /// See `TableSlice::try_print_tty` for details
+    pub fn try_print_tty(&self, force_colorize: bool) -> Result<usize, Error> {
+        self.as_slice().try_print_tty(force_colorize)
+    }
+
+    /// Print the table to standard output. Colors won't be displayed unless
+    /// stdout is a tty terminal. This means that if stdout is redirected to a file, or piped
+    /// to another program, no color will be displayed.
+    /// To force colors rendering, use `print_tty()` method.
+    /// Calling `printstd()` is equivalent to calling `print_tty(false)`
+    /// # Panic
+    /// Panic if writing to standard output fails. See `try_printstd` to handle errors instead
+    pub fn printstd(&self) {
+        self.as_slice().printstd();
+    }
+
+    /// Print the table to standard output like `printstd`, and return the number of printed
+    /// lines, or the error which stopped printing. See `TableSlice::try_print_tty` for details
+    pub fn try_printstd(&self) -> Result<usize, Error> {
+        self.as_slice().try_printstd()
+    }
+
+    /// Write the table to the specified writer.
+    #[cfg(feature = "csv")]
+    pub fn to_csv<W: Write>(&self, w: W) -> csv::Result<csv::Writer<W>> {
+        self.as_slice().to_csv(w)
+    }
+
+    /// Write the table to the specified writer.
+    ///
+    /// This allows for format customisation.
+    #[cfg(feature = "csv")]
+    pub fn to_csv_writer<W: Write>(&self, writer: csv::Writer<W>) -> csv::Result<csv::Writer<W>> {
+        self.as_slice().to_csv_writer(writer)
+    }
+
+    /// Write the table to the specified writer, with the CSV format and whether titles
+    /// are written set by `options`
+    #[cfg(feature = "csv")]
+    pub fn to_csv_opts<W: Write>(&self,
+                                 w: W,
+                                 options: &CsvOptions)
+                                 -> csv::Result<csv::Writer<W>> {
+        self.as_slice().to_csv_opts(w, options)
+    }
+}
+
+impl Index<usize> for Table {
+    type Output = Row;
+    fn index(&self, idx: usize) -> &Self::Output {
+        &self.rows[idx]
+    }
+}
+
+impl<'a> Index<usize> for TableSlice<'a> {
+    type Output = Row;
+    fn index(&self, idx: usize) -> &Self::Output {
+        &self.rows[idx]
+    }
+}
+
+impl IndexMut<usize> for Table {
+    fn index_mut(&mut self, idx: usize) -> &mut Self::Output {
+        &mut self.rows[idx]
+    }
+}
+
+impl Index<(usize, usize)> for Table {
+    type Output = Cell;
+    fn index(&self, (row, column): (usize, usize)) -> &Self::Output {
+        &self.rows[row][column]
+    }
+}
+
+impl<'a> Index<(usize, usize)> for TableSlice<'a> {
+    type Output = Cell;
+    fn index(&self, (row, column): (usize, usize)) -> &Self::Output {
+        &self.rows[row][column]
+    }
+}
+
+impl IndexMut<(usize, usize)> for Table {
+    fn index_mut(&mut self, (row, column): (usize, usize)) -> &mut Self::Output {
+        &mut self.rows[row][column]
+    }
+}
+
+impl fmt::Display for Table {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        self.as_slice().fmt(fmt)
+    }
+}
+
+impl<'a> fmt::Display for TableSlice<'a> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        let mut writer = StringWriter::new();
+        if self.print(&mut writer).is_err() {
+            return Err(fmt::Error);
+        }
+        write_lines(fmt, writer.as_string())
+    }
+}
+
+impl<B: ToString, A: IntoIterator<Item = B>> FromIterator<A> for Table {
+    fn from_iter<T>(iterator: T) -> Table
+        where T: IntoIterator<Item = A>
+    {
+        Self::init(iterator.into_iter().map(Row::from).collect())
+    }
+}
+
+impl Extend<Row> for Table {
+    fn extend<T: IntoIterator<Item = Row>>(&mut self, iter: T) {
+        self.add_rows(iter);
+    }
+}
+
+impl<T, A, B> From<T> for Table
+    where B: ToString,
+          A: IntoIterator<Item = B>,
+          T: IntoIterator<Item = A>
+{
+    fn from(it: T) -> Table {
+        Self::from_iter(it)
+    }
+}
+
+impl<'a> IntoIterator for &'a Table {
+    type Item = &'a Row;
+    type IntoIter = Iter<'a, Row>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.rows.iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a mut Table {
+    type Item = &'a mut Row;
+    type IntoIter = IterMut<'a, Row>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.row_iter_mut()
+    }
+}
+
+/// Iterator over the rows selected by a `TableView`
+#[derive(Clone)]
+pub struct ViewIter<'a, 'b>(Iter<'b, usize>, &'a [Row]);
+
+impl<'a, 'b> Iterator for ViewIter<'a, 'b> {
+    type Item = &'a Row;
+    fn next(&mut self) -> Option<&'a Row> {
+        let rows = self.1;
+        self.0.next().map(|&i| &rows[i])
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<'a, 'b> ExactSizeIterator for ViewIter<'a, 'b> {}
+
+/// Iterator over consecutive slices of a table, returned by `chunks`
+#[derive(Clone, Debug)]
+pub struct Chunks<'a> {
+    slice: TableSlice<'a>,
+    size: usize,
+}
+
+impl<'a> Iterator for Chunks<'a> {
+    type Item = TableSlice<'a>;
+    fn next(&mut self) -> Option<TableSlice<'a>> {
+        if self.slice.rows.is_empty() {
+            return None;
+        }
+        let (head, tail) = self.slice.rows.split_at(cmp::min(self.size, self.slice.rows.len()));
+        self.slice.rows = tail;
+        Some(TableSlice { rows: head, ..self.slice.clone() })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = (self.slice.rows.len() + self.size - 1) / self.size;
+        (n, Some(n))
+    }
+}
+
+impl<'a> ExactSizeIterator for Chunks<'a> {}
+
+/// Iterator over immutable cells in a column
+pub struct ColumnIter<'a>(Iter<'a, Row>, usize);
+
+impl<'a> Iterator for ColumnIter<'a> {
+    type Item = &'a Cell;
+    fn next(&mut self) -> Option<&'a Cell> {
+        self.0.next().and_then(|row| row.get_cell(self.1))
+    }
+}
+
+/// Iterator over mutable cells in a column
+pub struct ColumnIterMut<'a>(IterMut<'a, Row>, usize);
+
+impl<'a> Iterator for ColumnIterMut<'a> {
+    type Item = &'a mut Cell;
+    fn next(&mut self) -> Option<&'a mut Cell> {
+        self.0.next().and_then(|row| row.get_mut_cell(self.1))
+    }
+}
+
+impl<'a> AsRef<TableSlice<'a>> for TableSlice<'a> {
+    fn as_ref(&self) -> &TableSlice<'a> {
+        self
+    }
+}
+
+/// Trait implemented by types which can be sliced
+pub trait Slice<'a, E> {
+    /// Type output after slicing
+    type Output: 'a;
+    /// Get a slice from self
+    fn slice(&'a self, arg: E) -> Self::Output;
+}
+
+impl<'a, E> Slice<'a, E> for TableSlice<'a>
+    where [Row]: Index<E, Output = [Row]>
+{
+    type Output = TableSlice<'a>;
+    fn slice(&'a self, arg: E) -> Self::Output {
+        let mut slice = self.clone();
+        slice.rows = self.rows.index(arg);
+        slice
+    }
+}
+
+impl<'a, E> Slice<'a, E> for Table
+    where [Row]: Index<E, Output = [Row]>
+{
+    type Output = TableSlice<'a>;
+    fn slice(&'a self, arg: E) -> Self::Output {
+        let mut slice = self.as_slice();
+        slice.rows = self.rows[..].index(arg);
+        slice
+    }
+}
+
+/// Create a table filled with some values
+///
+/// All the arguments used for elements must implement the `std::string::ToString` trait
+/// # Syntax
+/// ```text
+/// table!([Element1_ row1, Element2_ row1, ...], [Element1_row2, ...], ...);
+/// table!([Title1, Title2, ...]; => [Element1_ row1, Element2_ row1, ...], ...);
+/// ```
+///
+/// # Example
+/// ```
+/// # #[macro_use] extern crate prettytable;
+/// # fn main() {
+/// // Create a table initialized with some rows :
+/// let tab = table!(["Element1", "Element2", "Element3"],
+/// 				 [1, 2, 3],
+/// 				 ["A", "B", "C"]
+/// 				 );
+/// # drop(tab);
+/// # }
+/// ```
+///
+/// Some style can also be given in table creation
+///
+/// ```
+/// # #[macro_use] extern crate prettytable;
+/// # fn main() {
+/// let tab = table!([FrByl->"Element1", Fgc->"Element2", "Element3"],
+/// 				 [FrBy => 1, 2, 3],
+/// 				 ["A", "B", "C"]
+/// 				 );
+/// # drop(tab);
+/// # }
+/// ```
+///
+/// Titles can be given first, followed by `; =>`
+///
+/// ```
+/// # #[macro_use] extern crate prettytable;
+/// # fn main() {
+/// let tab = table!([b->"Name", b->"Value"]; =>
+/// 				 ["A", 1],
+/// 				 ["B", 2]
+/// 				 );
+/// assert_eq!(tab.len(), 2);
+/// assert!(tab.to_string().starts_with("+------+-------+\n| Name | Value |"));
+/// # }
+/// ```
+///
+/// For details about style specifier syntax, check doc for [`Cell::style_spec`](cell/struct.Cell.html#method.style_spec) method
+#[macro_export]
+macro_rules! table {
+    ([$($titles:tt)*]; => $([$($content:tt)*]), *) => (
+        {
+            let mut table = $crate::Table::init(vec![$(row![$($content)*]), *]);
+            table.set_titles(row![$($titles)*]);
+            table
+        }
+    );
+    ($([$($content:tt)*]), *) => (
+        $crate::Table::init(vec![$(row![$($content)*]), *])
+    );
+}
+
+/// Create a table with `table!` macro, print it to standard output, then return this table for future usage.
+///
+/// The syntax is the same that the one for the `table!` macro
+#[macro_export]
+macro_rules! ptable {
+    ($($content:tt)*) => (
+        {
+            let tab = table!($($content)*);
+            tab.printstd();
+            tab
+        }
+    );
+}
+
+/// Append a row to an existing table, using the same syntax as the `row!` macro.
+/// Return a mutable reference to the added row, like `Table::add_row`
+///
+/// # Example
+/// ```
+/// # #[macro_use] extern crate prettytable;
+/// # fn main() {
+/// let mut table = table!(["Level", "Message"]);
+/// add_row!(table, Fr->"err", "disk full");
+/// add_row!(table, Fy => "warn", "low memory");
+/// assert_eq!(table.len(), 3);
+/// assert_eq!(table[1][0].get_content(), "err");
+/// # }
+/// ```
+#[macro_export]
+macro_rules! add_row {
+    ($table:expr, $($content:tt)*) => (
+        $table.add_row(row![$($content)*])
+    );
+}
+
+/// Append several rows to an existing table, each one using the same syntax
+/// as the `row!` macro, like in the `table!` macro
+///
+/// # Example
+/// ```
+/// # #[macro_use] extern crate prettytable;
+/// # fn main() {
+/// let mut table = table!(["Level", "Message"]);
+/// add_rows!(table, [Fr->"err", "disk full"], [Fy => "warn", "low memory"]);
+/// assert_eq!(table.len(), 3);
+/// # }
+/// ```
+#[macro_export]
+macro_rules! add_rows {
+    ($table:expr, $([$($content:tt)*]), *) => (
+        $table.add_rows(vec![$(row![$($content)*]), *])
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use Table;
+    use Slice;
+    use row::Row;
+    use cell::{Cell, Rgb};
+    use format;
+    use format::{PrintLimits, PrintOptions, ColorPolicy, FormatBuilder, Width, Alignment,
+                 VerticalAlignment, CaptionPosition};
+    use utils::StringWriter;
+    use span::{StyledSpan, SpanRecorder};
+    use term::{Attr, color};
+    use format::consts::{FORMAT_DEFAULT, FORMAT_NO_LINESEP, FORMAT_NO_COLSEP, FORMAT_CLEAN};
+
+    #[test]
+    fn table() {
+        let mut table = Table::new();
+        table.add_row(Row::new(vec![Cell::new("a"), Cell::new("bc"), Cell::new("def")]));
+        table.add_row(Row::new(vec![Cell::new("def"), Cell::new("bc"), Cell::new("a")]));
+        table.set_titles(Row::new(vec![Cell::new("t1"), Cell::new("t2"), Cell::new("t3")]));
+        let out = "\
++-----+----+-----+
+| t1  | t2 | t3  |
++=====+====+=====+
+| a   | bc | def |
++-----+----+-----+
+| def | bc | a   |
++-----+----+-----+
+";
+        assert_eq!(table.to_string().replace("\r\n", "\n"), out);
+        table.unset_titles();
+        let out = "\
++-----+----+-----+
+| a   | bc | def |
++-----+----+-----+
+| def | bc | a   |
++-----+----+-----+
+";
+        assert_eq!(table.to_string().replace("\r\n", "\n"), out);
+    }
+
+    #[test]
+    fn index() {
+        let mut table = Table::new();
+        table.add_row(Row::new(vec![Cell::new("a"), Cell::new("bc"), Cell::new("def")]));
+        table.add_row(Row::new(vec![Cell::new("def"), Cell::new("bc"), Cell::new("a")]));
+        table.set_titles(Row::new(vec![Cell::new("t1"), Cell::new("t2"), Cell::new("t3")]));
+        assert_eq!(table[1][1].get_content(), "bc");
+
+        table[1][1] = Cell::new("newval");
+        assert_eq!(table[1][1].get_content(), "newval");
+
+        let out = "\
++-----+--------+-----+
+| t1  | t2     | t3  |
++=====+========+=====+
+| a   | bc     | def |
++-----+--------+-----+
+| def | newval | a   |
++-----+--------+-----+
+";
+        assert_eq!(table.to_string().replace("\r\n", "\n"), out);
+    }
+
+    #[test]
+    fn table_size() {
+        let mut table = Table::new();
+        assert!(table.is_empty());
+        assert!(table.as_slice().is_empty());
+        assert_eq!(table.len(), 0);
+        assert_eq!(table.as_slice().len(), 0);
+        assert_eq!(table.get_column_num(), 0);
+        assert_eq!(table.as_slice().get_column_num(), 0);
+        table.add_empty_row();
+        assert!(!table.is_empty());
+        assert!(!table.as_slice().is_empty());
+        assert_eq!(table.len(), 1);
+        assert_eq!(table.as_slice().len(), 1);
+        assert_eq!(table.get_column_num(), 0);
+        assert_eq!(table.as_slice().get_column_num(), 0);
+        table[0].add_cell(Cell::default());
+        assert_eq!(table.get_column_num(), 1);
+        assert_eq!(table.as_slice().get_column_num(), 1);
+    }
+
+    #[test]
+    fn get_row() {
+        let mut table = Table::new();
+        table.add_row(Row::new(vec![Cell::new("a"), Cell::new("bc"), Cell::new("def")]));
+        table.add_row(Row::new(vec![Cell::new("def"), Cell::new("bc"), Cell::new("a")]));
+        assert!(table.get_row(12).is_none());
+        assert!(table.get_row(1).is_some());
+        assert_eq!(table.get_row(1).unwrap()[0].get_content(), "def");
+        assert!(table.get_mut_row(12).is_none());
+        assert!(table.get_mut_row(1).is_some());
+        table.get_mut_row(1).unwrap().add_cell(Cell::new("z"));
+        assert_eq!(table.get_row(1).unwrap()[3].get_content(), "z");
+    }
+
+    #[test]
     fn add_empty_row() {
         let mut table = Table::new();
         assert_eq!(table.len(), 0);
@@ -719,227 +3666,1392 @@ mod tests {
     }
 
     #[test]
-    fn remove_row() {
+    fn remove_row() {
+        let mut table = Table::new();
+        table.add_row(Row::new(vec![Cell::new("a"), Cell::new("bc"), Cell::new("def")]));
+        table.add_row(Row::new(vec![Cell::new("def"), Cell::new("bc"), Cell::new("a")]));
+        table.remove_row(12);
+        assert_eq!(table.len(), 2);
+        table.remove_row(0);
+        assert_eq!(table.len(), 1);
+        assert_eq!(table[0][0].get_content(), "def");
+    }
+
+    #[test]
+    fn interleave() {
+        let left = Table::init(vec![Row::from(vec!["a"]), Row::from(vec!["b"])]);
+        let mut right = Table::init(vec![Row::from(vec!["1"])]);
+        right.set_titles(Row::from(vec!["t"]));
+        let table = left.interleave(&right, Some(Attr::Bold));
+        let contents: Vec<String> = table.column_iter(0).map(|c| c.get_content()).collect();
+        assert_eq!(contents, vec!["a", "1", "b"]);
+        assert_eq!(table[1][0].get_style(), &[Attr::Bold]);
+        assert!(table[2][0].get_style().is_empty());
+        assert!(table.titles.is_some());
+        let table = right.interleave(&left, None);
+        let contents: Vec<String> = table.column_iter(0).map(|c| c.get_content()).collect();
+        assert_eq!(contents, vec!["1", "a", "b"]);
+    }
+
+    #[test]
+    fn center_bias() {
+        let mut table = Table::new();
+        table.set_titles(Row::new(vec![Cell::new_align("abc", Alignment::CENTER)]));
+        table.add_row(Row::new(vec![Cell::new_align("a\nabcd", Alignment::CENTER)]));
+        table.add_row(Row::new(vec![Cell::new_align("abc", Alignment::CENTER)
+                                        .with_style(Attr::Bold)]));
+        table.set_format(FormatBuilder::new().borders('|').build());
+        assert_eq!(table.to_string().replace("\r\n", "\n"),
+                   "|abc |\n| a  |\n|abcd|\n|abc |\n");
+        table.get_format().center_bias(format::CenterBias::Right);
+        assert_eq!(table.to_string().replace("\r\n", "\n"),
+                   "| abc|\n|  a |\n|abcd|\n| abc|\n");
+        let spans = table.render_spans();
+        assert_eq!(spans[3][1], StyledSpan::new(" abc", vec![Attr::Bold]));
+    }
+
+    #[test]
+    fn map_cells() {
+        let mut table = Table::new();
+        table.set_titles(Row::new(vec![Cell::new("a"), Cell::new("b")]));
+        table.add_row(Row::new(vec![Cell::new("1.26"), Cell::new("x")]));
+        table.add_row(Row::new(vec![Cell::new("2.5"), Cell::new("y")]));
+        table.map_titles(|_, cell| Cell::new(&cell.get_content().to_uppercase()));
+        table.map_cells(|r, c, cell| match cell.get_content().parse::<f64>() {
+            Ok(v) => Cell::new(&format!("{:.1}", v)),
+            Err(_) => Cell::new(&format!("{}{}{}", cell.get_content(), r, c)),
+        });
+        let contents = |row: &Row| row.iter().map(|c| c.get_content()).collect::<Vec<_>>();
+        assert_eq!(contents(table.titles.as_ref().as_ref().unwrap()), vec!["A", "B"]);
+        assert_eq!(contents(&table[0]), vec!["1.3", "x01"]);
+        assert_eq!(contents(&table[1]), vec!["2.5", "y11"]);
+    }
+
+    #[test]
+    fn slice_columns() {
+        let mut table = Table::new();
+        table.set_titles(Row::from(vec!["a", "b", "c"]));
+        table.add_row(Row::from(vec!["1", "2", "3"]));
+        table.add_banner("banner", "");
+        table.add_column_group("bc", 1..3);
+        table.set_column_width(2, Width::Min(3));
+        table.add_computed_column("d", |row| format!("{}!", row[0].get_content()));
+        let view = table.slice_columns(2..4);
+        assert_eq!(view.get_columns(), &[2, 3]);
+        let out = "\
++-----+----+
+| bc  |    |
++-----+----+
+| c   | d  |
++=====+====+
+| 3   | 1! |
++-----+----+
+| banner   |
++-----+----+
+";
+        assert_eq!(view.to_string().replace("\r\n", "\n"), out);
+        assert_eq!(table.slice_columns(..).get_column_num(), 4);
+        assert_eq!(table.slice(..0).slice_columns(..).get_column_num(), 1);
+    }
+
+    #[test]
+    fn filter_columns() {
+        let mut table = Table::new();
+        table.set_titles(Row::from(vec!["a", "b", "c"]));
+        table.add_row(Row::from(vec!["1", "2", "3"]));
+        table.add_column_group("abc", 0..3);
+        table.add_computed_column("d", |row| format!("{}!", row[0].get_content()));
+        let view = table.filter_columns(|title, _| title != "b");
+        assert_eq!(view.get_columns(), &[0, 2, 3]);
+        let view = view.filter_columns(|_, i| i < 3);
+        let out = "\
++---+---+
+|  abc  |
++---+---+
+| a | c |
++===+===+
+| 1 | 3 |
++---+---+
+";
+        assert_eq!(view.to_string().replace("\r\n", "\n"), out);
+        assert_eq!(table.slice_columns(1..).filter_columns(|_, i| i != 2).get_columns(), &[1, 3]);
+        let mut table = Table::new();
+        table.add_row(Row::from(vec!["1", "2"]));
+        assert_eq!(table.filter_columns(|title, _| title.is_empty()).get_column_num(), 2);
+    }
+
+    #[test]
+    fn aligned_text() {
+        let mut table = Table::new();
+        table.set_titles(Row::from(vec!["n", "name"]));
+        table.add_row(Row::new(vec![Cell::new_align("10", Alignment::RIGHT), Cell::new("é")]));
+        table.add_row(Row::new(vec![Cell::new_align("2", Alignment::RIGHT), Cell::new("")]));
+        let mut format = *FORMAT_DEFAULT;
+        format.indent(4);
+        table.set_format(format);
+        assert_eq!(table.to_aligned_text().replace("\r\n", "\n"), "n  name\n10 é\n 2\n");
+    }
+
+    #[test]
+    fn index_cell() {
+        let mut table = Table::new();
+        table.add_row(Row::from(vec!["a", "b"]));
+        table.add_row(Row::from(vec!["c"]));
+        assert_eq!(table[(0, 1)].get_content(), "b");
+        assert_eq!(table.slice(1..)[(0, 0)].get_content(), "c");
+        assert_eq!(table[1][0].get_content(), "c");
+        table[(1, 0)] = Cell::new("d");
+        table.get_mut((0, 0)).unwrap().align(Alignment::RIGHT);
+        assert_eq!(table.get((1, 0)).map(|c| c.get_content()), Some("d".to_string()));
+        assert_eq!(table.get((0, 0)).map(|c| c.get_alignment()), Some(Alignment::RIGHT));
+        assert!(table.get((1, 1)).is_none() && table.get_mut((2, 0)).is_none());
+        assert!(table.slice(..).get((0, 2)).is_none());
+    }
+
+    #[test]
+    fn split_vertical() {
+        let mut table = Table::new();
+        table.set_titles(Row::from(vec!["k", "a", "b", "c"]));
+        table.add_row(Row::from(vec!["1", "aaaa", "bbbb", "cc"]));
+        table.add_banner("banner", "");
+        table.set_column_width(3, Width::Fixed(3));
+        // "| k | aaaa | bbbb |" is 20 characters wide
+        let tables = table.split_vertical(20, &[0]);
+        assert_eq!(tables.len(), 2);
+        assert_eq!(tables[0].render_lines()[1], "| k | a    | b    |");
+        assert_eq!(tables[1].render_lines()[3], "| 1 | cc  |");
+        assert_eq!(tables[1].render_lines()[5], "| banner  |");
+        assert_eq!(table.split_vertical(5, &[0]).len(), 3);
+        assert_eq!(table.split_vertical(100, &[]).len(), 1);
+        assert_eq!(table.split_vertical(5, &[0, 1, 2, 3]).len(), 1);
+    }
+
+    #[test]
+    fn chunks() {
+        let mut table = Table::new();
+        table.set_titles(Row::new(vec![Cell::new("t")]));
+        for i in 0..5 {
+            table.add_row(Row::new(vec![Cell::new(&i.to_string())]));
+        }
+        let chunks = table.chunks(2);
+        assert_eq!(chunks.len(), 3);
+        let lens: Vec<usize> = chunks.map(|c| c.len()).collect();
+        assert_eq!(lens, vec![2, 2, 1]);
+        let last = table.chunks(2).last().unwrap();
+        assert_eq!(last.to_string().replace("\r\n", "\n"),
+                   "+---+\n| t |\n+===+\n| 4 |\n+---+\n");
+        assert_eq!(Table::new().chunks(3).count(), 0);
+    }
+
+    #[test]
+    fn dedup() {
+        let contents = |t: &Table| t.column_iter(1).map(|c| c.get_content()).collect::<Vec<_>>();
+        let mut table = Table::from(vec![vec!["a", "1"], vec!["a", "1"], vec!["a", "2"],
+                                         vec!["b", "3"], vec!["a", "1"]]);
+        table[1][0].style(Attr::Bold);
+        let mut copy = table.clone();
+        copy.dedup();
+        assert_eq!(contents(&copy), vec!["1", "2", "3", "1"]);
+        let mut copy = table.clone();
+        copy.dedup_all();
+        assert_eq!(contents(&copy), vec!["1", "2", "3"]);
+        let mut copy = table.clone();
+        copy.dedup_by_key(0);
+        assert_eq!(contents(&copy), vec!["1", "3", "1"]);
+        table.dedup_all_by_key(0);
+        assert_eq!(contents(&table), vec!["1", "3"]);
+        let mut table = Table::from(vec![vec!["a"], vec![], vec![]]);
+        table.dedup_by_key(0);
+        assert_eq!(table.len(), 2);
+    }
+
+    #[test]
+    fn retain() {
+        let mut table = Table::init((0..6).map(|i| Row::from(vec![i])).collect());
+        table.retain(|row| row[0].get_content() != "0");
+        let even = |row: &Row| row[0].get_content().parse::<u32>().unwrap() % 2 == 0;
+        let removed = table.remove_rows_where(even);
+        assert_eq!(removed.iter().map(|r| r[0].get_content()).collect::<Vec<_>>(), vec!["2", "4"]);
+        assert_eq!(table.column_iter(0).map(|c| c.get_content()).collect::<Vec<_>>(),
+                   vec!["1", "3", "5"]);
+    }
+
+    #[test]
+    fn clear_truncate() {
+        let mut table = Table::init((0..4).map(|i| Row::from(vec![i])).collect());
+        table.set_titles(Row::from(vec!["n"]));
+        assert_eq!(table.pop_row().unwrap()[0].get_content(), "3");
+        table.truncate(5);
+        assert_eq!(table.len(), 3);
+        table.truncate(1);
+        assert_eq!(table.len(), 1);
+        table.clear();
+        assert!(table.is_empty() && table.titles.is_some());
+        assert!(table.pop_row().is_none());
+    }
+
+    #[test]
+    fn row_ids() {
+        let mut table = Table::new();
+        for id in &["c", "a", "b"] {
+            table.add_row_with_id(id, Row::from(vec![*id, "0"]));
+        }
+        assert_eq!(table.get_row_by_id("a").unwrap()[0].get_content(), "a");
+        table.sort_by_column(0, ::sort::Collation::new());
+        assert_eq!(table.row_index("c"), Some(2));
+        table.insert_row(0, Row::from(vec!["z"]));
+        table.remove_row(1);
+        assert_eq!(table.update_row("b", Row::from(vec!["b", "1"])).unwrap()[1].get_content(),
+                   "0");
+        assert_eq!(table[1][1].get_content(), "1");
+        assert!(table.update_row("x", Row::from(vec!["x"])).is_none());
+        table.add_row_with_id("c", Row::from(vec!["c", "2"]));
+        assert_eq!(table.len(), 3);
+        assert_eq!(table[2][1].get_content(), "2");
+        assert_eq!(table.remove_row_by_id("b").unwrap().get_id(), Some("b"));
+        assert_eq!(table.row_index("b"), None);
+        assert_eq!(table.row_index("c"), Some(1));
+        let mut row = Row::from(vec!["d"]);
+        row.set_id("d");
+        table.insert_row(0, row);
+        assert_eq!(table.row_index("d"), Some(0));
+        assert_eq!(table.get_row_by_id("c").unwrap()[1].get_content(), "2");
+    }
+
+    #[test]
+    fn row_ids_not_in_index() {
+        let mut a = Table::new();
+        a.add_row_with_id("x", Row::from(vec!["x"]));
+        let mut b = Table::new();
+        b.add_row_with_id("y", Row::from(vec!["y"]));
+        let mut table = a.interleave(&b, None);
+        assert_eq!(table.row_index("x"), Some(0));
+        assert_eq!(table.row_index("y"), Some(1));
+        assert_eq!(table.update_row("y", Row::from(vec!["y", "1"])).unwrap().len(), 1);
+
+        table[0].set_id("z");
+        assert_eq!(table.row_index("z"), Some(0));
+        assert_eq!(table.row_index("x"), None);
+        table.get_mut_row(1).unwrap().set_id("w");
+        assert_eq!(table.remove_row_by_id("w").unwrap().len(), 2);
+        assert_eq!(table.len(), 1);
+    }
+
+    #[test]
+    fn insert_row() {
+        let mut table = Table::new();
+        table.add_row(Row::new(vec![Cell::new("a"), Cell::new("bc"), Cell::new("def")]));
+        table.add_row(Row::new(vec![Cell::new("def"), Cell::new("bc"), Cell::new("a")]));
+        table.insert_row(12, Row::new(vec![Cell::new("1"), Cell::new("2"), Cell::new("3")]));
+        assert_eq!(table.len(), 3);
+        assert_eq!(table[2][1].get_content(), "2");
+        table.insert_row(1, Row::new(vec![Cell::new("3"), Cell::new("4"), Cell::new("5")]));
+        assert_eq!(table.len(), 4);
+        assert_eq!(table[1][1].get_content(), "4");
+        assert_eq!(table[2][1].get_content(), "bc");
+    }
+
+    #[test]
+    fn load_rows() {
+        let owned = vec![vec!["a".to_string(), "b\nc".to_string()], vec![], vec!["".to_string()]];
+        let mut table = Table::new();
+        table.load_rows(&owned);
+        table.load_rows(vec![vec!["d"]]);
+        assert_eq!(table.len(), 4);
+        assert_eq!(table[0][1].get_height(), 2);
+        assert!(table[1].is_empty());
+        assert_eq!(table[2][0].get_height(), 0);
+        assert_eq!(table.to_string(), Table::from(vec![vec!["a", "b\nc"], vec![], vec![""],
+                                                        vec!["d"]]).to_string());
+    }
+
+    #[test]
+    fn add_rows() {
+        let mut table = Table::new();
+        table.add_row(Row::from(vec!["a"]));
+        table.add_rows(vec!["b", "c"].into_iter().map(|s| Row::from(vec![s])));
+        table.extend(vec![Row::from(vec!["d"])]);
+        assert_eq!(table.len(), 4);
+        assert_eq!(table[1][0].get_content(), "b");
+        assert_eq!(table[3][0].get_content(), "d");
+    }
+
+    #[test]
+    fn append() {
+        let mut table = Table::init(vec![Row::from(vec!["a", "1"])]);
+        let mut other = Table::init(vec![Row::from(vec!["b", "2"])]);
+        other.set_titles(Row::from(vec!["name", "value"]));
+        table.try_append(&mut other).unwrap();
+        assert!(other.is_empty());
+        assert_eq!(table.len(), 2);
+        assert_eq!(table.titles.as_ref().unwrap()[0].get_content(), "name");
+        let mut other = Table::init(vec![Row::from(vec!["c"])]);
+        assert!(table.try_append(&mut other).is_err());
+        other.set_titles(Row::from(vec!["name", "other"]));
+        other[0].add_cell(Cell::new("3"));
+        assert_eq!(table.try_append(&mut other), Err("Titles mismatch".to_string()));
+        assert_eq!(other.len(), 1);
+        table.set_row_validator(|row| if row[0].get_content() == "c" {
+                                    Err("c".to_string())
+                                } else {
+                                    Ok(())
+                                });
+        other.unset_titles();
+        assert_eq!(table.try_append(&mut other), Err("c".to_string()));
+        assert_eq!(table.len(), 2);
+        let last = Table::init(vec![Row::from(vec!["d"])]);
+        let table = Table::concat(vec![table, Table::new(), last]);
+        assert_eq!(table.len(), 3);
+        assert_eq!(table[2][0].get_content(), "d");
+        assert!(Table::concat(vec![]).is_empty());
+    }
+
+    #[test]
+    fn color_overrides() {
+        let mut table = Table::new();
+        table.add_row(Row::new(vec![Cell::new("a").style_spec("FrBg"), Cell::new("b")]));
+        table.set_color_overrides(vec![(color::RED, Rgb(1, 2, 3)), (300, Rgb(4, 5, 6))]);
+        assert_eq!(table.get_format().get_color_override(color::RED), Some(Rgb(1, 2, 3)));
+        assert_eq!(table.get_format().get_color_override(300), None);
+        let spans = table.render_spans();
+        let styles: Vec<&[Attr]> = spans[1].iter().map(|s| s.get_style()).collect();
+        assert!(styles.iter().all(|s| !s.contains(&Attr::ForegroundColor(color::RED))));
+        assert!(styles.iter().any(|s| s.contains(&Attr::BackgroundColor(color::GREEN))));
+        assert_eq!(table[0][0].get_style().len(), 2);
+        table.set_color_overrides(vec![]);
+        assert!(!table.get_format().has_color_overrides());
+    }
+
+    #[test]
+    fn measure() {
+        let mut table = Table::new();
+        table.set_titles(Row::from(vec!["name", "value"]));
+        table.add_row(Row::from(vec!["été", "1\n2"]));
+        table.get_format().indent(2);
+        let mut out = Vec::new();
+        table.print(&mut out).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        let width = text.lines().map(|l| l.chars().count()).max().unwrap();
+        assert_eq!(table.measure(&PrintOptions::new()), (width, text.lines().count()));
+        assert_eq!(table.measure(&PrintOptions::new()), (18, 6));
+        assert_eq!(table.measure(&PrintOptions::new().max_width(12)), (14, 6));
+        assert_eq!(table.measure(&PrintOptions::new().hide_titles(true)), (13, 4));
+        assert_eq!(Table::new().measure(&PrintOptions::new()), (2, 2));
+    }
+
+    #[test]
+    fn set_element() {
+        let mut table = Table::new();
+        table.add_row(Row::new(vec![Cell::new("a"), Cell::new("bc"), Cell::new("def")]));
+        table.add_row(Row::new(vec![Cell::new("def"), Cell::new("bc"), Cell::new("a")]));
+        assert!(table.set_element("foo", 12, 12).is_err());
+        assert!(table.set_element("foo", 1, 1).is_ok());
+        assert_eq!(table[1][1].get_content(), "foo");
+    }
+
+    #[test]
+    fn no_linesep() {
+        let mut table = Table::new();
+        table.set_format(*FORMAT_NO_LINESEP);
+        table.add_row(Row::new(vec![Cell::new("a"), Cell::new("bc"), Cell::new("def")]));
+        table.add_row(Row::new(vec![Cell::new("def"), Cell::new("bc"), Cell::new("a")]));
+        table.set_titles(Row::new(vec![Cell::new("t1"), Cell::new("t2"), Cell::new("t3")]));
+        assert_eq!(table[1][1].get_content(), "bc");
+
+        table[1][1] = Cell::new("newval");
+        assert_eq!(table[1][1].get_content(), "newval");
+
+        let out = "\
++-----+--------+-----+
+| t1  | t2     | t3  |
+| a   | bc     | def |
+| def | newval | a   |
++-----+--------+-----+
+";
+        assert_eq!(table.to_string().replace("\r\n", "\n"), out);
+    }
+
+    #[test]
+    fn no_colsep() {
+        let mut table = Table::new();
+        table.set_format(*FORMAT_NO_COLSEP);
+        table.add_row(Row::new(vec![Cell::new("a"), Cell::new("bc"), Cell::new("def")]));
+        table.add_row(Row::new(vec![Cell::new("def"), Cell::new("bc"), Cell::new("a")]));
+        table.set_titles(Row::new(vec![Cell::new("t1"), Cell::new("t2"), Cell::new("t3")]));
+        assert_eq!(table[1][1].get_content(), "bc");
+
+        table[1][1] = Cell::new("newval");
+        assert_eq!(table[1][1].get_content(), "newval");
+
+        let out = "\
+------------------
+ t1   t2      t3 \n\
+==================
+ a    bc      def \n\
+------------------
+ def  newval  a \n\
+------------------
+";
+        println!("{}", out);
+        println!("____");
+        println!("{}", table.to_string().replace("\r\n", "\n"));
+        assert_eq!(table.to_string().replace("\r\n", "\n"), out);
+    }
+
+    #[test]
+    fn clean() {
+        let mut table = Table::new();
+        table.set_format(*FORMAT_CLEAN);
+        table.add_row(Row::new(vec![Cell::new("a"), Cell::new("bc"), Cell::new("def")]));
+        table.add_row(Row::new(vec![Cell::new("def"), Cell::new("bc"), Cell::new("a")]));
+        table.set_titles(Row::new(vec![Cell::new("t1"), Cell::new("t2"), Cell::new("t3")]));
+        assert_eq!(table[1][1].get_content(), "bc");
+
+        table[1][1] = Cell::new("newval");
+        assert_eq!(table[1][1].get_content(), "newval");
+
+        let out = "\
+\u{0020}t1   t2      t3 \n\
+\u{0020}a    bc      def \n\
+\u{0020}def  newval  a \n\
+";
+        println!("{}", out);
+        println!("____");
+        println!("{}", table.to_string().replace("\r\n", "\n"));
+        assert_eq!(out, table.to_string().replace("\r\n", "\n"));
+    }
+
+    #[test]
+    fn min_width() {
+        let mut table = Table::new();
+        table.add_row(Row::from(vec!["a", "bb"]));
+        let mut format = *FORMAT_DEFAULT;
+        format.min_width(15);
+        format.remainder_policy(format::RemainderPolicy::Rightmost);
+        table.set_format(format);
+        let out = "\
++-----+-------+
+| a   | bb    |
++-----+-------+
+";
+        assert_eq!(table.to_string().replace("\r\n", "\n"), out);
+        format.remainder_policy(format::RemainderPolicy::Leftmost);
+        table.set_format(format);
+        assert!(table.to_string().starts_with("+------+------+"));
+        format.min_width(5);
+        table.set_format(format);
+        assert!(table.to_string().starts_with("+---+----+"));
+    }
+
+    #[test]
+    fn rendered_size() {
+        let mut table = Table::new();
+        table.add_row(Row::from(vec!["a", "bb"]));
+        let mut format = *FORMAT_DEFAULT;
+        format.min_width(15);
+        format.indent(2);
+        table.set_format(format);
+        assert_eq!(table.rendered_column_widths(), vec![4, 4]);
+        assert_eq!(table.rendered_width(), 17);
+        assert_eq!(table.rendered_height(), 3);
+        assert!(table.slice(..0).rendered_column_widths().is_empty());
+    }
+
+    #[test]
+    fn fixed_width() {
+        let mut table = Table::new();
+        table.set_format(*format::consts::FORMAT_FIXED_WIDTH_RULER);
+        table.set_titles(Row::from(vec!["t1", "t2", "t3"]));
+        table.add_row(Row::from(vec!["a", "bcd", "e"]));
+        table.add_row(Row::from(vec!["fghij", "k", "lm"]));
+        let out = "-----+---+--\nt1    t2  t3\na     bcd e\nfghij k   lm\n";
+        assert_eq!(out, table.to_string().replace("\r\n", "\n"));
+    }
+
+    #[test]
+    fn padding() {
+        let mut table = Table::new();
+        let mut format = *FORMAT_DEFAULT;
+        format.padding(2, 2);
+        table.set_format(format);
+        table.add_row(Row::new(vec![Cell::new("a"), Cell::new("bc"), Cell::new("def")]));
+        table.add_row(Row::new(vec![Cell::new("def"), Cell::new("bc"), Cell::new("a")]));
+        table.set_titles(Row::new(vec![Cell::new("t1"), Cell::new("t2"), Cell::new("t3")]));
+        assert_eq!(table[1][1].get_content(), "bc");
+
+        table[1][1] = Cell::new("newval");
+        assert_eq!(table[1][1].get_content(), "newval");
+
+        let out = "\
++-------+----------+-------+
+|  t1   |  t2      |  t3   |
++=======+==========+=======+
+|  a    |  bc      |  def  |
++-------+----------+-------+
+|  def  |  newval  |  a    |
++-------+----------+-------+
+";
+        println!("{}", out);
+        println!("____");
+        println!("{}", table.to_string().replace("\r\n", "\n"));
+        assert_eq!(out, table.to_string().replace("\r\n", "\n"));
+    }
+
+    #[test]
+    fn indent() {
         let mut table = Table::new();
         table.add_row(Row::new(vec![Cell::new("a"), Cell::new("bc"), Cell::new("def")]));
         table.add_row(Row::new(vec![Cell::new("def"), Cell::new("bc"), Cell::new("a")]));
-        table.remove_row(12);
-        assert_eq!(table.len(), 2);
-        table.remove_row(0);
-        assert_eq!(table.len(), 1);
-        assert_eq!(table[0][0].get_content(), "def");
+        table.set_titles(Row::new(vec![Cell::new("t1"), Cell::new("t2"), Cell::new("t3")]));
+        table.get_format().indent(8);
+        let out = "        +-----+----+-----+
+        | t1  | t2 | t3  |
+        +=====+====+=====+
+        | a   | bc | def |
+        +-----+----+-----+
+        | def | bc | a   |
+        +-----+----+-----+
+";
+        assert_eq!(table.to_string().replace("\r\n", "\n"), out);
     }
 
     #[test]
-    fn insert_row() {
+    fn slices() {
         let mut table = Table::new();
-        table.add_row(Row::new(vec![Cell::new("a"), Cell::new("bc"), Cell::new("def")]));
-        table.add_row(Row::new(vec![Cell::new("def"), Cell::new("bc"), Cell::new("a")]));
-        table.insert_row(12, Row::new(vec![Cell::new("1"), Cell::new("2"), Cell::new("3")]));
+        table.set_titles(Row::new(vec![Cell::new("t1"), Cell::new("t2"), Cell::new("t3")]));
+        table.add_row(Row::new(vec![Cell::new("0"), Cell::new("0"), Cell::new("0")]));
+        table.add_row(Row::new(vec![Cell::new("1"), Cell::new("1"), Cell::new("1")]));
+        table.add_row(Row::new(vec![Cell::new("2"), Cell::new("2"), Cell::new("2")]));
+        table.add_row(Row::new(vec![Cell::new("3"), Cell::new("3"), Cell::new("3")]));
+        table.add_row(Row::new(vec![Cell::new("4"), Cell::new("4"), Cell::new("4")]));
+        table.add_row(Row::new(vec![Cell::new("5"), Cell::new("5"), Cell::new("5")]));
+        let out = "\
++----+----+----+
+| t1 | t2 | t3 |
++====+====+====+
+| 1  | 1  | 1  |
++----+----+----+
+| 2  | 2  | 2  |
++----+----+----+
+| 3  | 3  | 3  |
++----+----+----+
+";
+        let slice = table.slice(..);
+        let slice = slice.slice(1..);
+        let slice = slice.slice(..3);
+        assert_eq!(out, slice.to_string().replace("\r\n", "\n"));
+        assert_eq!(out, table.slice(1..4).to_string().replace("\r\n", "\n"));
+    }
+
+    #[test]
+    fn test_unicode_separators() {
+        let mut table = Table::new();
+        table.set_format(FormatBuilder::new()
+                             .column_separator('│')
+                             .borders('│')
+                             .separators(&[format::LinePosition::Top],
+                                         format::LineSeparator::new('─',
+                                                                    '┬',
+                                                                    '┌',
+                                                                    '┐'))
+                             .separators(&[format::LinePosition::Intern],
+                                         format::LineSeparator::new('─',
+                                                                    '┼',
+                                                                    '├',
+                                                                    '┤'))
+                             .separators(&[format::LinePosition::Bottom],
+                                         format::LineSeparator::new('─',
+                                                                    '┴',
+                                                                    '└',
+                                                                    '┘'))
+                             .padding(1, 1)
+                             .build());
+        table.add_row(Row::new(vec![Cell::new("1"), Cell::new("1"), Cell::new("1")]));
+        table.add_row(Row::new(vec![Cell::new("2"), Cell::new("2"), Cell::new("2")]));
+        table.set_titles(Row::new(vec![Cell::new("t1"), Cell::new("t2"), Cell::new("t3")]));
+        let out = "\
+┌────┬────┬────┐
+│ t1 │ t2 │ t3 │
+├────┼────┼────┤
+│ 1  │ 1  │ 1  │
+├────┼────┼────┤
+│ 2  │ 2  │ 2  │
+└────┴────┴────┘
+";
+        println!("{}", out);
+        println!("____");
+        println!("{}", table.to_string().replace("\r\n", "\n"));
+        assert_eq!(out, table.to_string().replace("\r\n", "\n"));
+    }
+
+    #[test]
+    fn print_limits() {
+        let mut table = Table::new();
+        table.set_titles(Row::new(vec![Cell::new("t1"), Cell::new("t2"), Cell::new("t3")]));
+        table.add_row(Row::new(vec![Cell::new("0"), Cell::new("0"), Cell::new("0")]));
+        table.add_row(Row::new(vec![Cell::new("1"), Cell::new("1"), Cell::new("1")]));
+        table.add_row(Row::new(vec![Cell::new("2"), Cell::new("2"), Cell::new("2")]));
+        let limits = PrintLimits::new().rows(2).columns(2);
+        let out = "\
++----+----+
+| t1 | t2 |
++====+====+
+| 0  | 0  |
++----+----+
+| 1  | 1  |
++----+----+
+... 1 more row, 1 more column not shown
+";
+        let mut writer = StringWriter::new();
+        table.as_slice().__print(&mut writer, &limits, Row::print).unwrap();
+        assert_eq!(out, writer.as_string().replace("\r\n", "\n"));
+
+        // 51 bytes are written before the first row, which reaches the limit
+        let limits = PrintLimits::new().bytes(60);
+        let out = "\
++----+----+----+
+| t1 | t2 | t3 |
++====+====+====+
+| 0  | 0  | 0  |
++----+----+----+
+... 2 more rows not shown
+";
+        let mut writer = StringWriter::new();
+        table.as_slice().__print(&mut writer, &limits, Row::print).unwrap();
+        assert_eq!(out, writer.as_string().replace("\r\n", "\n"));
+        // Borders and titles count, so no data row fits in 40 bytes
+        let mut writer = StringWriter::new();
+        table.as_slice().__print(&mut writer, &PrintLimits::new().bytes(40), Row::print).unwrap();
+        assert!(!writer.as_string().contains("| 0 "));
+        assert!(writer.as_string().contains("... 3 more rows not shown"));
+
+        // Limits are not applied to explicit exports
+        table.get_format().limits(PrintLimits::new().rows(1));
+        assert_eq!(table.to_string().lines().count(), 9);
+    }
+
+    #[test]
+    fn filter_view() {
+        let mut table = Table::new();
+        table.set_titles(Row::from(vec!["n"]));
+        table.load_rows((0..10).map(|i| vec![i.to_string()]));
+        let even = table.filter_view(|row| row[0].get_content().parse::<u32>().unwrap() % 2 == 0);
+        assert_eq!(even.get_indices(), &[0, 2, 4, 6, 8]);
+        let view = even.filter_view(|row| row[0].get_content() > "3".to_string());
+        assert_eq!(view.get_indices(), &[4, 6, 8]);
+        assert_eq!(table.slice(5..).filter_view(|_| true).len(), 5);
+        let out = "\
++---+
+| n |
++===+
+| 4 |
++---+
+| 6 |
++---+
+| 8 |
++---+
+";
+        assert_eq!(view.to_string().replace("\r\n", "\n"), out);
+    }
+
+    #[test]
+    fn sample() {
+        let mut table = Table::new();
+        table.set_titles(Row::new(vec![Cell::new("t1")]));
+        for i in 0..100 {
+            table.add_row(Row::new(vec![Cell::new(&i.to_string())]));
+        }
+        let view = table.sample(10, 7);
+        assert_eq!(view.len(), 10);
+        assert_eq!(view.get_indices(), table.sample(10, 7).get_indices());
+        let mut prev = None;
+        for (i, &idx) in view.get_indices().iter().enumerate() {
+            assert!(prev.map_or(true, |p| p < idx));
+            assert_eq!(view[i].get_cell(0).unwrap().get_content(), idx.to_string());
+            prev = Some(idx);
+        }
+        assert!(table.sample(10, 7).get_indices() != table.sample(10, 8).get_indices());
+        assert_eq!(table.sample(1000, 7).len(), 100);
+        assert!(table.sample(0, 7).is_empty());
+
+        let view = table.slice(..2).sample(2, 1);
+        let out = "\
++----+
+| t1 |
++====+
+| 0  |
++----+
+| 1  |
++----+
+";
+        assert_eq!(out, view.to_string().replace("\r\n", "\n"));
+    }
+
+    #[test]
+    fn describe() {
+        let mut table = Table::new();
+        table.set_titles(Row::new(vec![Cell::new("name"), Cell::new("x"), Cell::new("y")]));
+        table.add_row(Row::new(vec![Cell::new("a"), Cell::new("1"), Cell::new("2")]));
+        table.add_row(Row::new(vec![Cell::new("b"), Cell::new("2"), Cell::new("")]));
+        table.add_row(Row::new(vec![Cell::new("c"), Cell::new("3"), Cell::new("4")]));
+        let out = "\
++-------+----------+----------+
+|       | x        | y        |
++=======+==========+==========+
+| count |        3 |        2 |
++-------+----------+----------+
+| mean  | 2.000000 | 3.000000 |
++-------+----------+----------+
+| std   | 1.000000 | 1.414214 |
++-------+----------+----------+
+| min   | 1.000000 | 2.000000 |
++-------+----------+----------+
+| max   | 3.000000 | 4.000000 |
++-------+----------+----------+
+";
+        assert_eq!(out, table.describe().to_string().replace("\r\n", "\n"));
+        table.unset_titles();
+        assert_eq!(table.describe().get_column_num(), 3);
+        assert_eq!(Table::new().describe().get_column_num(), 1);
+        let single = Table::from(vec![vec!["5"]]).describe();
+        assert_eq!(single[2][1].get_content(), "");
+        assert_eq!(single[1][1].get_content(), "5.000000");
+    }
+
+    #[test]
+    fn column_width() {
+        let mut table = Table::new();
+        table.set_titles(Row::new(vec![Cell::new("t1"), Cell::new("t2"), Cell::new("t3")]));
+        table.add_row(Row::new(vec![Cell::new("a"), Cell::new("bc"), Cell::new("defgh")]));
+        assert_eq!(table.get_column_width(0), Width::Auto);
+        table.set_column_width(0, Width::Min(4));
+        table.set_column_width(2, Width::Fixed(3));
+        assert_eq!(table.get_column_width(0), Width::Min(4));
+        assert_eq!(table.get_column_width(1), Width::Auto);
+        assert_eq!(table.get_column_width(12), Width::Auto);
+        let out = "\
++------+----+-----+
+| t1   | t2 | t3  |
++======+====+=====+
+| a    | bc | def |
++------+----+-----+
+";
+        assert_eq!(out, table.to_string().replace("\r\n", "\n"));
+        table.add_row(Row::new(vec![Cell::new("abcdef")]));
+        assert_eq!(table.to_string().lines().next().unwrap(), "+--------+----+-----+");
+        table.set_column_width(0, Width::Auto);
+        assert_eq!(table.slice(..1).to_string().lines().next().unwrap(), "+----+----+-----+");
+    }
+
+    #[test]
+    fn subtotals() {
+        let mut table = Table::new();
+        table.add_row(Row::from(vec!["a", "x", "1"]));
+        table.add_row(Row::from(vec!["a", "y", "2.5"]));
+        table.add_row(Row::from(vec!["b", "z", "3"]));
+        let report = table.subtotals(0, "b", "bFr");
+        let out = "\
++------------+---+-----+
+| a          | x | 1   |
++------------+---+-----+
+| a          | y | 2.5 |
++------------+---+-----+
+| a subtotal |   | 3.5 |
++------------+---+-----+
+| b          | z | 3   |
++------------+---+-----+
+| b subtotal |   | 3.0 |
++------------+---+-----+
+| Total      |   | 6.5 |
++------------+---+-----+
+";
+        assert_eq!(out, report.to_string().replace("\r\n", "\n"));
+        assert_eq!(report[2][0].get_content(), "a subtotal");
+        assert_eq!(table.subtotals(0, "", "").len(), 6);
+        let table = Table::from(vec![vec!["a", "0.1"], vec!["a", "0.2"], vec!["b", "1e1"]]);
+        let report = table.subtotals(0, "", "");
+        assert_eq!(report[2][1].get_content(), "0.3");
+        assert_eq!(report[5][1].get_content(), "10.3");
+    }
+
+    #[test]
+    fn print_with() {
+        let mut table = Table::new();
+        table.add_row(Row::from(vec!["a", "bcdef"]));
+        table.add_row(Row::from(vec!["gh", "i"]));
+        table.add_row(Row::from(vec!["j", "k"]));
+        let options = PrintOptions::new()
+            .format(*FORMAT_CLEAN)
+            .max_width(9)
+            .rows(0..2)
+            .color(ColorPolicy::Never);
+        let mut out = StringWriter::new();
+        table.print_with(&mut out, &options).unwrap();
+        assert_eq!(" a   bcd \n gh  i \n", out.as_string().replace("\r\n", "\n"));
+        let mut out = StringWriter::new();
+        table.print_with(&mut out, &PrintOptions::new().rows(2..10)).unwrap();
+        assert_eq!(out.as_string().lines().count(), 3);
+        // The table itself is left unchanged
+        assert_eq!(table.to_string().lines().count(), 7);
+        table.set_titles(Row::from(vec!["t1", "t2"]));
+        let mut out = StringWriter::new();
+        table.print_with(&mut out, &PrintOptions::new().hide_titles(true)).unwrap();
+        assert!(!out.as_string().contains("t1"));
+        assert!(table.to_string().contains("t1"));
+    }
+
+    #[test]
+    fn print_hide_titles() {
+        let mut table = Table::new();
+        table.set_titles(Row::from(vec!["t1", "t2"]));
+        table.add_row(Row::from(vec!["a", "b"]));
+        table.add_row(Row::from(vec!["c", "d"]));
+        let mut out = StringWriter::new();
+        table.print_with(&mut out, &PrintOptions::new().hide_titles(true)).unwrap();
+        let expected = "+---+---+\n| a | b |\n+---+---+\n| c | d |\n+---+---+\n";
+        assert_eq!(out.as_string().replace("\r\n", "\n"), expected);
+        // Titles and their separator are only hidden for this print
+        assert!(table.titles.is_some());
+        let out = table.to_string().replace("\r\n", "\n");
+        assert!(out.starts_with("+----+----+\n| t1 | t2 |\n+====+====+\n"));
+    }
+
+    #[test]
+    fn print_expanded() {
+        let mut table = Table::new();
+        table.set_titles(Row::from(vec!["name", "id"]));
+        table.add_row(Row::from(vec!["foo", "1"]));
+        table.add_row(Row::new(vec![Cell::new("bar\nbaz")]));
+        let mut out = StringWriter::new();
+        table.print_expanded(&mut out).unwrap();
+        let expected = "\
+-[ RECORD 1 ]-
+name | foo
+id   | 1
+-[ RECORD 2 ]-
+name | bar
+     | baz
+id   | 
+";
+        assert_eq!(expected, out.as_string().replace("\r\n", "\n"));
+    }
+
+    #[test]
+    fn computed_column() {
+        let mut table = Table::new();
+        table.set_titles(Row::from(vec!["a", "b"]));
+        table.add_row(Row::from(vec!["1", "2"]));
+        table.add_row(Row::from(vec!["3"]));
+        table.add_computed_column("sum", |row| {
+            row.iter().filter_map(|c| c.get_content().parse::<i32>().ok()).sum::<i32>().to_string()
+        });
+        let out = "\
++---+---+-----+
+| a | b | sum |
++===+===+=====+
+| 1 | 2 | 3   |
++---+---+-----+
+| 3 |   | 3   |
++---+---+-----+
+";
+        assert_eq!(out, table.to_string().replace("\r\n", "\n"));
+        table[1].add_cell(Cell::new("5"));
+        assert!(table.to_string().contains("| 3 | 5 | 8   |"));
+        assert_eq!(table.get_column_num(), 2);
+        table.clear_computed_columns();
+        assert!(!table.to_string().contains("sum"));
+    }
+
+    #[test]
+    fn to_paged_text() {
+        let mut table = Table::new();
+        table.set_titles(Row::from(vec!["t"]));
+        for i in &[1, 2, 3] {
+            table.add_row(Row::from(vec![i]));
+        }
+        let pages = table.to_paged_text(9, |n, count| format!("Page {}/{}", n, count));
+        assert_eq!(pages.len(), 2);
+        let expected = "Page 1/2\n+---+\n| t |\n+===+\n| 1 |\n+---+\n| 2 |\n+---+\n\n";
+        assert_eq!(expected, pages[0].replace("\r\n", "\n"));
+        let expected = "Page 2/2\n+---+\n| t |\n+===+\n| 3 |\n+---+\n\n\n\n";
+        assert_eq!(expected, pages[1].replace("\r\n", "\n"));
+        assert_eq!(Table::new().to_paged_text(3, |_, _| String::new()).len(), 1);
+    }
+
+    #[test]
+    fn print_paged() {
+        let mut table = Table::new();
+        table.set_titles(Row::from(vec!["t"]));
+        for i in &[1, 22, 3] {
+            table.add_row(Row::from(vec![i]));
+        }
+        let mut out = StringWriter::new();
+        table.print_paged(&mut out, 2).unwrap();
+        let expected = "\
++----+
+| t  |
++====+
+| 1  |
++----+
+| 22 |
++----+
+
++----+
+| t  |
++====+
+| 3  |
++----+
+";
+        assert_eq!(expected, out.as_string().replace("\r\n", "\n"));
+        table.set_line_filter(|l| format!(">{}", l));
+        let mut out = StringWriter::new();
+        table.print_paged(&mut out, 2).unwrap();
+        let filtered: Vec<String> = expected.lines().map(|l| format!(">{}", l)).collect();
+        assert_eq!(filtered.join("\n") + "\n", out.as_string().replace("\r\n", "\n"));
+    }
+
+    #[test]
+    fn render_lines() {
+        let mut table = Table::new();
+        table.add_row(Row::from(vec!["a", "bc"]));
+        assert_eq!(table.render_lines(), vec!["+---+----+", "| a | bc |", "+---+----+"]);
+        assert!(Table::new().render_lines().iter().all(|l| !l.contains('\n')));
+    }
+
+    #[test]
+    fn repeat_titles() {
+        let mut table = Table::new();
+        table.set_format(FormatBuilder::new().column_separator('|').padding(1, 1).build());
+        table.get_format().repeat_titles(2);
+        table.set_titles(Row::from(vec!["t"]));
+        for i in 0..5 {
+            table.add_row(Row::from(vec![i]));
+        }
+        let out = " t \n 0 \n 1 \n t \n 2 \n 3 \n t \n 4 \n";
+        assert_eq!(out, table.to_string().replace("\r\n", "\n"));
+    }
+
+    #[test]
+    fn insert_remove_column() {
+        let mut table = Table::new();
+        table.set_titles(Row::from(vec!["a", "b", "c"]));
+        table.add_row(Row::from(vec!["1", "2", "3"]));
+        table.add_row(Row::from(vec!["4"]));
+        table.add_banner("banner", "");
+        table.set_column_width(2, Width::Fixed(3));
+        table.set_column_style(2, Attr::Bold);
+        table.add_column_group("bc", 1..3);
+        table.insert_column(2, "x", vec![Cell::new("x1"), Cell::new("x2")]);
+        assert_eq!(table.get_column_width(3), Width::Fixed(3));
+        assert_eq!(table.column_styles[0].0, 3);
+        assert_eq!(table.get_column_groups()[0].1, 1..4);
+        assert_eq!(table[1][2].get_content(), "x2");
+        assert_eq!(table[1][1].get_content(), "");
+        assert_eq!(table[2].len(), 1);
+        table.add_column("y", vec![Cell::new("y1"), Cell::new("y2"), Cell::new("y3"),
+                                   Cell::new("y4")]);
+        // The banner doesn't take any cell
+        assert_eq!(table.len(), 5);
+        assert_eq!(table[0].len(), 5);
+        assert_eq!(table[2].len(), 1);
+        assert_eq!(table[3][4].get_content(), "y3");
+        assert_eq!(table[4][4].get_content(), "y4");
+        table.remove_column(0);
+        table.remove_column(0);
+        assert_eq!(table.titles.as_ref().as_ref().unwrap()[0].get_content(), "x");
+        assert_eq!(table.get_column_groups()[0].1, 0..2);
+        table.remove_column(0);
+        table.remove_column(0);
+        assert!(table.get_column_groups().is_empty());
+        assert!(table.column_styles.is_empty());
+        assert_eq!(table.get_column_width(0), Width::Auto);
+        assert_eq!(table[0][0].get_content(), "y1");
+        assert_eq!(table[2][0].get_content(), "banner");
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid row : too short")]
+    fn insert_column_validated() {
+        let mut table = Table::new();
+        table.add_row(Row::from(vec!["a", "b"]));
+        table.set_row_validator(|row| if row.len() > 1 {
+                                    Ok(())
+                                } else {
+                                    Err("too short".to_string())
+                                });
+        table.insert_column(0, "", vec![Cell::new("1"), Cell::new("2")]);
+    }
+
+    #[test]
+    fn build_on_thread() {
+        let table = ::std::thread::spawn(|| {
+                                            let mut table = Table::new();
+                                            table.add_row(Row::from(vec!["a", "b"]));
+                                            table.add_computed_column("c", |r| r.len().to_string());
+                                            table
+                                        })
+                .join()
+                .unwrap();
+        assert_eq!(table.to_string().replace("\r\n", "\n"),
+                   "+---+---+---+\n| a | b | 2 |\n+---+---+---+\n");
+    }
+
+    #[test]
+    fn transpose() {
+        let mut table = Table::new();
+        table.set_titles(Row::from(vec!["a", "b", "c"]));
+        table.add_row(Row::from(vec!["1", "2", "3"]));
+        table.add_banner("banner", "");
+        table.add_row(Row::new(vec![Cell::new_align("4", Alignment::RIGHT)]));
+        let out = "\
++---+---+---+
+| a | 1 | 4 |
++---+---+---+
+| b | 2 |   |
++---+---+---+
+| c | 3 |   |
++---+---+---+
+";
+        assert_eq!(table.transpose(true).to_string().replace("\r\n", "\n"), out);
+        let transposed = table.transpose(false);
+        assert_eq!(transposed.len(), 3);
+        assert_eq!(transposed[0].len(), 2);
+        assert_eq!(transposed[0][1].get_alignment(), Alignment::RIGHT);
+        assert!(transposed.titles.is_none());
+        assert!(Table::new().transpose(true).is_empty());
+    }
+
+    #[test]
+    fn materialize() {
+        let mut table = Table::new();
+        table.set_titles(Row::from(vec!["a", "b"]));
+        table.add_row(Row::from(vec!["1", "2"]));
+        table.add_row(Row::from(vec!["3", "4"]));
+        table.add_computed_column("sum", |row| {
+            let sum: u32 = row.iter().filter_map(|c| c.get_content().parse::<u32>().ok()).sum();
+            sum.to_string()
+        });
+        table.set_column_style(0, Attr::Bold);
+        table.set_cell_formatter(|_, col, _| if col == 1 { Some(vec![Attr::Dim]) } else { None });
+        table.set_caption("caption", Alignment::LEFT);
+        let frozen = table.materialize();
+        assert!(frozen.computed.is_empty() && frozen.column_styles.is_empty());
+        assert!(frozen.cell_formatter.is_none());
+        assert_eq!(frozen.to_string(), table.to_string());
+        assert_eq!(frozen.render_spans(), table.render_spans());
+        assert_eq!(frozen[1][2].get_content(), "7");
+        assert_eq!(frozen[0][1].get_style(), &[Attr::Dim]);
+        table.add_row(Row::from(vec!["5", "6"]));
+        assert_eq!(frozen.len(), 2);
+        assert_eq!(table.slice(1..).materialize()[0][2].get_content(), "7");
+    }
+
+    #[test]
+    fn row_validator() {
+        let mut table = Table::new();
+        table.add_row(Row::from(vec!["a"]));
+        table.set_row_validator(|row| match row.get_cell(1) {
+                                    Some(c) if c.get_content().parse::<u32>().is_ok() => Ok(()),
+                                    _ => Err("column 1 must be a number".to_string()),
+                                });
+        assert!(table.try_add_row(Row::from(vec!["b", "2"])).is_ok());
+        assert!(table.try_insert_row(0, Row::from(vec!["c", "3"])).is_ok());
+        assert_eq!(table.try_add_row(Row::from(vec!["d", "x"])).unwrap_err(),
+                   "column 1 must be a number");
+        assert!(table.try_insert_row(5, Row::from(vec!["e"])).is_err());
         assert_eq!(table.len(), 3);
-        assert_eq!(table[2][1].get_content(), "2");
-        table.insert_row(1, Row::new(vec![Cell::new("3"), Cell::new("4"), Cell::new("5")]));
+        assert_eq!(table[0][0].get_content(), "c");
+        table.unset_row_validator();
+        table.add_empty_row();
         assert_eq!(table.len(), 4);
-        assert_eq!(table[1][1].get_content(), "4");
-        assert_eq!(table[2][1].get_content(), "bc");
     }
 
     #[test]
-    fn set_element() {
+    #[should_panic(expected = "Invalid row : rejected")]
+    fn row_validator_panic() {
         let mut table = Table::new();
-        table.add_row(Row::new(vec![Cell::new("a"), Cell::new("bc"), Cell::new("def")]));
-        table.add_row(Row::new(vec![Cell::new("def"), Cell::new("bc"), Cell::new("a")]));
-        assert!(table.set_element("foo", 12, 12).is_err());
-        assert!(table.set_element("foo", 1, 1).is_ok());
-        assert_eq!(table[1][1].get_content(), "foo");
+        table.set_row_validator(|_| Err("rejected".to_string()));
+        table.add_row(Row::from(vec!["a"]));
     }
 
     #[test]
-    fn no_linesep() {
+    fn colorize_column_gradient() {
         let mut table = Table::new();
-        table.set_format(*FORMAT_NO_LINESEP);
-        table.add_row(Row::new(vec![Cell::new("a"), Cell::new("bc"), Cell::new("def")]));
-        table.add_row(Row::new(vec![Cell::new("def"), Cell::new("bc"), Cell::new("a")]));
-        table.set_titles(Row::new(vec![Cell::new("t1"), Cell::new("t2"), Cell::new("t3")]));
-        assert_eq!(table[1][1].get_content(), "bc");
+        table.set_titles(Row::from(vec!["1"]));
+        for v in &["10", "x", "20", "15"] {
+            table.add_row(Row::from(vec![v]));
+        }
+        table.add_row(Row::banner(Cell::new("12")));
+        table.colorize_column_gradient(0, Rgb(0, 0, 0), Rgb(200, 100, 0));
+        let colors: Vec<_> = table.column_iter(0).take(4).map(|c| c.get_bg_rgb()).collect();
+        assert_eq!(colors,
+                   vec![Some(Rgb(0, 0, 0)), None, Some(Rgb(200, 100, 0)), Some(Rgb(100, 50, 0))]);
+        assert_eq!(table.titles.as_ref().as_ref().unwrap()[0].get_bg_rgb(), None);
+        assert_eq!(table[4][0].get_bg_rgb(), None);
+        let mut table = Table::init(vec![Row::from(vec!["5"])]);
+        table.colorize_column_gradient(0, Rgb(1, 2, 3), Rgb(200, 100, 0));
+        assert_eq!(table[0][0].get_bg_rgb(), Some(Rgb(1, 2, 3)));
+    }
 
-        table[1][1] = Cell::new("newval");
-        assert_eq!(table[1][1].get_content(), "newval");
+    #[test]
+    fn auto_align() {
+        let mut table = Table::new();
+        table.add_row(Row::from(vec!["", "abc", "true", "12"]));
+        table.add_row(Row::from(vec!["1.5", "2", "False", "x"]));
+        table.add_row(Row::from(vec!["10", "d", "yes", "123"]));
+        table.auto_align();
+        let out = "\
++-----+-----+-------+-----+
+|     | abc | true  |  12 |
++-----+-----+-------+-----+
+| 1.5 | 2   | False |   x |
++-----+-----+-------+-----+
+|  10 | d   |  yes  | 123 |
++-----+-----+-------+-----+
+";
+        assert_eq!(out, table.to_string().replace("\r\n", "\n"));
+    }
 
+    #[test]
+    fn column_groups() {
+        let mut table = Table::new();
+        table.set_titles(Row::from(vec!["name", "p50", "p99"]));
+        table.add_row(Row::from(vec!["foo", "1", "2"]));
+        table.add_column_group("Latency", 1..3);
         let out = "\
-+-----+--------+-----+
-| t1  | t2     | t3  |
-| a   | bc     | def |
-| def | newval | a   |
-+-----+--------+-----+
++------+-----+-----+
+|      |  Latency  |
++------+-----+-----+
+| name | p50 | p99 |
++======+=====+=====+
+| foo  | 1   | 2   |
++------+-----+-----+
 ";
-        assert_eq!(table.to_string().replace("\r\n", "\n"), out);
+        assert_eq!(out, table.to_string().replace("\r\n", "\n"));
+        assert_eq!(table.get_column_groups()[0].1, 1..3);
     }
 
     #[test]
-    fn no_colsep() {
+    fn caption() {
         let mut table = Table::new();
-        table.set_format(*FORMAT_NO_COLSEP);
-        table.add_row(Row::new(vec![Cell::new("a"), Cell::new("bc"), Cell::new("def")]));
-        table.add_row(Row::new(vec![Cell::new("def"), Cell::new("bc"), Cell::new("a")]));
-        table.set_titles(Row::new(vec![Cell::new("t1"), Cell::new("t2"), Cell::new("t3")]));
-        assert_eq!(table[1][1].get_content(), "bc");
-
-        table[1][1] = Cell::new("newval");
-        assert_eq!(table[1][1].get_content(), "newval");
-
+        table.add_row(Row::new(vec![Cell::new("a"), Cell::new("bc")]));
+        table.set_caption("Title", Alignment::CENTER);
+        let out = "  Title\n+---+----+\n| a | bc |\n+---+----+\n";
+        assert_eq!(out, table.to_string().replace("\r\n", "\n"));
+        table.set_caption_position(CaptionPosition::Below);
+        table.set_caption("Right", Alignment::RIGHT);
         let out = "\
-------------------
- t1   t2      t3 \n\
-==================
- a    bc      def \n\
-------------------
- def  newval  a \n\
-------------------
++---+----+
+| a | bc |
++---+----+
+     Right
 ";
-        println!("{}", out);
-        println!("____");
-        println!("{}", table.to_string().replace("\r\n", "\n"));
-        assert_eq!(table.to_string().replace("\r\n", "\n"), out);
+        assert_eq!(out, table.to_string().replace("\r\n", "\n"));
+        table.unset_caption();
+        assert_eq!(table.to_string().lines().count(), 3);
     }
 
     #[test]
-    fn clean() {
+    fn banner() {
         let mut table = Table::new();
-        table.set_format(*FORMAT_CLEAN);
-        table.add_row(Row::new(vec![Cell::new("a"), Cell::new("bc"), Cell::new("def")]));
-        table.add_row(Row::new(vec![Cell::new("def"), Cell::new("bc"), Cell::new("a")]));
-        table.set_titles(Row::new(vec![Cell::new("t1"), Cell::new("t2"), Cell::new("t3")]));
-        assert_eq!(table[1][1].get_content(), "bc");
-
-        table[1][1] = Cell::new("newval");
-        assert_eq!(table[1][1].get_content(), "newval");
-
+        table.add_row(Row::new(vec![Cell::new("a"), Cell::new("bc")]));
+        table.add_banner("Section with a long title", "c");
+        table.add_row(Row::new(vec![Cell::new("def"), Cell::new("g")]));
         let out = "\
-\u{0020}t1   t2      t3 \n\
-\u{0020}a    bc      def \n\
-\u{0020}def  newval  a \n\
++-----+----+
+| a   | bc |
++-----+----+
+| Section  |
++-----+----+
+| def | g  |
++-----+----+
 ";
-        println!("{}", out);
-        println!("____");
-        println!("{}", table.to_string().replace("\r\n", "\n"));
         assert_eq!(out, table.to_string().replace("\r\n", "\n"));
     }
 
     #[test]
-    fn padding() {
+    fn line_filter() {
         let mut table = Table::new();
-        let mut format = *FORMAT_DEFAULT;
-        format.padding(2, 2);
-        table.set_format(format);
-        table.add_row(Row::new(vec![Cell::new("a"), Cell::new("bc"), Cell::new("def")]));
-        table.add_row(Row::new(vec![Cell::new("def"), Cell::new("bc"), Cell::new("a")]));
-        table.set_titles(Row::new(vec![Cell::new("t1"), Cell::new("t2"), Cell::new("t3")]));
-        assert_eq!(table[1][1].get_content(), "bc");
+        table.set_format(*FORMAT_CLEAN);
+        table.add_row(Row::new(vec![Cell::new("a"), Cell::new("bc")]));
+        table.add_row(Row::new(vec![Cell::new("def"), Cell::new("g")]));
+        table.set_line_filter(|l| format!("[log]{}|", l));
+        let out = "[log] a    bc |\n[log] def  g |\n";
+        assert_eq!(out, table.to_string().replace("\r\n", "\n"));
+        assert_eq!(out, table.sample(2, 0).to_string().replace("\r\n", "\n"));
+        // Styled output is written to the terminal directly, without the filter
+        let mut term = SpanRecorder::new();
+        table.print_term(&mut term).unwrap();
+        let lines = term.into_lines();
+        assert_eq!(lines[0], vec![StyledSpan::new(" a    bc ", vec![])]);
+        assert_eq!(lines[1], vec![StyledSpan::new(" def  g ", vec![])]);
+        table.unset_line_filter();
+        assert_eq!(" a    bc \n def  g \n", table.to_string().replace("\r\n", "\n"));
+    }
 
-        table[1][1] = Cell::new("newval");
-        assert_eq!(table[1][1].get_content(), "newval");
+    #[test]
+    fn render_spans() {
+        let mut table = Table::new();
+        table.set_format(*FORMAT_CLEAN);
+        table.add_row(Row::new(vec![Cell::new("a").style_spec("bFr"), Cell::new("bc")]));
+        table.add_row(Row::new(vec![Cell::new("def"), Cell::new("g").style_spec("u")]));
+        let lines = table.render_spans();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0],
+                   vec![StyledSpan::new(" ", vec![]),
+                        StyledSpan::new("a  ", vec![Attr::Bold, Attr::ForegroundColor(color::RED)]),
+                        StyledSpan::new("  bc ", vec![])]);
+        assert_eq!(lines[1],
+                   vec![StyledSpan::new(" def  ", vec![]),
+                        StyledSpan::new("g", vec![Attr::Underline(true)]),
+                        StyledSpan::new(" ", vec![])]);
+        let text: Vec<String> = lines.iter()
+            .map(|l| l.iter().map(|s| s.get_text()).collect())
+            .collect();
+        assert_eq!(text.join("\n") + "\n", table.to_string().replace("\r\n", "\n"));
+    }
 
-        let out = "\
-+-------+----------+-------+
-|  t1   |  t2      |  t3   |
-+=======+==========+=======+
-|  a    |  bc      |  def  |
-+-------+----------+-------+
-|  def  |  newval  |  a    |
-+-------+----------+-------+
-";
-        println!("{}", out);
-        println!("____");
-        println!("{}", table.to_string().replace("\r\n", "\n"));
-        assert_eq!(out, table.to_string().replace("\r\n", "\n"));
+    #[test]
+    fn column_styles() {
+        let mut table = Table::new();
+        table.set_format(*FORMAT_CLEAN);
+        table.set_titles(Row::from(vec!["id", "name"]));
+        table.add_row(Row::from(vec!["1", "a"]));
+        table.set_column_style(0, Attr::Dim);
+        table.set_column_style_spec(1, "Fg");
+        table.add_row(Row::new(vec![Cell::new("2"), Cell::new("b").style_spec("u")]));
+        let lines = table.render_spans();
+        assert_eq!(lines[0], vec![StyledSpan::new(" id  name ", vec![])]);
+        assert_eq!(lines[1],
+                   vec![StyledSpan::new(" ", vec![]),
+                        StyledSpan::new("1 ", vec![Attr::Dim]),
+                        StyledSpan::new("  ", vec![]),
+                        StyledSpan::new("a", vec![Attr::ForegroundColor(color::GREEN)]),
+                        StyledSpan::new(" ", vec![])]);
+        assert_eq!(lines[2][3], StyledSpan::new("b", vec![Attr::Underline(true)]));
+        table.clear_column_styles();
+        assert_eq!(table.render_spans()[1], vec![StyledSpan::new(" 1   a ", vec![])]);
     }
 
     #[test]
-    fn indent() {
+    fn column_valign() {
         let mut table = Table::new();
-        table.add_row(Row::new(vec![Cell::new("a"), Cell::new("bc"), Cell::new("def")]));
-        table.add_row(Row::new(vec![Cell::new("def"), Cell::new("bc"), Cell::new("a")]));
-        table.set_titles(Row::new(vec![Cell::new("t1"), Cell::new("t2"), Cell::new("t3")]));
-        table.get_format().indent(8);
-        let out = "        +-----+----+-----+
-        | t1  | t2 | t3  |
-        +=====+====+=====+
-        | a   | bc | def |
-        +-----+----+-----+
-        | def | bc | a   |
-        +-----+----+-----+
-";
-        assert_eq!(table.to_string().replace("\r\n", "\n"), out);
+        table.set_format(*FORMAT_CLEAN);
+        table.add_row(Row::from(vec!["1\n2\n3", "a", "b", "c"]));
+        table.set_column_valign(1, VerticalAlignment::Bottom);
+        table.set_column_style_spec(2, "v");
+        table[0][3].valign(VerticalAlignment::Middle);
+        table.set_column_valign(3, VerticalAlignment::Bottom);
+        assert_eq!(table.get_column_valign(1), VerticalAlignment::Bottom);
+        assert_eq!(table.get_column_valign(2), VerticalAlignment::Middle);
+        assert_eq!(table.get_column_valign(0), VerticalAlignment::Top);
+        let expected = " 1         \n 2     b  c \n 3  a      \n";
+        assert_eq!(table.to_string().replace("\r\n", "\n"), expected);
     }
 
     #[test]
-    fn slices() {
+    fn toc() {
         let mut table = Table::new();
-        table.set_titles(Row::new(vec![Cell::new("t1"), Cell::new("t2"), Cell::new("t3")]));
-        table.add_row(Row::new(vec![Cell::new("0"), Cell::new("0"), Cell::new("0")]));
-        table.add_row(Row::new(vec![Cell::new("1"), Cell::new("1"), Cell::new("1")]));
-        table.add_row(Row::new(vec![Cell::new("2"), Cell::new("2"), Cell::new("2")]));
-        table.add_row(Row::new(vec![Cell::new("3"), Cell::new("3"), Cell::new("3")]));
-        table.add_row(Row::new(vec![Cell::new("4"), Cell::new("4"), Cell::new("4")]));
-        table.add_row(Row::new(vec![Cell::new("5"), Cell::new("5"), Cell::new("5")]));
+        table.set_format(*format::consts::FORMAT_TOC);
+        table.add_row(Row::from(vec!["Introduction", "1"]));
+        table.add_row(Row::from(vec!["Getting started", "12"]));
+        table.add_row(Row::new(vec![Cell::new("Index").with_fill('_'), Cell::new("")]));
+        assert_eq!(table.to_string().replace("\r\n", "\n"),
+                   "Introduction.....1\nGetting started.12\nIndex__________.  \n");
+
+        let mut table = Table::new();
+        table.set_format(*FORMAT_CLEAN);
+        table.add_row(Row::from(vec!["a", "bbb", "c"]));
+        table.add_row(Row::new(vec![Cell::new("dd"), Cell::new("e"), Cell::new("f\ng")]));
+        table.set_column_fill(1, '-');
+        table.set_column_style_spec(1, "b");
+        assert_eq!(table.get_column_fill(1), '-');
+        assert_eq!(table.get_column_fill(0), ' ');
+        assert_eq!(table.to_string().replace("\r\n", "\n"),
+                   " a   bbb  c \n dd  e--  f \n          g \n");
+    }
+
+    #[test]
+    fn debug_view() {
+        let mut table = Table::new();
+        table.set_titles(Row::from(vec!["a\tb"]));
+        table.add_row(Row::from(vec!["c\nd "]));
         let out = "\
-+----+----+----+
-| t1 | t2 | t3 |
-+====+====+====+
-| 1  | 1  | 1  |
-+----+----+----+
-| 2  | 2  | 2  |
-+----+----+----+
-| 3  | 3  | 3  |
-+----+----+----+
++-------------+
+| \"a\\tb\" [3]  |
++=============+
+| \"c\\nd·\" [2] |
++-------------+
 ";
-        let slice = table.slice(..);
-        let slice = slice.slice(1..);
-        let slice = slice.slice(..3);
-        assert_eq!(out, slice.to_string().replace("\r\n", "\n"));
-        assert_eq!(out, table.slice(1..4).to_string().replace("\r\n", "\n"));
+        assert_eq!(out, table.debug_view().to_string().replace("\r\n", "\n"));
     }
 
     #[test]
-    fn test_unicode_separators() {
+    fn cell_formatter() {
         let mut table = Table::new();
-        table.set_format(format::FormatBuilder::new()
-                             .column_separator('│')
-                             .borders('│')
-                             .separators(&[format::LinePosition::Top],
-                                         format::LineSeparator::new('─',
-                                                                    '┬',
-                                                                    '┌',
-                                                                    '┐'))
-                             .separators(&[format::LinePosition::Intern],
-                                         format::LineSeparator::new('─',
-                                                                    '┼',
-                                                                    '├',
-                                                                    '┤'))
-                             .separators(&[format::LinePosition::Bottom],
-                                         format::LineSeparator::new('─',
-                                                                    '┴',
-                                                                    '└',
-                                                                    '┘'))
-                             .padding(1, 1)
+        table.set_format(*FORMAT_CLEAN);
+        table.set_titles(Row::from(vec!["-1"]));
+        table.add_row(Row::from(vec!["1", "-2"]));
+        table.add_row(Row::new(vec![Cell::new("-3").style_spec("b")]));
+        table.set_cell_formatter(|row, col, content| {
+            let color = color::RED + (row + col) as color::Color;
+            if content.starts_with('-') {
+                Some(vec![Attr::ForegroundColor(color)])
+            } else {
+                None
+            }
+        });
+        let lines = table.render_spans();
+        assert_eq!(lines[0], vec![StyledSpan::new(" -1   ", vec![])]);
+        assert_eq!(lines[1][1],
+                   StyledSpan::new("-2", vec![Attr::ForegroundColor(color::RED + 1)]));
+        assert_eq!(lines[2][1],
+                   StyledSpan::new("-3", vec![Attr::Bold, Attr::ForegroundColor(color::RED + 1)]));
+        table.unset_cell_formatter();
+        assert_eq!(table.render_spans()[1], vec![StyledSpan::new(" 1   -2 ", vec![])]);
+        assert_eq!(table.get_row(1).unwrap().get_cell(0).unwrap().get_style(), &[Attr::Bold]);
+    }
+
+    #[test]
+    fn stripe_groups() {
+        let mut table = Table::new();
+        table.set_format(FormatBuilder::from(*FORMAT_CLEAN)
+                             .stripe(Attr::Bold)
+                             .stripe_by_column(0)
                              .build());
-        table.add_row(Row::new(vec![Cell::new("1"), Cell::new("1"), Cell::new("1")]));
-        table.add_row(Row::new(vec![Cell::new("2"), Cell::new("2"), Cell::new("2")]));
-        table.set_titles(Row::new(vec![Cell::new("t1"), Cell::new("t2"), Cell::new("t3")]));
-        let out = "\
-┌────┬────┬────┐
-│ t1 │ t2 │ t3 │
-├────┼────┼────┤
-│ 1  │ 1  │ 1  │
-├────┼────┼────┤
-│ 2  │ 2  │ 2  │
-└────┴────┴────┘
-";
-        println!("{}", out);
-        println!("____");
-        println!("{}", table.to_string().replace("\r\n", "\n"));
-        assert_eq!(out, table.to_string().replace("\r\n", "\n"));
+        for key in &["a", "a", "b", "c", "c"] {
+            table.add_row(Row::from(vec![*key, "x"]));
+        }
+        table.insert_row(3, Row::banner(Cell::new("banner")));
+        let striped = |table: &Table| -> Vec<bool> {
+            table.render_spans()
+                .iter()
+                .map(|l| l.iter().any(|s| !s.get_style().is_empty()))
+                .collect()
+        };
+        assert_eq!(striped(&table), vec![false, false, true, true, false, false]);
+        table.get_format().unset_stripe_by_column();
+        assert_eq!(striped(&table), vec![false, true, false, true, false, true]);
+    }
+
+    #[test]
+    fn title_style() {
+        let mut table = Table::new();
+        table.set_format(FormatBuilder::from(*FORMAT_CLEAN).title_style("bc").build());
+        table.set_titles(Row::new(vec![Cell::new("id"),
+                                       Cell::new("name").style_spec("Fr"),
+                                       Cell::new_align("n", Alignment::RIGHT)]));
+        table.add_row(Row::from(vec!["1", "foobar", "1234"]));
+        let lines = table.render_spans();
+        assert_eq!(lines[0],
+                   vec![StyledSpan::new(" ", vec![]),
+                        StyledSpan::new("id", vec![Attr::Bold]),
+                        StyledSpan::new("  ", vec![]),
+                        StyledSpan::new("name  ", vec![Attr::ForegroundColor(color::RED)]),
+                        StyledSpan::new("  ", vec![]),
+                        StyledSpan::new("   n", vec![Attr::Bold]),
+                        StyledSpan::new(" ", vec![])]);
+        assert_eq!(lines[1], vec![StyledSpan::new(" 1   foobar  1234 ", vec![])]);
     }
 
     #[cfg(feature = "csv")]
@@ -963,6 +5075,11 @@ mod tests {
             table
         }
 
+        fn to_string(table: &Table) -> String {
+            let writer = table.to_csv(Vec::new()).unwrap();
+            String::from_utf8(writer.into_inner().unwrap()).unwrap()
+        }
+
         #[test]
         fn from() {
             assert_eq!(test_table().to_string().replace("\r\n", "\n"),
@@ -974,19 +5091,316 @@ mod tests {
 
         #[test]
         fn to() {
-            assert_eq!(test_table().to_csv(Vec::new()).unwrap().as_string(), CSV_S);
+            assert_eq!(to_string(&test_table()), CSV_S);
         }
 
         #[test]
         fn trans() {
-            assert_eq!(Table::from_csv_string(test_table()
-                                                  .to_csv(Vec::new())
-                                                  .unwrap()
-                                                  .as_string())
+            assert_eq!(Table::from_csv_string(&to_string(&test_table()))
                                .unwrap()
                                .to_string()
                                .replace("\r\n", "\n"),
                        test_table().to_string().replace("\r\n", "\n"));
         }
+
+        #[test]
+        fn to_opts() {
+            use export::CsvOptions;
+            use csv::{QuoteStyle, Terminator};
+
+            let mut table = test_table();
+            table.set_titles(Row::new(vec![Cell::new("a b"), Cell::new("c"), Cell::new("d")]));
+            let options = CsvOptions::new().delimiter(b'|').terminator(Terminator::CRLF);
+            let writer = table.to_csv_opts(Vec::new(), &options).unwrap();
+            let out = String::from_utf8(writer.into_inner().unwrap()).unwrap();
+            let rows = CSV_S.replace(",", "|").replace("\n", "\r\n");
+            assert_eq!(out, format!("a b|c|d\r\n{}", rows));
+            table.unset_titles();
+            table.add_row(Row::new(vec![Cell::new("1"), Cell::new("x,y"), Cell::new("z")]));
+            let options = CsvOptions::new().titles(false).quote_style(QuoteStyle::NonNumeric);
+            let writer = table.to_csv_opts(Vec::new(), &options).unwrap();
+            let out = String::from_utf8(writer.into_inner().unwrap()).unwrap();
+            assert!(out.ends_with("\"foo2\"\n1,\"x,y\",\"z\"\n"));
+        }
+
+        #[test]
+        fn malformed() {
+            let err = Table::from_csv_string("a,b\nc,d\ne\n").unwrap_err();
+            assert_eq!(err.position().map(|p| p.record()), Some(2));
+        }
+
+        #[test]
+        fn lenient() {
+            use import::ImportError;
+            use csv::ReaderBuilder;
+
+            let data = "a,b\nc\nd,e,f\ng,h\n";
+            let mut reader = ReaderBuilder::new().has_headers(false).from_reader(data.as_bytes());
+            let (table, errors) = Table::from_csv_lenient(&mut reader);
+            assert_eq!(table.len(), 2);
+            assert_eq!(table[1][1].get_content(), "h");
+            let records: Vec<_> = errors.iter()
+                .map(|e| match *e {
+                    ImportError::Csv(ref error) => error.position().map(|p| p.record()),
+                    _ => None,
+                })
+                .collect();
+            assert_eq!(records, vec![Some(1), Some(2)]);
+        }
+
+        #[test]
+        fn with_options() {
+            use import::ImportOptions;
+            use csv::ReaderBuilder;
+
+            let data = &b"id\tname\n1\t a\xff \n"[..];
+            let mut reader = ReaderBuilder::new().delimiter(b'\t').from_reader(data);
+            let err = Table::from_csv_with(&mut reader, &ImportOptions::new()).unwrap_err();
+            assert!(err.to_string().starts_with("row 1, column 1: invalid UTF-8"));
+            let mut reader = ReaderBuilder::new().delimiter(b'\t').from_reader(data);
+            let options = ImportOptions::new().trim(true).utf8(::import::Utf8Policy::Lossy);
+            let table = Table::from_csv_with(&mut reader, &options).unwrap();
+            assert_eq!(table.len(), 1);
+            assert_eq!(table[0][1].get_content(), "a\u{FFFD}");
+            assert!(table.to_string().contains("| id | name |"));
+        }
+    }
+
+    #[test]
+    fn lazy_cells() {
+        use std::sync::Arc;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        let calls = Arc::new(AtomicUsize::new(0));
+        let mut table = Table::new();
+        for i in 0..3 {
+            let calls = calls.clone();
+            table.add_row(Row::new(vec![Cell::new_lazy(move || {
+                                                           calls.fetch_add(1, Ordering::SeqCst);
+                                                           format!("line {}\nof {}", i, i)
+                                                       })]));
+        }
+        let out = table.slice(1..2).to_string();
+        assert_eq!(out.replace("\r\n", "\n"),
+                   "+--------+\n| line 1 |\n| of 1   |\n+--------+\n");
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        let copy = table.clone();
+        table.to_string();
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+        assert!(copy[0][0].is_computed());
+        assert_eq!(copy[2][0].get_height(), 2);
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    fn unicode_alignment() {
+        let mut table = Table::new();
+        table.set_titles(Row::from(vec!["名前", "ville"]));
+        table.add_row(Row::from(vec!["Zoe\u{308}", "東京"]));
+        table.add_row(Row::from(vec!["a", "Orléans"]));
+        let out = table.to_string().replace("\r\n", "\n");
+        assert_eq!(out,
+                   "+------+---------+\n\
+                    | 名前 | ville   |\n\
+                    +======+=========+\n\
+                    | Zoe\u{308}  | 東京    |\n\
+                    +------+---------+\n\
+                    | a    | Orléans |\n\
+                    +------+---------+\n");
+    }
+
+    #[test]
+    fn display_flags() {
+        let mut table = Table::new();
+        table.add_row(Row::from(vec!["a", "bc"]));
+        table.set_format(*FORMAT_CLEAN);
+        let out = format!("[{:6}]", table).replace("\r\n", "\n");
+        assert_eq!(out, "[ a  bc \n]");
+        let out = format!("{:*<8.4}|", table).replace("\r\n", "\n");
+        assert_eq!(out, " a  ****\n|");
+        assert_eq!(format!("{}", table), table.to_string());
+        let out = format!("{:.3}", table.slice(..)).replace("\r\n", "\n");
+        assert_eq!(out, " a \n");
+        if cfg!(fmt_align) {
+            let out = format!("[{:>10}]", table).replace("\r\n", "\n");
+            assert_eq!(out, "[    a  bc \n]");
+            let out = format!("[{:-^12}]", table).replace("\r\n", "\n");
+            assert_eq!(out, "[-- a  bc ---\n]");
+        }
+    }
+
+    #[test]
+    fn buffered_print() {
+        use std::io::{self, Write};
+        struct CountWrites(Vec<u8>, usize);
+        impl Write for CountWrites {
+            fn write(&mut self, data: &[u8]) -> io::Result<usize> {
+                self.1 += 1;
+                self.0.write(data)
+            }
+            fn flush(&mut self) -> io::Result<()> {
+                Ok(())
+            }
+        }
+        let mut table = Table::new();
+        table.set_titles(Row::from(vec!["name", "value"]));
+        for i in 0..20 {
+            table.add_row(Row::from(vec![format!("row {}", i), i.to_string()]));
+        }
+        let mut out = CountWrites(Vec::new(), 0);
+        table.print(&mut out).unwrap();
+        assert_eq!(out.1, 1);
+        assert_eq!(String::from_utf8(out.0).unwrap(), table.to_string());
+        let mut out = CountWrites(Vec::new(), 0);
+        table.slice(..5).print(&mut out).unwrap();
+        assert_eq!(out.1, 1);
+    }
+
+    #[test]
+    fn buffered_print_term() {
+        use std::io::{self, Write};
+        use term::{self, Terminal};
+        // Writes are counted, and style changes are written as markers
+        struct CountTerm(Vec<u8>, usize, usize);
+        impl Write for CountTerm {
+            fn write(&mut self, data: &[u8]) -> io::Result<usize> {
+                self.1 += 1;
+                self.0.write(data)
+            }
+            fn flush(&mut self) -> io::Result<()> {
+                Ok(())
+            }
+        }
+        impl Terminal for CountTerm {
+            type Output = CountTerm;
+            fn fg(&mut self, color: color::Color) -> term::Result<()> {
+                self.attr(Attr::ForegroundColor(color))
+            }
+            fn bg(&mut self, color: color::Color) -> term::Result<()> {
+                self.attr(Attr::BackgroundColor(color))
+            }
+            fn attr(&mut self, _: Attr) -> term::Result<()> {
+                self.2 += 1;
+                self.0.extend_from_slice(b"<style>");
+                Ok(())
+            }
+            fn supports_attr(&self, _: Attr) -> bool {
+                true
+            }
+            fn reset(&mut self) -> term::Result<()> {
+                self.2 += 1;
+                self.0.extend_from_slice(b"<reset>");
+                Ok(())
+            }
+            fn supports_reset(&self) -> bool {
+                true
+            }
+            fn supports_color(&self) -> bool {
+                true
+            }
+            fn cursor_up(&mut self) -> term::Result<()> {
+                Err(term::Error::NotSupported)
+            }
+            fn delete_line(&mut self) -> term::Result<()> {
+                Err(term::Error::NotSupported)
+            }
+            fn carriage_return(&mut self) -> term::Result<()> {
+                Err(term::Error::NotSupported)
+            }
+            fn get_ref(&self) -> &CountTerm {
+                self
+            }
+            fn get_mut(&mut self) -> &mut CountTerm {
+                self
+            }
+            fn into_inner(self) -> CountTerm {
+                self
+            }
+        }
+        let mut table = Table::new();
+        table.set_titles(Row::new(vec![Cell::new("name").style_spec("bFg"), Cell::new("value")]));
+        for i in 0..20 {
+            table.add_row(Row::new(vec![Cell::new(&format!("row {}", i)),
+                                        Cell::new(&i.to_string()).style_spec("Fr")]));
+        }
+        let mut unbuffered = CountTerm(Vec::new(), 0, 0);
+        table.as_slice().__print(&mut unbuffered, &PrintLimits::new(), Row::print_term).unwrap();
+        let mut out = CountTerm(Vec::new(), 0, 0);
+        table.print_term(&mut out).unwrap();
+        // Styles are applied to the same text, with one write at most between style changes
+        assert_eq!(String::from_utf8(out.0).unwrap(),
+                   String::from_utf8(unbuffered.0).unwrap());
+        assert!(out.1 <= out.2 + 1);
+        assert!(out.1 * 2 < unbuffered.1);
+        let mut out = CountTerm(Vec::new(), 0, 0);
+        table.slice(..5).print_term(&mut out).unwrap();
+        assert!(out.1 <= out.2 + 1);
+    }
+
+    #[test]
+    fn ambiguous_width() {
+        let mut table = Table::from(vec![vec!["±5", "°C"], vec!["abc", "d"]]);
+        assert_eq!(table.rendered_column_widths(), vec![3, 2]);
+        table.get_format().ambiguous_width(format::AmbiguousWidth::Wide);
+        assert_eq!(table.rendered_column_widths(), vec![3, 3]);
+        let out = "\
++-----+-----+
+| ±5 | °C |
++-----+-----+
+| abc | d   |
++-----+-----+
+";
+        assert_eq!(table.to_string().replace("\r\n", "\n"), out);
+        assert_eq!(table.measure(&PrintOptions::new()), (13, 5));
+        // Cells keep their own setting
+        assert_eq!(table[0][0].get_width(), 2);
+    }
+
+    #[test]
+    fn table_macro() {
+        let table = table!(["H1", "H2"]; => [1, 2], [3, 4]);
+        assert_eq!(table.len(), 2);
+        assert_eq!(table.titles.as_ref().map(|t| t[1].get_content()), Some("H2".to_string()));
+        assert_eq!(table[1][0].get_content(), "3");
+        let table = table!([Fr->"a", "b"]);
+        assert!(table.titles.is_none());
+        assert_eq!(table[0][0].get_style_spec(), "Fr");
+        let table = table!(["H1", "H2"]; =>);
+        assert_eq!(table.len(), 0);
+        assert_eq!(table.titles.as_ref().map(|t| t.len()), Some(2));
+        let table = table!();
+        assert!(table.is_empty() && table.titles.is_none());
+    }
+
+    #[test]
+    fn add_row_macros() {
+        let mut table = table!(["a", "b"]);
+        add_row!(table, "c", 1).add_cell(Cell::new("extra"));
+        add_row!(table, Fr->"d", "e");
+        add_row!(table, Fg => 2, 3);
+        assert_eq!(table.len(), 4);
+        assert_eq!(table[1][2].get_content(), "extra");
+        assert_eq!(table[2][0].get_style_spec(), "Fr");
+        assert_eq!(table[2][1].get_style_spec(), "");
+        assert_eq!(table[3][1].get_content(), "3");
+        assert_eq!(table[3][1].get_style_spec(), "Fg");
+        add_rows!(table, ["f", 4], [Fr->"g", 5], [b => "h", 6]);
+        add_rows!(table,);
+        assert_eq!(table.len(), 7);
+        assert_eq!(table[5][0].get_style_spec(), "Fr");
+        assert_eq!(table[6][1].get_style_spec(), "b");
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid row : 2 columns expected")]
+    fn add_row_macro_rejected() {
+        let mut table = Table::new();
+        table.set_row_validator(|row| if row.len() == 2 {
+                                    Ok(())
+                                } else {
+                                    Err("2 columns expected".to_string())
+                                });
+        add_rows!(table, ["a", "b"]);
+        assert_eq!(table.len(), 1);
+        add_row!(table, "a", "b", "c");
     }
 }
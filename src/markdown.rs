@@ -0,0 +1,118 @@
+//! This module contains utilities to import tables from Markdown documents
+use super::Table;
+use super::row::Row;
+use super::cell::Cell;
+use super::format::Alignment;
+
+/// Split a Markdown table line into its trimmed cells.
+/// Leading and trailing pipes are optional, and escaped pipes (`\|`) are kept in cell content
+fn split_line(line: &str) -> Vec<String> {
+    let line = line.trim();
+    let line = if line.starts_with('|') { &line[1..] } else { line };
+    let mut cells = Vec::new();
+    let mut cell = String::new();
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if chars.peek() == Some(&'|') => {
+                cell.push('|');
+                chars.next();
+            }
+            '|' => cells.push(::std::mem::replace(&mut cell, String::new())),
+            _ => cell.push(c),
+        }
+    }
+    // A trailing pipe closes the last cell instead of opening an empty one
+    if !cell.is_empty() || !line.ends_with('|') {
+        cells.push(cell);
+    }
+    cells.iter().map(|c| c.trim().to_string()).collect()
+}
+
+/// Parse the cells of a delimiter row, like `| :--- | :---: | ---: |`, into column alignments.
+/// Return `None` if one of the cells is not a valid delimiter
+fn parse_alignments(cells: &[String]) -> Option<Vec<Alignment>> {
+    cells.iter()
+        .map(|c| {
+            let dashes = c.trim_matches(':');
+            if dashes.is_empty() || dashes.chars().any(|c| c != '-') {
+                return None;
+            }
+            Some(match (c.starts_with(':'), c.ends_with(':')) {
+                     (true, true) => Alignment::CENTER,
+                     (false, true) => Alignment::RIGHT,
+                     _ => Alignment::LEFT,
+                 })
+        })
+        .collect()
+}
+
+impl Table {
+    /// Create a table from a Markdown pipe table, like the ones found in GitHub flavored Markdown.
+    /// The header line is used as titles, and the alignment given in the delimiter row is applied
+    /// to every cell of the corresponding column. Lines before the header and after the first line
+    /// not containing a pipe are ignored, so a table can be read from a whole document.
+    /// Return `None` if no header line followed by a delimiter row is found
+    ///
+    /// # Examples
+    /// ```rust
+    /// use prettytable::Table;
+    ///
+    /// let table = Table::from_markdown("| name | qty |\n|:-----|----:|\n| foo  |   1 |").unwrap();
+    /// assert_eq!(table.len(), 1);
+    /// ```
+    pub fn from_markdown(markdown: &str) -> Option<Table> {
+        let lines: Vec<&str> = markdown.lines().collect();
+        let (start, aligns) = match lines.windows(2)
+                  .enumerate()
+                  .filter(|&(_, w)| w[0].contains('|') && w[1].contains('|'))
+                  .filter_map(|(i, w)| parse_alignments(&split_line(w[1])).map(|a| (i, a)))
+                  .next() {
+            Some(found) => found,
+            None => return None,
+        };
+        let make_row = |line: &str| {
+            let mut cells = split_line(line);
+            cells.resize(aligns.len(), String::new());
+            Row::new(cells.iter()
+                         .zip(aligns.iter())
+                         .map(|(c, a)| Cell::new_align(c, *a))
+                         .collect())
+        };
+        let mut table = Table::new();
+        table.set_titles(make_row(lines[start]));
+        for line in lines[start + 2..].iter().take_while(|l| l.contains('|')) {
+            table.add_row(make_row(line));
+        }
+        Some(table)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split() {
+        assert_eq!(split_line("| a | b |"), vec!["a", "b"]);
+        assert_eq!(split_line("a | b"), vec!["a", "b"]);
+        assert_eq!(split_line("| a \\| b | |"), vec!["a | b", ""]);
+    }
+
+    #[test]
+    fn from_markdown() {
+        let md = "# Title\n\n| name | mid | qty |\n|---|:-:|--:|\n| x | y | 3 |\n| 4 |\n\nText";
+        let table = Table::from_markdown(md).unwrap();
+        let out = "\
++------+-----+-----+
+| name | mid | qty |
++======+=====+=====+
+| x    |  y  |   3 |
++------+-----+-----+
+| 4    |     |     |
++------+-----+-----+
+";
+        assert_eq!(out, table.to_string().replace("\r\n", "\n"));
+        assert!(Table::from_markdown("| a | b |\n| 1 | 2 |").is_none());
+    }
+}
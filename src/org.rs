@@ -0,0 +1,153 @@
+//! This module contains utilities to import and export tables in Emacs org-mode format
+use std::cmp;
+
+use super::{Table, TableSlice};
+use super::row::Row;
+use super::cell::Cell;
+use super::format::line_width;
+
+/// Org-mode has no escape sequence for pipes in cells, so they are replaced by this entity
+const PIPE_ENTITY: &'static str = "\\vert{}";
+
+/// Check if `line` is a horizontal separator, like `|---+---|`
+fn is_hline(line: &str) -> bool {
+    line.starts_with("|-")
+}
+
+/// Split an org table line into its trimmed cells
+fn split_line(line: &str) -> Vec<String> {
+    let line = &line[1..];
+    let line = if line.ends_with('|') { &line[..line.len() - 1] } else { line };
+    line.split('|').map(|c| c.trim().replace(PIPE_ENTITY, "|")).collect()
+}
+
+/// Convert `cell` to the content of an org table cell, which must fit on a single line
+fn cell_content(cell: &Cell) -> String {
+    cell.get_content().replace('\n', " ").replace('|', PIPE_ENTITY)
+}
+
+impl Table {
+    /// Create a table from an org-mode table.
+    /// If the table contains a horizontal separator after some rows, the first row is used as
+    /// titles. Other separators are ignored. Lines before the table and after its first
+    /// following non table line are ignored, so a table can be read from a whole org document.
+    /// Return `None` if no table is found
+    ///
+    /// # Examples
+    /// ```rust
+    /// use prettytable::Table;
+    ///
+    /// let table = Table::from_org("| name | qty |\n|------+-----|\n| foo  |   1 |").unwrap();
+    /// assert_eq!(table.len(), 1);
+    /// ```
+    pub fn from_org(org: &str) -> Option<Table> {
+        let lines: Vec<&str> = org.lines()
+            .map(|l| l.trim())
+            .skip_while(|l| !l.starts_with('|'))
+            .take_while(|l| l.starts_with('|'))
+            .collect();
+        if lines.is_empty() {
+            return None;
+        }
+        let has_titles = match lines.iter().position(|l| is_hline(l)) {
+            Some(pos) => lines[..pos].iter().any(|l| !is_hline(l)),
+            None => false,
+        };
+        let mut rows = lines.iter()
+            .filter(|l| !is_hline(l))
+            .map(|l| Row::new(split_line(l).iter().map(|c| Cell::new(c)).collect()));
+        let mut table = Table::new();
+        if has_titles {
+            if let Some(titles) = rows.next() {
+                table.set_titles(titles);
+            }
+        }
+        for row in rows {
+            table.add_row(row);
+        }
+        Some(table)
+    }
+
+    /// Export the table in org-mode format. See `TableSlice::to_org`
+    pub fn to_org(&self) -> String {
+        self.as_slice().to_org()
+    }
+}
+
+impl<'a> TableSlice<'a> {
+    /// Export the table in org-mode format, with titles separated from other rows by a
+    /// horizontal separator. Multi-line cells are joined on a single line, and pipes are
+    /// replaced by the `\vert{}` entity
+    pub fn to_org(&self) -> String {
+        let rows: Vec<Vec<String>> = self.titles
+            .iter()
+            .chain(self.rows.iter())
+            .map(|r| r.iter().map(cell_content).collect())
+            .collect();
+        let columns = rows.iter().map(|r| r.len()).max().unwrap_or(0);
+        let widths: Vec<usize> = (0..columns)
+            .map(|i| {
+                     rows.iter()
+                         .filter_map(|r| r.get(i))
+                         .fold(1, |w, c| cmp::max(w, line_width(&c[..])))
+                 })
+            .collect();
+        let mut out = String::new();
+        for (i, row) in rows.iter().enumerate() {
+            out.push('|');
+            for (col, width) in widths.iter().enumerate() {
+                let content = row.get(col).map(|c| &c[..]).unwrap_or("");
+                out.push(' ');
+                out.push_str(content);
+                for _ in line_width(content)..*width + 1 {
+                    out.push(' ');
+                }
+                out.push('|');
+            }
+            out.push('\n');
+            if i == 0 && self.titles.is_some() {
+                let dashes: Vec<String> = widths.iter()
+                    .map(|w| (0..w + 2).map(|_| '-').collect())
+                    .collect();
+                out.push('|');
+                out.push_str(&dashes.join("+"));
+                out.push_str("|\n");
+            }
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn to_org() {
+        let mut table = Table::new();
+        table.set_titles(Row::from(vec!["name", "qty"]));
+        table.add_row(Row::from(vec!["a|b", "10"]));
+        table.add_row(Row::from(vec!["c"]));
+        let out = "\
+| name      | qty |
+|-----------+-----|
+| a\\vert{}b | 10  |
+| c         |     |
+";
+        assert_eq!(out, table.to_org());
+    }
+
+    #[test]
+    fn round_trip() {
+        let expected = "| name | qty |\n|------+-----|\n| a    | 1   |\n| b    | 2   |\n";
+        let org = "Intro\n| name | qty |\n|------+-----|\n| a    | 1   |\n|--+--|\n\
+                   | b    | 2   |\n";
+        let table = Table::from_org(org).unwrap();
+        assert_eq!(table.len(), 2);
+        assert_eq!(table.get_row(1).unwrap().get_cell(0).unwrap().get_content(), "b");
+        let table = Table::from_org(&table.to_org()).unwrap();
+        assert_eq!(table.to_org(), expected);
+        assert!(Table::from_org("| a |\n| b |").unwrap().to_org().starts_with("| a |\n| b |"));
+        assert!(Table::from_org("no table").is_none());
+    }
+}
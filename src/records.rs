@@ -0,0 +1,132 @@
+//! This module contains utilities to import tables from key/value records, like maps or JSON objects
+use std::collections::HashSet;
+
+use super::Table;
+use super::row::Row;
+use super::cell::Cell;
+
+/// Policy used to order the columns of a table imported from key/value records
+#[derive(Clone, Debug, PartialEq)]
+pub enum ColumnOrder {
+    /// Columns are ordered by first appearance of their key in the records
+    FirstSeen,
+    /// Columns are ordered alphabetically by key
+    Alphabetical,
+    /// Columns are exactly the given keys, in the given order.
+    /// Keys not in the list are ignored
+    Explicit(Vec<String>),
+}
+
+impl ColumnOrder {
+    /// Compute the ordered list of column keys for `records`.
+    /// Only `FirstSeen` depends on the iteration order of the records' keys,
+    /// so prefer other policies with unordered maps like `HashMap`
+    pub fn resolve<'a, I, R, K, V>(&self, records: I) -> Vec<String>
+        where I: IntoIterator<Item = R>,
+              R: IntoIterator<Item = &'a (K, V)>,
+              K: AsRef<str> + 'a,
+              V: 'a
+    {
+        if let ColumnOrder::Explicit(ref keys) = *self {
+            return keys.clone();
+        }
+        let mut seen = HashSet::new();
+        let mut keys = Vec::new();
+        for record in records {
+            for &(ref k, _) in record {
+                if seen.insert(k.as_ref().to_string()) {
+                    keys.push(k.as_ref().to_string());
+                }
+            }
+        }
+        if *self == ColumnOrder::Alphabetical {
+            keys.sort();
+        }
+        keys
+    }
+}
+
+impl Table {
+    /// Create a table from key/value `records`, such as maps or JSON objects.
+    /// Each record is converted into a row, and the titles are set to the column keys,
+    /// ordered according to `order`. Keys missing in a record are printed as empty cells.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use prettytable::Table;
+    /// use prettytable::records::ColumnOrder;
+    ///
+    /// let records = vec![vec![("name", "foo"), ("id", "1")], vec![("id", "2")]];
+    /// let table = Table::from_records(records, &ColumnOrder::Alphabetical);
+    /// table.printstd(); // Columns are "id" then "name"
+    /// ```
+    pub fn from_records<I, R, K, V>(records: I, order: &ColumnOrder) -> Table
+        where I: IntoIterator<Item = R>,
+              R: IntoIterator<Item = (K, V)>,
+              K: AsRef<str>,
+              V: ToString
+    {
+        let records: Vec<Vec<(K, V)>> = records.into_iter()
+            .map(|r| r.into_iter().collect())
+            .collect();
+        let keys = order.resolve(&records);
+        let mut table = Table::new();
+        table.set_titles(Row::new(keys.iter().map(|k| Cell::new(k)).collect()));
+        for record in &records {
+            let cells = keys.iter()
+                .map(|key| match record.iter().find(|&&(ref k, _)| k.as_ref() == key) {
+                         Some(&(_, ref v)) => Cell::new(&v.to_string()),
+                         None => Cell::default(),
+                     })
+                .collect();
+            table.add_row(Row::new(cells));
+        }
+        table
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn records() -> Vec<Vec<(&'static str, i32)>> {
+        vec![vec![("b", 1), ("c", 2)], vec![("a", 3), ("b", 4)]]
+    }
+
+    #[test]
+    fn resolve() {
+        let records = records();
+        assert_eq!(ColumnOrder::FirstSeen.resolve(&records), vec!["b", "c", "a"]);
+        assert_eq!(ColumnOrder::Alphabetical.resolve(&records), vec!["a", "b", "c"]);
+        let explicit = ColumnOrder::Explicit(vec!["c".to_string(), "a".to_string()]);
+        assert_eq!(explicit.resolve(&records), vec!["c", "a"]);
+    }
+
+    #[test]
+    fn from_records() {
+        let table = Table::from_records(records(), &ColumnOrder::FirstSeen);
+        let out = "\
++---+---+---+
+| b | c | a |
++===+===+===+
+| 1 | 2 |   |
++---+---+---+
+| 4 |   | 3 |
++---+---+---+
+";
+        assert_eq!(out, table.to_string().replace("\r\n", "\n"));
+        let explicit = ColumnOrder::Explicit(vec!["a".to_string()]);
+        assert_eq!(Table::from_records(records(), &explicit).get_column_num(), 1);
+    }
+
+    #[test]
+    fn stable_order_from_maps() {
+        let mut map = HashMap::new();
+        for k in &["z", "y", "x", "w", "v"] {
+            map.insert(k.to_string(), 0);
+        }
+        let table = Table::from_records(vec![map.clone(), map], &ColumnOrder::Alphabetical);
+        assert_eq!(table.to_string().lines().nth(1).unwrap(), "| v | w | x | y | z |");
+    }
+}
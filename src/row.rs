@@ -3,23 +3,79 @@ use std::io::{Write, Error};
 use std::iter::FromIterator;
 use std::slice::{Iter, IterMut};
 use std::ops::{Index, IndexMut};
+use std::cmp;
 
 use term::Terminal;
 
-use super::utils::NEWLINE;
+use super::utils::print_spanned_row_lines;
 use super::cell::Cell;
-use super::format::{TableFormat, ColumnPosition};
+use super::format::{TableFormat, Alignment, CenterBias, line_width_with};
+use super::span::StyledText;
 
 /// Represent a table row made of cells
 #[derive(Clone, Debug)]
 pub struct Row {
     cells: Vec<Cell>,
+    spans: Vec<usize>,
+    banner: bool,
+    id: Option<String>,
 }
 
 impl Row {
     /// Create a new `Row` backed with `cells` vector
     pub fn new(cells: Vec<Cell>) -> Row {
-        Row { cells: cells }
+        Row {
+            cells: cells,
+            spans: Vec::new(),
+            banner: false,
+            id: None,
+        }
+    }
+
+    /// Create a new `Row` where each cell spans over the given number of columns.
+    /// Spanned rows are not taken into account when computing column widths
+    pub fn spanned(cells: Vec<(Cell, usize)>) -> Row {
+        let spans = cells.iter().map(|&(_, span)| span).collect();
+        Row {
+            cells: cells.into_iter().map(|(cell, _)| cell).collect(),
+            spans: spans,
+            banner: false,
+            id: None,
+        }
+    }
+
+    /// Create a banner row, printed as a single `cell` spanning all the columns of the table.
+    /// Banner rows are not taken into account when computing column widths
+    pub fn banner(cell: Cell) -> Row {
+        Row {
+            cells: vec![cell],
+            spans: Vec::new(),
+            banner: true,
+            id: None,
+        }
+    }
+
+    /// Check if this row is a banner row
+    pub fn is_banner(&self) -> bool {
+        self.banner
+    }
+
+    /// Check if cells of this row may span over several columns,
+    /// which is the case for banner rows and rows created with `Row::spanned`
+    pub fn is_spanned(&self) -> bool {
+        self.banner || !self.spans.is_empty()
+    }
+
+    /// Set the identifier of the row, used by a table to find it wherever it is moved.
+    /// Rows are usually identified with `Table::add_row_with_id`. The identifier of a row
+    /// already in a table must not be changed
+    pub fn set_id(&mut self, id: &str) {
+        self.id = Some(id.to_string());
+    }
+
+    /// Get the identifier of the row, if any
+    pub fn get_id(&self) -> Option<&str> {
+        self.id.as_ref().map(|id| &id[..])
     }
 
     /// Create an row of length `size`, with empty strings stored
@@ -99,6 +155,26 @@ impl Row {
         }
     }
 
+    /// Replace each cell of the row with the cell returned by `f`, which is given
+    /// the index of the cell and the cell itself
+    ///
+    /// # Examples
+    /// ```rust
+    /// # #[macro_use] extern crate prettytable;
+    /// # use prettytable::cell::Cell;
+    /// # fn main() {
+    /// let mut row = row!["name", "value"];
+    /// row.map(|_, cell| Cell::new(&cell.get_content().to_uppercase()));
+    /// assert_eq!(row[1].get_content(), "VALUE");
+    /// # }
+    /// ```
+    pub fn map<F: FnMut(usize, &Cell) -> Cell>(&mut self, mut f: F) {
+        for (i, cell) in self.cells.iter_mut().enumerate() {
+            let new = f(i, cell);
+            *cell = new;
+        }
+    }
+
     /// Returns an immutable iterator over cells
     pub fn iter(&self) -> Iter<Cell> {
         self.cells.iter()
@@ -109,6 +185,43 @@ impl Row {
         self.cells.iter_mut()
     }
 
+    /// Internal only. Compute the index and the number of columns spanned by each printed cell,
+    /// merging adjacent cells with identical content if enabled in `format`
+    fn get_layout(&self, format: &TableFormat, columns: usize) -> Vec<(usize, usize)> {
+        if self.banner {
+            return if columns > 0 { vec![(0, columns)] } else { Vec::new() };
+        }
+        let mut layout: Vec<(usize, usize)> = Vec::with_capacity(columns);
+        if !self.spans.is_empty() {
+            let mut j = 0;
+            for (k, &span) in self.spans.iter().enumerate() {
+                if j >= columns {
+                    break;
+                }
+                let span = cmp::min(cmp::max(span, 1), columns - j);
+                layout.push((k, span));
+                j += span;
+            }
+            for k in layout.len()..layout.len() + columns - j {
+                layout.push((k, 1));
+            }
+            return layout;
+        }
+        for j in 0..columns {
+            if format.get_merge_equal_adjacent() && j > 0 {
+                if let (Some(prev), Some(cell)) = (self.get_cell(j - 1), self.get_cell(j)) {
+                    let content = cell.get_content();
+                    if !content.is_empty() && content == prev.get_content() {
+                        layout.last_mut().unwrap().1 += 1;
+                        continue;
+                    }
+                }
+            }
+            layout.push((j, 1));
+        }
+        layout
+    }
+
     /// Internal only
     fn __print<T: Write + ?Sized, F>(&self,
                                      out: &mut T,
@@ -118,28 +231,30 @@ impl Row {
                                      -> Result<(), Error>
         where F: Fn(&Cell, &mut T, usize, usize, bool) -> Result<(), Error>
     {
-        for i in 0..self.get_height() {
-            //TODO: Wrap this into dedicated function one day
-            out.write_all(&vec![b' '; format.get_indent()])?;
-            format.print_column_separator(out, ColumnPosition::Left)?;
-            let (lp, rp) = format.get_padding();
-            for j in 0..col_width.len() {
-                out.write_all(&vec![b' '; lp])?;
-                let skip_r_fill = (j == col_width.len() - 1) &&
-                                  format.get_column_separator(ColumnPosition::Right).is_none();
-                match self.get_cell(j) {
-                    Some(c) => f(c, out, i, col_width[j], skip_r_fill)?,
-                    None => f(&Cell::default(), out, i, col_width[j], skip_r_fill)?,
-                };
-                out.write_all(&vec![b' '; rp])?;
-                if j < col_width.len() - 1 {
-                    format.print_column_separator(out, ColumnPosition::Intern)?;
+        let height = self.get_height();
+        let layout = self.get_layout(format, col_width.len());
+        let spans: Vec<usize> = layout.iter().map(|&(_, span)| span).collect();
+        print_spanned_row_lines(out, format, col_width, &spans, height, |out, i, k, width, skip| {
+            match self.get_cell(layout[k].0) {
+                Some(c) => {
+                    // Lines above the content are printed as empty lines
+                    let offset = c.get_valign().offset(c.get_height(), height);
+                    let idx = if i >= offset { i - offset } else { c.get_height() };
+                    let led = with_leader(c, format, k + 1 == layout.len());
+                    let c = led.as_ref().unwrap_or(c);
+                    let shifted = right_biased(c, format, idx, width);
+                    let (c, idx) = match shifted {
+                        Some(ref shifted) => (shifted, 0),
+                        None => (c, idx),
+                    };
+                    match with_ambiguous_width(c, format) {
+                        Some(ref measured) => f(measured, out, idx, width, skip),
+                        None => f(c, out, idx, width, skip),
+                    }
                 }
+                None => f(&Cell::default(), out, i, width, skip),
             }
-            format.print_column_separator(out, ColumnPosition::Right)?;
-            out.write_all(NEWLINE)?;
-        }
-        Ok(())
+        })
     }
 
     /// Print the row to `out`, with `separator` as column separator, and `col_width`
@@ -153,16 +268,85 @@ impl Row {
     }
 
     /// Print the row to terminal `out`, with `separator` as column separator, and `col_width`
-    /// specifying the width of each columns. Apply style when needed
+    /// specifying the width of each columns. Apply style when needed, with the color overrides
+    /// of `format`
     pub fn print_term<T: Terminal + ?Sized>(&self,
                                             out: &mut T,
                                             format: &TableFormat,
                                             col_width: &[usize])
                                             -> Result<(), Error> {
+        if format.has_color_overrides() {
+            let mut row = self.clone();
+            for cell in row.iter_mut() {
+                cell.override_colors(|c| format.get_color_override(c));
+            }
+            return row.__print(out, format, col_width, Cell::print_term);
+        }
         self.__print(out, format, col_width, Cell::print_term)
     }
 }
 
+/// Internal only. If `format` has a leader, return a copy of `cell` filled with it,
+/// and aligned to the right if it is printed in the last column
+fn with_leader(cell: &Cell, format: &TableFormat, last: bool) -> Option<Cell> {
+    let leader = match format.get_leader() {
+        Some(leader) => leader,
+        None => return None,
+    };
+    let mut cell = cell.clone();
+    if cell.get_fill() == ' ' {
+        cell.set_fill(leader);
+    }
+    if last {
+        cell.align(Alignment::RIGHT);
+    }
+    Some(cell)
+}
+
+/// Internal only. If `cell` does not measure East Asian ambiguous characters like `format`,
+/// return a copy of `cell` measuring them like `format`
+fn with_ambiguous_width(cell: &Cell, format: &TableFormat) -> Option<Cell> {
+    let width = format.get_ambiguous_width();
+    if cell.get_ambiguous_width() == width {
+        return None;
+    }
+    let mut cell = cell.clone();
+    cell.set_ambiguous_width(width);
+    Some(cell)
+}
+
+/// Internal only. If line `idx` of centered cell `cell` must be shifted to the right to be
+/// centered in `width` according to the center bias of `format`, return a single line copy
+/// of the cell with a leading space, which is centered exactly
+fn right_biased(cell: &Cell, format: &TableFormat, idx: usize, width: usize) -> Option<Cell> {
+    if format.get_center_bias() != CenterBias::Right ||
+       cell.get_line_alignment(idx) != Alignment::CENTER {
+        return None;
+    }
+    let content = cell.get_content();
+    let line = match content.lines().nth(idx) {
+        Some(line) => line,
+        None => return None,
+    };
+    let text_width = line_width_with(line, format.get_ambiguous_width());
+    if text_width >= width || (width - text_width) % 2 == 0 {
+        return None;
+    }
+    let mut shifted = match cell.get_styled_line(idx) {
+        Some(spans) => {
+            let mut text = StyledText::new().with(" ", Vec::new());
+            for span in spans {
+                text.push(span.get_text(), span.get_style().to_vec());
+            }
+            Cell::new_styled(&text)
+        }
+        None => Cell::new(&format!(" {}", line)),
+    };
+    shifted.align(Alignment::CENTER);
+    shifted.inherit_style(cell);
+    Some(shifted)
+}
+
 impl Default for Row {
     fn default() -> Row {
         Row::empty()
@@ -253,6 +437,9 @@ macro_rules! row {
 mod tests {
     use super::*;
     use cell::Cell;
+    use format::VerticalAlignment;
+    use format::consts::FORMAT_CLEAN;
+    use utils::StringWriter;
 
     #[test]
     fn row_default_empty() {
@@ -303,4 +490,50 @@ mod tests {
         assert_eq!(row.get_cell(0).unwrap().get_content(), "foo");
         assert_eq!(row.get_cell(1).unwrap().get_content(), "foobar");
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn vertical_alignment() {
+        let mut middle = Cell::new("m");
+        middle.valign(VerticalAlignment::Middle);
+        let mut bottom = Cell::new("b");
+        bottom.valign(VerticalAlignment::Bottom);
+        let row = Row::new(vec![Cell::new("1\n2\n3"), Cell::new("t"), middle, bottom]);
+        let mut out = StringWriter::new();
+        row.print(&mut out, &*FORMAT_CLEAN, &[1, 1, 1, 1]).unwrap();
+        let expected = " 1  t      \n 2     m   \n 3        b \n";
+        assert_eq!(out.as_string().replace("\r\n", "\n"), expected);
+    }
+
+    #[test]
+    fn merge_equal_adjacent() {
+        let mut format = *FORMAT_CLEAN;
+        format.column_separator('|');
+        format.merge_equal_adjacent(true);
+        let row = Row::from(vec!["a", "a", "a", "b", "", ""]);
+        let mut out = StringWriter::new();
+        row.print(&mut out, &format, &[1, 1, 1, 1, 1, 1]).unwrap();
+        assert_eq!(out.as_string().replace("\r\n", "\n"), " a         | b |   |  \n");
+    }
+
+    #[test]
+    fn banner() {
+        let mut format = *FORMAT_CLEAN;
+        format.column_separator('|');
+        let row = Row::banner(Cell::new("foo").style_spec("c"));
+        assert!(row.is_banner());
+        let mut out = StringWriter::new();
+        row.print(&mut out, &format, &[1, 2, 3]).unwrap();
+        assert_eq!(out.as_string().replace("\r\n", "\n"), "     foo \n");
+    }
+
+    #[test]
+    fn spanned() {
+        let mut format = *FORMAT_CLEAN;
+        format.column_separator('|');
+        let row = Row::spanned(vec![(Cell::new("a"), 2), (Cell::new("b"), 1)]);
+        assert!(row.is_spanned());
+        let mut out = StringWriter::new();
+        row.print(&mut out, &format, &[1, 1, 1, 1]).unwrap();
+        assert_eq!(out.as_string().replace("\r\n", "\n"), " a     | b |  \n");
+    }
+}
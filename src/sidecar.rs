@@ -0,0 +1,477 @@
+//! This module contains a JSON sidecar describing the titles and styles of a table exported
+//! to CSV, so that the styled table can be reconstructed when importing the CSV back
+use std::io::{Read, Write};
+
+use csv;
+
+use super::{Table, TableSlice};
+use super::row::Row;
+use super::cell::Cell;
+use super::import::ImportError;
+
+/// Style specifiers of the cells of a table, as written in JSON alongside its CSV export.
+/// Specifiers are the ones returned by `Cell::get_style_spec`, including the alignment
+///
+/// # Examples
+/// ```rust
+/// use prettytable::sidecar::Sidecar;
+///
+/// let sidecar = Sidecar::from_json(r#"{"titles": ["b"], "rows": [["Frc"]]}"#).unwrap();
+/// assert_eq!(sidecar.get_titles(), Some(&["b".to_string()][..]));
+/// assert_eq!(sidecar.get_rows()[0][0], "Frc");
+/// assert!(Sidecar::from_json(r#"{"rows": [1]}"#).is_err());
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Sidecar {
+    /// Style specifiers of the titles, if the table has titles
+    titles: Option<Vec<String>>,
+    /// Style specifiers of the cells of each row
+    rows: Vec<Vec<String>>,
+}
+
+impl Sidecar {
+    /// Describe the titles and the style of the cells of `table`
+    pub fn new(table: &TableSlice) -> Sidecar {
+        let specs = |row: &Row| -> Vec<String> { row.iter().map(Cell::get_style_spec).collect() };
+        Sidecar {
+            titles: table.titles.as_ref().map(&specs),
+            rows: table.rows.iter().map(&specs).collect(),
+        }
+    }
+
+    /// Get the style specifiers of the titles, if the table has titles
+    pub fn get_titles(&self) -> Option<&[String]> {
+        self.titles.as_ref().map(|t| &t[..])
+    }
+
+    /// Get the style specifiers of the cells of each row
+    pub fn get_rows(&self) -> &[Vec<String>] {
+        &self.rows
+    }
+
+    /// Apply the style specifiers to the titles and cells of `table`.
+    /// Cells without a specifier are left unchanged. Fail with `ImportError::InvalidSidecar`
+    /// on the first invalid specifier, leaving the cells styled so far unchanged
+    pub fn apply_to(&self, table: &mut Table) -> Result<(), ImportError> {
+        let apply = |row: &mut Row, specs: &[String], name: &str| -> Result<(), ImportError> {
+            for (j, (cell, spec)) in row.iter_mut().zip(specs).enumerate() {
+                *cell = cell.clone()
+                    .try_style_spec(spec)
+                    .map_err(|e| invalid(&format!("{}, column {}: {}", name, j, e)))?;
+            }
+            Ok(())
+        };
+        if let (&mut Some(ref mut titles), Some(specs)) = (&mut table.titles, self.get_titles()) {
+            apply(titles, specs, "titles")?;
+        }
+        for (i, (row, specs)) in table.rows.iter_mut().zip(&self.rows).enumerate() {
+            apply(row, specs, &format!("row {}", i))?;
+        }
+        Ok(())
+    }
+
+    /// Write the sidecar as a JSON object, with a `titles` member which is `null` when
+    /// the table has no titles, and a `rows` member
+    pub fn to_json(&self) -> String {
+        let array = |specs: &[String]| {
+            let specs: Vec<String> = specs.iter().map(|s| json_string(s)).collect();
+            format!("[{}]", specs.join(", "))
+        };
+        let titles = self.get_titles().map_or("null".to_string(), &array);
+        let rows: Vec<String> = self.rows.iter().map(|r| format!("    {}", array(r))).collect();
+        if rows.is_empty() {
+            return format!("{{\n  \"titles\": {},\n  \"rows\": []\n}}\n", titles);
+        }
+        format!("{{\n  \"titles\": {},\n  \"rows\": [\n{}\n  ]\n}}\n", titles, rows.join(",\n"))
+    }
+
+    /// Read a sidecar from JSON. Unknown members are ignored, and a missing `titles`
+    /// member is the same as `null`
+    pub fn from_json(json: &str) -> Result<Sidecar, ImportError> {
+        let mut parser = Parser {
+            text: json,
+            pos: 0,
+            depth: 0,
+        };
+        let value = parser.value()?;
+        parser.skip_whitespace();
+        if parser.pos < json.len() {
+            return parser.error();
+        }
+        let members = match value {
+            Json::Object(members) => members,
+            _ => return Err(invalid("expected an object")),
+        };
+        let mut sidecar = Sidecar::default();
+        for (name, value) in members {
+            match (&name[..], value) {
+                ("titles", Json::Null) => sidecar.titles = None,
+                ("titles", value) => {
+                    let titles = strings(value);
+                    sidecar.titles = Some(titles.ok_or_else(|| invalid("invalid titles"))?);
+                }
+                ("rows", Json::Array(rows)) => {
+                    sidecar.rows = Vec::with_capacity(rows.len());
+                    for row in rows {
+                        sidecar.rows.push(strings(row).ok_or_else(|| invalid("invalid row"))?);
+                    }
+                }
+                ("rows", _) => return Err(invalid("rows must be an array")),
+                _ => (),
+            }
+        }
+        Ok(sidecar)
+    }
+}
+
+impl<'a> TableSlice<'a> {
+    /// Write the table to `csv` like `to_csv`, titles included, and its sidecar describing
+    /// the titles and the style of the cells as JSON to `sidecar`. Computed columns, column
+    /// styles and the cell formatter are written as their current result, see `materialize`.
+    /// Use `Table::from_csv_with_sidecar` to read both back
+    pub fn to_csv_with_sidecar<W: Write, S: Write>(&self,
+                                                  csv: W,
+                                                  mut sidecar: S)
+                                                  -> csv::Result<csv::Writer<W>> {
+        let table = self.materialize();
+        let writer = table.to_csv(csv)?;
+        sidecar.write_all(Sidecar::new(&table.as_slice()).to_json().as_bytes())?;
+        sidecar.flush()?;
+        Ok(writer)
+    }
+}
+
+impl Table {
+    /// Write the table to `csv`, and its sidecar describing the titles and the style of
+    /// the cells to `sidecar`. See `TableSlice::to_csv_with_sidecar` for details
+    pub fn to_csv_with_sidecar<W: Write, S: Write>(&self,
+                                                  csv: W,
+                                                  sidecar: S)
+                                                  -> csv::Result<csv::Writer<W>> {
+        self.as_slice().to_csv_with_sidecar(csv, sidecar)
+    }
+
+    /// Create a table from a CSV source and its JSON sidecar written by `to_csv_with_sidecar`,
+    /// restoring the titles and the style of the cells
+    ///
+    /// # Examples
+    /// ```rust
+    /// # #[macro_use] extern crate prettytable;
+    /// use prettytable::Table;
+    /// # fn main() {
+    /// let table = table!([Fgb->"ok", r->"12"]);
+    /// let (mut csv, mut sidecar) = (Vec::new(), Vec::new());
+    /// table.to_csv_with_sidecar(&mut csv, &mut sidecar).unwrap();
+    /// let copy = Table::from_csv_with_sidecar(&csv[..], &sidecar[..]).unwrap();
+    /// assert_eq!(copy[0][1].get_style_spec(), "r");
+    /// assert_eq!(copy.render_spans(), table.render_spans());
+    /// # }
+    /// ```
+    pub fn from_csv_with_sidecar<R: Read, S: Read>(csv: R,
+                                                   mut sidecar: S)
+                                                   -> Result<Table, ImportError> {
+        let mut json = String::new();
+        sidecar.read_to_string(&mut json).map_err(csv::Error::from)?;
+        let sidecar = Sidecar::from_json(&json)?;
+        let mut reader = csv::ReaderBuilder::new()
+            .has_headers(sidecar.titles.is_some())
+            .flexible(true)
+            .from_reader(csv);
+        let mut table = Table::from_csv(&mut reader)?;
+        sidecar.apply_to(&mut table)?;
+        Ok(table)
+    }
+}
+
+/// Internal only. Build an error about an invalid sidecar
+fn invalid(message: &str) -> ImportError {
+    ImportError::InvalidSidecar(message.to_string())
+}
+
+/// Internal only. Quote `text` as a JSON string
+fn json_string(text: &str) -> String {
+    let mut quoted = String::with_capacity(text.len() + 2);
+    quoted.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if c < ' ' => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// Internal only. A parsed JSON value. Numbers are not kept since a sidecar has none
+enum Json {
+    Null,
+    Bool,
+    Number,
+    String(String),
+    Array(Vec<Json>),
+    Object(Vec<(String, Json)>),
+}
+
+/// Internal only. Get the strings of a JSON array of strings
+fn strings(value: Json) -> Option<Vec<String>> {
+    let values = match value {
+        Json::Array(values) => values,
+        _ => return None,
+    };
+    let mut strings = Vec::with_capacity(values.len());
+    for value in values {
+        match value {
+            Json::String(s) => strings.push(s),
+            _ => return None,
+        }
+    }
+    Some(strings)
+}
+
+/// Internal only. Maximum nesting of JSON arrays and objects, so that deeply nested input
+/// fails instead of overflowing the stack
+const MAX_DEPTH: usize = 128;
+
+/// Internal only. A minimal JSON parser, reading `text` from byte offset `pos`
+struct Parser<'s> {
+    text: &'s str,
+    pos: usize,
+    /// Number of arrays and objects being parsed
+    depth: usize,
+}
+
+impl<'s> Parser<'s> {
+    /// Fail at the current position
+    fn error<T>(&self) -> Result<T, ImportError> {
+        Err(ImportError::InvalidSidecar(format!("invalid JSON at byte {}", self.pos)))
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.text[self.pos..].chars().next()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(c) = self.peek() {
+            if c != ' ' && c != '\t' && c != '\n' && c != '\r' {
+                break;
+            }
+            self.pos += 1;
+        }
+    }
+
+    /// Skip `token` if the text continues with it, and return whether it has been skipped
+    fn eat(&mut self, token: &str) -> bool {
+        self.skip_whitespace();
+        let found = self.text[self.pos..].starts_with(token);
+        if found {
+            self.pos += token.len();
+        }
+        found
+    }
+
+    fn value(&mut self) -> Result<Json, ImportError> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some('{') => self.nested(Parser::object),
+            Some('[') => self.nested(Parser::array),
+            Some('"') => self.string().map(Json::String),
+            Some(c) if c == '-' || c.is_digit(10) => {
+                while let Some(c) = self.peek() {
+                    if !c.is_digit(10) && !"+-.eE".contains(c) {
+                        break;
+                    }
+                    self.pos += 1;
+                }
+                Ok(Json::Number)
+            }
+            _ if self.eat("null") => Ok(Json::Null),
+            _ if self.eat("true") || self.eat("false") => Ok(Json::Bool),
+            _ => self.error(),
+        }
+    }
+
+    /// Parse an array or an object with `parse`, unless nesting is too deep
+    fn nested<F>(&mut self, parse: F) -> Result<Json, ImportError>
+        where F: FnOnce(&mut Parser<'s>) -> Result<Json, ImportError>
+    {
+        if self.depth == MAX_DEPTH {
+            return Err(invalid(&format!("JSON nested too deeply at byte {}", self.pos)));
+        }
+        self.depth += 1;
+        let value = parse(self);
+        self.depth -= 1;
+        value
+    }
+
+    fn array(&mut self) -> Result<Json, ImportError> {
+        self.pos += 1;
+        let mut values = Vec::new();
+        if self.eat("]") {
+            return Ok(Json::Array(values));
+        }
+        loop {
+            values.push(self.value()?);
+            if self.eat("]") {
+                return Ok(Json::Array(values));
+            }
+            if !self.eat(",") {
+                return self.error();
+            }
+        }
+    }
+
+    fn object(&mut self) -> Result<Json, ImportError> {
+        self.pos += 1;
+        let mut members = Vec::new();
+        if self.eat("}") {
+            return Ok(Json::Object(members));
+        }
+        loop {
+            self.skip_whitespace();
+            if self.peek() != Some('"') {
+                return self.error();
+            }
+            let name = self.string()?;
+            if !self.eat(":") {
+                return self.error();
+            }
+            members.push((name, self.value()?));
+            if self.eat("}") {
+                return Ok(Json::Object(members));
+            }
+            if !self.eat(",") {
+                return self.error();
+            }
+        }
+    }
+
+    fn string(&mut self) -> Result<String, ImportError> {
+        self.pos += 1;
+        let mut s = String::new();
+        loop {
+            let c = match self.peek() {
+                Some(c) => c,
+                None => return self.error(),
+            };
+            self.pos += c.len_utf8();
+            match c {
+                '"' => return Ok(s),
+                '\\' => {
+                    let escaped = match self.peek() {
+                        Some(c) => c,
+                        None => return self.error(),
+                    };
+                    self.pos += escaped.len_utf8();
+                    match escaped {
+                        '"' | '\\' | '/' => s.push(escaped),
+                        'b' => s.push('\u{8}'),
+                        'f' => s.push('\u{c}'),
+                        'n' => s.push('\n'),
+                        'r' => s.push('\r'),
+                        't' => s.push('\t'),
+                        'u' => s.push(self.unicode_escape()?),
+                        _ => return self.error(),
+                    }
+                }
+                c if c < ' ' => return self.error(),
+                c => s.push(c),
+            }
+        }
+    }
+
+    /// Read the code point of a `\u` escape, combining surrogate pairs
+    fn unicode_escape(&mut self) -> Result<char, ImportError> {
+        let high = self.hex4()?;
+        let code = if high >= 0xD800 && high < 0xDC00 && self.text[self.pos..].starts_with("\\u") {
+            self.pos += 2;
+            let low = self.hex4()?;
+            if low < 0xDC00 || low >= 0xE000 {
+                return self.error();
+            }
+            0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00)
+        } else {
+            high
+        };
+        match ::std::char::from_u32(code) {
+            Some(c) => Ok(c),
+            None => self.error(),
+        }
+    }
+
+    fn hex4(&mut self) -> Result<u32, ImportError> {
+        let digits = self.text[self.pos..].chars().take(4).collect::<String>();
+        if digits.len() != 4 || !digits.chars().all(|d| d.is_digit(16)) {
+            return self.error();
+        }
+        self.pos += 4;
+        Ok(u32::from_str_radix(&digits, 16).unwrap_or(0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn json() {
+        let mut table = Table::new();
+        table.set_titles(Row::new(vec![Cell::new("a").style_spec("bc"), Cell::new("b")]));
+        table.add_row(Row::new(vec![Cell::new("1").style_spec("Fr\"B{200}"), Cell::new("2")]));
+        let sidecar = Sidecar::new(&table.as_slice());
+        let json = sidecar.to_json();
+        assert_eq!(json,
+                   "{\n  \"titles\": [\"bc\", \"\"],\n  \"rows\": [\n    \
+                    [\"FrB{200}\", \"\"]\n  ]\n}\n");
+        assert_eq!(Sidecar::from_json(&json).unwrap(), sidecar);
+        assert_eq!(Sidecar::new(&Table::new().as_slice()).to_json(),
+                   "{\n  \"titles\": null,\n  \"rows\": []\n}\n");
+
+        let json = r#" { "version": 1.5e3, "extra": [true, {}, null],
+                         "rows": [["\u0046r\"\ud83d\ude00"]] } "#;
+        let sidecar = Sidecar::from_json(json).unwrap();
+        assert_eq!(sidecar.get_titles(), None);
+        assert_eq!(sidecar.get_rows()[0][0], "Fr\"\u{1F600}");
+        assert_eq!(json_string("a\"\\\n\u{1}"), "\"a\\\"\\\\\\n\\u0001\"");
+        for invalid in &["", "[]", "{\"rows\": [[1]]}", "{\"titles\": 2}", "{} x", "{\"a\" 1}",
+                         "{\"a\": \"\\x\"}", "{\"a\": \"\\ud800\\u0041\"}"] {
+            assert!(Sidecar::from_json(invalid).is_err(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut table = Table::new();
+        table.set_titles(Row::new(vec![Cell::new("name").style_spec("bc"),
+                                       Cell::new("qty").style_spec("r")]));
+        table.add_row(Row::new(vec![Cell::new("a, b\nc"),
+                                    Cell::new("1").style_spec("FgB#102030")]));
+        table.add_row(Row::new(vec![Cell::new("d")]));
+        table.set_column_style(0, ::term::Attr::Italic(true));
+        let (mut csv, mut json) = (Vec::new(), Vec::new());
+        table.to_csv_with_sidecar(&mut csv, &mut json).unwrap();
+        let copy = Table::from_csv_with_sidecar(&csv[..], &json[..]).unwrap();
+        assert_eq!(copy.render_spans(), table.render_spans());
+        assert_eq!(copy[0][0].get_style_spec(), "i");
+
+        let err = Table::from_csv_with_sidecar(&csv[..], &b"{"[..]).unwrap_err();
+        assert_eq!(err.to_string(), "invalid sidecar: invalid JSON at byte 1");
+        let json = br#"{"titles": ["b", "Fz"], "rows": []}"#;
+        let err = Table::from_csv_with_sidecar(&csv[..], &json[..]).unwrap_err();
+        assert_eq!(err.to_string(),
+                   "invalid sidecar: titles, column 1: unknown color specifier 'z' at position 1");
+    }
+
+    #[test]
+    fn nesting_limit() {
+        let repeat = |c, n| ::std::iter::repeat(c).take(n).collect::<String>();
+        let err = Sidecar::from_json(&repeat('[', 200_000)).unwrap_err();
+        assert_eq!(err.to_string(), "invalid sidecar: JSON nested too deeply at byte 128");
+        let json = format!("{{\"extra\": {}{}, \"rows\": []}}", repeat('[', 100), repeat(']', 100));
+        assert!(Sidecar::from_json(&json).is_ok());
+    }
+}
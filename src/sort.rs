@@ -0,0 +1,237 @@
+//! This module contains utilities to sort the rows of a table
+use std::cmp::Ordering;
+use std::iter::Peekable;
+use std::str::Chars;
+
+use super::Table;
+use super::row::Row;
+
+/// Order of sorted rows
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Order {
+    /// Smallest values first
+    Ascending,
+    /// Greatest values first
+    Descending,
+}
+
+impl Default for Order {
+    fn default() -> Order {
+        Order::Ascending
+    }
+}
+
+/// Rules used to compare cell content when sorting rows
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Collation {
+    /// Compare content ignoring case
+    case_insensitive: bool,
+    /// Compare runs of digits by their numeric value
+    numeric: bool,
+    /// Order of sorted rows
+    order: Order,
+}
+
+impl Collation {
+    /// Create a new `Collation`, comparing content character by character
+    pub fn new() -> Collation {
+        Collation::default()
+    }
+
+    /// Set whether content is compared ignoring case. Can be chained
+    pub fn case_insensitive(mut self, case_insensitive: bool) -> Collation {
+        self.case_insensitive = case_insensitive;
+        self
+    }
+
+    /// Set whether runs of digits are compared by their numeric value, so that
+    /// `"item2"` comes before `"item10"`. Can be chained
+    pub fn numeric(mut self, numeric: bool) -> Collation {
+        self.numeric = numeric;
+        self
+    }
+
+    /// Set the order of sorted rows. Default is `Order::Ascending`. Can be chained
+    pub fn order(mut self, order: Order) -> Collation {
+        self.order = order;
+        self
+    }
+
+    /// Compare `a` and `b` according to this collation. The result is reversed when the order
+    /// is `Order::Descending`
+    pub fn compare(&self, a: &str, b: &str) -> Ordering {
+        if self.case_insensitive {
+            self.compare_keys(&a.to_lowercase(), &b.to_lowercase())
+        } else {
+            self.compare_keys(a, b)
+        }
+    }
+
+    /// Internal only. Get the key of `content` compared by `compare_keys`
+    fn key(&self, content: String) -> String {
+        if self.case_insensitive {
+            content.to_lowercase()
+        } else {
+            content
+        }
+    }
+
+    /// Internal only. Compare the keys `a` and `b` according to this collation, taking the
+    /// order into account
+    fn compare_keys(&self, a: &str, b: &str) -> Ordering {
+        let ord = self.compare_keys_ascending(a, b);
+        match self.order {
+            Order::Ascending => ord,
+            Order::Descending => ord.reverse(),
+        }
+    }
+
+    /// Internal only. Compare the keys `a` and `b`, in ascending order
+    fn compare_keys_ascending(&self, a: &str, b: &str) -> Ordering {
+        if !self.numeric {
+            return a.cmp(b);
+        }
+        let (mut a, mut b) = (a.chars().peekable(), b.chars().peekable());
+        loop {
+            let ord = match (a.peek().cloned(), b.peek().cloned()) {
+                (None, None) => return Ordering::Equal,
+                (None, Some(_)) => return Ordering::Less,
+                (Some(_), None) => return Ordering::Greater,
+                (Some(x), Some(y)) if x.is_digit(10) && y.is_digit(10) => {
+                    compare_numbers(&take_digits(&mut a), &take_digits(&mut b))
+                }
+                (Some(x), Some(y)) => {
+                    a.next();
+                    b.next();
+                    x.cmp(&y)
+                }
+            };
+            if ord != Ordering::Equal {
+                return ord;
+            }
+        }
+    }
+}
+
+impl From<Order> for Collation {
+    fn from(order: Order) -> Collation {
+        Collation::new().order(order)
+    }
+}
+
+impl<'a> From<&'a Collation> for Collation {
+    fn from(collation: &'a Collation) -> Collation {
+        *collation
+    }
+}
+
+/// Consume and return the run of digits at the start of `chars`
+fn take_digits(chars: &mut Peekable<Chars>) -> String {
+    let mut digits = String::new();
+    while let Some(&c) = chars.peek() {
+        if !c.is_digit(10) {
+            break;
+        }
+        digits.push(c);
+        chars.next();
+    }
+    digits
+}
+
+/// Compare two runs of digits by their numeric value, without overflowing on long runs.
+/// Runs with the same value but more leading zeros come last
+fn compare_numbers(a: &str, b: &str) -> Ordering {
+    let trim = |s: &str| s.find(|c| c != '0').map_or(String::new(), |i| s[i..].to_string());
+    let (ta, tb) = (trim(a), trim(b));
+    match ta.len().cmp(&tb.len()) {
+        Ordering::Equal => {}
+        ord => return ord,
+    }
+    match ta.cmp(&tb) {
+        Ordering::Equal => a.len().cmp(&b.len()),
+        ord => ord,
+    }
+}
+
+impl Table {
+    /// Sort the rows by the content of the cell in `column`, compared according to `collation`,
+    /// which can also be given as a `&Collation` or as an `Order`.
+    /// The sort is stable, and rows without a cell in `column` are considered empty.
+    /// Titles are not sorted
+    ///
+    /// # Examples
+    /// ```rust
+    /// # #[macro_use] extern crate prettytable;
+    /// use prettytable::sort::{Collation, Order};
+    /// # fn main() {
+    /// let mut table = table![["item10"], ["Item2"], ["item1"]];
+    /// table.sort_by_column(0, &Collation::new().case_insensitive(true).numeric(true));
+    /// assert_eq!(table[1][0].get_content(), "Item2");
+    /// table.sort_by_column(0, Order::Descending);
+    /// assert_eq!(table[0][0].get_content(), "item10");
+    /// # }
+    /// ```
+    pub fn sort_by_column<C: Into<Collation>>(&mut self, column: usize, collation: C) {
+        let collation = collation.into();
+        // Get the key of each row once, instead of once per comparison
+        let mut keyed: Vec<(String, Row)> = self.rows
+            .drain(..)
+            .map(|r| {
+                let content = r.get_cell(column).map_or(String::new(), |c| c.get_content());
+                (collation.key(content), r)
+            })
+            .collect();
+        keyed.sort_by(|a, b| collation.compare_keys(&a.0, &b.0));
+        self.rows.extend(keyed.into_iter().map(|(_, r)| r));
+    }
+
+    /// Sort the rows with the comparison function `compare`. The sort is stable, and
+    /// titles are not sorted
+    pub fn sort_by<F>(&mut self, compare: F)
+        where F: FnMut(&Row, &Row) -> Ordering
+    {
+        self.rows.sort_by(compare);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cmp::Ordering::*;
+
+    #[test]
+    fn compare() {
+        let plain = Collation::new();
+        assert_eq!(plain.compare("B", "a"), Less);
+        assert_eq!(plain.compare("a10", "a9"), Less);
+        let collation = Collation::new().case_insensitive(true);
+        assert_eq!(collation.compare("B", "a"), Greater);
+        assert_eq!(collation.compare("ABC", "abc"), Equal);
+        let collation = Collation::new().numeric(true);
+        assert_eq!(collation.compare("a10", "a9"), Greater);
+        assert_eq!(collation.compare("a10b", "a10c"), Less);
+        assert_eq!(collation.compare("a007", "a7"), Greater);
+        assert_eq!(collation.compare("99999999999999999999999", "1"), Greater);
+        assert_eq!(collation.compare("a", "a1"), Less);
+        let collation = collation.order(Order::Descending);
+        assert_eq!(collation.compare("a10", "a9"), Less);
+        assert_eq!(collation.compare("a", "a"), Equal);
+    }
+
+    #[test]
+    fn sort_rows() {
+        let mut table = Table::new();
+        table.set_titles(Row::from(vec!["name", "qty"]));
+        for &(name, qty) in &[("b", "10"), ("a", "9"), ("c", "10")] {
+            table.add_row(Row::from(vec![name, qty]));
+        }
+        table.sort_by_column(1, &Collation::new().numeric(true).order(Order::Descending));
+        let names: Vec<String> = table.column_iter(0).map(|c| c.get_content()).collect();
+        assert_eq!(names, vec!["b", "c", "a"]);
+        table.sort_by_column(0, Order::Descending);
+        assert_eq!(table[0][0].get_content(), "c");
+        table.sort_by(|a, b| a[1].get_content().len().cmp(&b[1].get_content().len()));
+        assert_eq!(table[0][0].get_content(), "a");
+        assert_eq!(table.titles.as_ref().as_ref().unwrap()[0].get_content(), "name");
+    }
+}
@@ -0,0 +1,472 @@
+//! This module contains definition of styled spans, used to render tables without ANSI escape sequences,
+//! and of styled text, used to mix several styles in a single cell
+use std::fmt;
+use std::io::{Write, Error};
+
+use term::{self, Attr, Terminal, color};
+
+/// A fragment of rendered text, with the style attributes to apply to it
+#[derive(Clone, Debug, PartialEq)]
+pub struct StyledSpan {
+    text: String,
+    style: Vec<Attr>,
+}
+
+impl StyledSpan {
+    /// Create a new span containing `text` rendered with `style`
+    pub fn new(text: &str, style: Vec<Attr>) -> StyledSpan {
+        StyledSpan {
+            text: text.to_string(),
+            style: style,
+        }
+    }
+
+    /// Get the text of this span
+    pub fn get_text(&self) -> &str {
+        &self.text
+    }
+
+    /// Get the style attributes to apply to this span
+    pub fn get_style(&self) -> &[Attr] {
+        &self.style
+    }
+}
+
+/// Rich text made of spans with their own style, displayed in a cell with `Cell::new_styled`.
+/// Styles of spans are applied on top of the style of the cell
+///
+/// # Examples
+/// ```rust
+/// # extern crate prettytable;
+/// # extern crate term;
+/// # fn main() {
+/// use prettytable::cell::Cell;
+/// use prettytable::span::StyledText;
+/// use term::{Attr, color};
+///
+/// let text = StyledText::new()
+///     .with("3 passed", vec![Attr::ForegroundColor(color::GREEN)])
+///     .with(", ", vec![])
+///     .with("1 failed", vec![Attr::ForegroundColor(color::RED), Attr::Bold]);
+/// let cell = Cell::new_styled(&text);
+/// assert_eq!(cell.get_content(), "3 passed, 1 failed");
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct StyledText {
+    spans: Vec<StyledSpan>,
+}
+
+impl StyledText {
+    /// Create a new empty text
+    pub fn new() -> StyledText {
+        StyledText { spans: Vec::new() }
+    }
+
+    /// Append `text` rendered with `style`
+    pub fn push(&mut self, text: &str, style: Vec<Attr>) {
+        self.spans.push(StyledSpan::new(text, style));
+    }
+
+    /// Append `text` rendered with `style`. Can be chained
+    pub fn with(mut self, text: &str, style: Vec<Attr>) -> StyledText {
+        self.push(text, style);
+        self
+    }
+
+    /// Parse a lightweight markup into styled text. Text between `**` is bold, and text
+    /// following a `{style}` tag is styled until the matching `{/}` tag. Styles are color names
+    /// like `red` or `bright_red` for the foreground, `on_red` for the background, and `bold`,
+    /// `italic`, `underline` or `dim`. A backslash escapes the next character, and unknown tags
+    /// are kept as text
+    ///
+    /// # Examples
+    /// ```rust
+    /// use prettytable::span::StyledText;
+    ///
+    /// let text = StyledText::parse_markup("{green}3 passed{/}, **{red}1 failed{/}**");
+    /// assert_eq!(text.to_string(), "3 passed, 1 failed");
+    /// assert_eq!(text.get_spans().len(), 3);
+    /// ```
+    pub fn parse_markup(markup: &str) -> StyledText {
+        let mut text = StyledText::new();
+        let mut buffer = String::new();
+        let mut stack: Vec<Attr> = Vec::new();
+        let mut bold = false;
+        let mut chars = markup.char_indices();
+        while let Some((i, c)) = chars.next() {
+            match c {
+                '\\' => {
+                    if let Some((_, next)) = chars.next() {
+                        buffer.push(next);
+                    }
+                }
+                '*' if markup[i..].starts_with("**") => {
+                    chars.next();
+                    flush_markup(&mut text, &mut buffer, &stack, bold);
+                    bold = !bold;
+                }
+                '{' => {
+                    let tag = markup[i + 1..].find('}').map(|end| &markup[i + 1..i + 1 + end]);
+                    match (tag, tag.and_then(parse_tag)) {
+                        (Some("/"), _) => {
+                            flush_markup(&mut text, &mut buffer, &stack, bold);
+                            stack.pop();
+                        }
+                        (Some(_), Some(attr)) => {
+                            flush_markup(&mut text, &mut buffer, &stack, bold);
+                            stack.push(attr);
+                        }
+                        _ => {
+                            buffer.push(c);
+                            continue;
+                        }
+                    }
+                    for (_, d) in chars.by_ref() {
+                        if d == '}' {
+                            break;
+                        }
+                    }
+                }
+                _ => buffer.push(c),
+            }
+        }
+        // Unterminated styles still apply up to the end of the text
+        flush_markup(&mut text, &mut buffer, &stack, bold);
+        text
+    }
+
+    /// Get the spans of this text
+    pub fn get_spans(&self) -> &[StyledSpan] {
+        &self.spans
+    }
+
+    /// Split the spans of this text into lines, like `str::lines` does with its content
+    pub fn lines(&self) -> Vec<Vec<StyledSpan>> {
+        let mut lines: Vec<Vec<StyledSpan>> = vec![Vec::new()];
+        for span in &self.spans {
+            for (i, part) in span.text.split('\n').enumerate() {
+                if i > 0 {
+                    if let Some(line) = lines.last_mut() {
+                        if let Some(last) = line.last_mut() {
+                            if last.text.ends_with('\r') {
+                                last.text.pop();
+                            }
+                        }
+                    }
+                    lines.push(Vec::new());
+                }
+                if !part.is_empty() {
+                    if let Some(line) = lines.last_mut() {
+                        line.push(StyledSpan::new(part, span.style.clone()));
+                    }
+                }
+            }
+        }
+        if lines.len() > 1 && lines.last().map_or(false, |l| l.is_empty()) {
+            lines.pop();
+        }
+        lines
+    }
+}
+
+/// Internal only. Move the markup text in `buffer` into a new span of `text`, styled with the
+/// tags in `stack` and in bold if `bold` is set
+fn flush_markup(text: &mut StyledText, buffer: &mut String, stack: &[Attr], bold: bool) {
+    if buffer.is_empty() {
+        return;
+    }
+    let mut style = stack.to_vec();
+    if bold {
+        style.insert(0, Attr::Bold);
+    }
+    text.push(buffer, style);
+    buffer.clear();
+}
+
+/// Internal only. Get the attribute corresponding to a markup tag, or `None` if it is unknown
+fn parse_tag(tag: &str) -> Option<Attr> {
+    let color = |name: &str| {
+        let (bright, name) = if name.starts_with("bright_") {
+            (true, &name[7..])
+        } else {
+            (false, name)
+        };
+        let c = match name {
+            "black" => color::BLACK,
+            "red" => color::RED,
+            "green" => color::GREEN,
+            "yellow" => color::YELLOW,
+            "blue" => color::BLUE,
+            "magenta" => color::MAGENTA,
+            "cyan" => color::CYAN,
+            "white" => color::WHITE,
+            _ => return None,
+        };
+        Some(if bright { c + 8 } else { c })
+    };
+    match tag {
+        "bold" => Some(Attr::Bold),
+        "italic" => Some(Attr::Italic(true)),
+        "underline" => Some(Attr::Underline(true)),
+        "dim" => Some(Attr::Dim),
+        _ if tag.starts_with("on_") => color(&tag[3..]).map(Attr::BackgroundColor),
+        _ => color(tag).map(Attr::ForegroundColor),
+    }
+}
+
+impl fmt::Display for StyledText {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        for span in &self.spans {
+            fmt.write_str(&span.text)?;
+        }
+        Ok(())
+    }
+}
+
+/// Internal only. A terminal recording the text written to it as lines of styled spans
+pub struct SpanRecorder {
+    lines: Vec<Vec<StyledSpan>>,
+    current: Vec<StyledSpan>,
+    buffer: Vec<u8>,
+    style: Vec<Attr>,
+    escape: Escape,
+    colors: Option<color::Color>,
+}
+
+/// Position of a `SpanRecorder` in a raw escape sequence
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Escape {
+    /// Not in an escape sequence
+    None,
+    /// After the escape character
+    Start,
+    /// In a control sequence, ended by a letter
+    Csi,
+    /// In a string, like an OSC or APC sequence, ended by BEL or `ESC \\`
+    Str,
+    /// After an escape character in a string
+    StrEnd,
+}
+
+impl SpanRecorder {
+    /// Create a new empty recorder
+    pub fn new() -> SpanRecorder {
+        SpanRecorder {
+            lines: Vec::new(),
+            current: Vec::new(),
+            buffer: Vec::new(),
+            style: Vec::new(),
+            escape: Escape::None,
+            colors: None,
+        }
+    }
+
+    /// Create a new empty recorder emulating a terminal supporting only the
+    /// first `colors` colors of the palette
+    pub fn with_colors(colors: color::Color) -> SpanRecorder {
+        SpanRecorder { colors: Some(colors), ..SpanRecorder::new() }
+    }
+
+    /// Move buffered text into a new span with the current style,
+    /// or append it to the last span if it has the same style
+    fn flush_span(&mut self) {
+        if self.buffer.is_empty() {
+            return;
+        }
+        let text = String::from_utf8_lossy(&self.buffer).into_owned();
+        self.buffer.clear();
+        if let Some(last) = self.current.last_mut() {
+            if last.style == self.style {
+                last.text.push_str(&text);
+                return;
+            }
+        }
+        self.current.push(StyledSpan {
+                              text: text,
+                              style: self.style.clone(),
+                          });
+    }
+
+    /// Return all the recorded lines. Text written after the last newline is returned as a last line
+    pub fn into_lines(mut self) -> Vec<Vec<StyledSpan>> {
+        self.flush_span();
+        if !self.current.is_empty() {
+            self.lines.push(self.current);
+        }
+        self.lines
+    }
+}
+
+impl Write for SpanRecorder {
+    fn write(&mut self, data: &[u8]) -> Result<usize, Error> {
+        for &b in data {
+            // Raw escape sequences, like truecolor or inline images ones,
+            // cannot be represented in spans
+            let previous = self.escape;
+            self.escape = match (previous, b) {
+                (Escape::None, 0x1b) => Escape::Start,
+                (Escape::None, _) => Escape::None,
+                (Escape::Start, b'[') => Escape::Csi,
+                (Escape::Start, b']') |
+                (Escape::Start, b'_') |
+                (Escape::Start, b'P') => Escape::Str,
+                (Escape::Csi, _) if (b as char).is_alphabetic() => Escape::None,
+                (Escape::Str, 0x07) => Escape::None,
+                (Escape::Str, 0x1b) => Escape::StrEnd,
+                (Escape::StrEnd, b'\\') => Escape::None,
+                (Escape::Csi, _) => Escape::Csi,
+                (Escape::Str, _) |
+                (Escape::StrEnd, _) => Escape::Str,
+                (Escape::Start, _) => Escape::None,
+            };
+            if previous != Escape::None || self.escape != Escape::None {
+                continue;
+            }
+            match b {
+                b'\n' => {
+                    if self.buffer.last() == Some(&b'\r') {
+                        self.buffer.pop();
+                    }
+                    self.flush_span();
+                    let line = ::std::mem::replace(&mut self.current, Vec::new());
+                    self.lines.push(line);
+                }
+                _ => self.buffer.push(b),
+            }
+        }
+        Ok(data.len())
+    }
+
+    fn flush(&mut self) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+impl Terminal for SpanRecorder {
+    type Output = SpanRecorder;
+
+    fn fg(&mut self, color: color::Color) -> term::Result<()> {
+        self.attr(Attr::ForegroundColor(color))
+    }
+
+    fn bg(&mut self, color: color::Color) -> term::Result<()> {
+        self.attr(Attr::BackgroundColor(color))
+    }
+
+    fn attr(&mut self, attr: Attr) -> term::Result<()> {
+        if !self.supports_attr(attr) {
+            return Err(term::Error::ColorOutOfRange);
+        }
+        self.flush_span();
+        self.style.push(attr);
+        Ok(())
+    }
+
+    fn supports_attr(&self, attr: Attr) -> bool {
+        match (attr, self.colors) {
+            (Attr::ForegroundColor(c), Some(n)) |
+            (Attr::BackgroundColor(c), Some(n)) => c < n,
+            _ => true,
+        }
+    }
+
+    fn reset(&mut self) -> term::Result<()> {
+        self.flush_span();
+        self.style.clear();
+        Ok(())
+    }
+
+    fn supports_reset(&self) -> bool {
+        true
+    }
+
+    fn supports_color(&self) -> bool {
+        true
+    }
+
+    fn cursor_up(&mut self) -> term::Result<()> {
+        Err(term::Error::NotSupported)
+    }
+
+    fn delete_line(&mut self) -> term::Result<()> {
+        Err(term::Error::NotSupported)
+    }
+
+    fn carriage_return(&mut self) -> term::Result<()> {
+        Err(term::Error::NotSupported)
+    }
+
+    fn get_ref(&self) -> &SpanRecorder {
+        self
+    }
+
+    fn get_mut(&mut self) -> &mut SpanRecorder {
+        self
+    }
+
+    fn into_inner(self) -> SpanRecorder {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn record() {
+        let mut rec = SpanRecorder::new();
+        rec.write_all(b"| ").unwrap();
+        rec.attr(Attr::Bold).unwrap();
+        rec.write_all(b"foo").unwrap();
+        rec.reset().unwrap();
+        rec.write_all(b" |").unwrap();
+        rec.write_all(b"\r\n").unwrap();
+        rec.attr(Attr::Bold).unwrap();
+        rec.reset().unwrap();
+        rec.write_all(b"bar").unwrap();
+        let lines = rec.into_lines();
+        assert_eq!(lines,
+                   vec![vec![StyledSpan::new("| ", vec![]),
+                             StyledSpan::new("foo", vec![Attr::Bold]),
+                             StyledSpan::new(" |", vec![])],
+                        vec![StyledSpan::new("bar", vec![])]]);
+    }
+
+    #[test]
+    fn styled_text_lines() {
+        let text = StyledText::new()
+            .with("a\r\nb", vec![Attr::Bold])
+            .with("c\n", vec![]);
+        assert_eq!(text.to_string(), "a\r\nbc\n");
+        assert_eq!(text.lines(),
+                   vec![vec![StyledSpan::new("a", vec![Attr::Bold])],
+                        vec![StyledSpan::new("b", vec![Attr::Bold]),
+                             StyledSpan::new("c", vec![])]]);
+    }
+
+    #[test]
+    fn markup() {
+        let markup = "a **b {red}c{/}** {on_bright_blue}{dim}d{/}e{/} \\**{x}";
+        let text = StyledText::parse_markup(markup);
+        assert_eq!(text.get_spans(),
+                   &[StyledSpan::new("a ", vec![]),
+                     StyledSpan::new("b ", vec![Attr::Bold]),
+                     StyledSpan::new("c", vec![Attr::Bold, Attr::ForegroundColor(color::RED)]),
+                     StyledSpan::new(" ", vec![]),
+                     StyledSpan::new("d",
+                                     vec![Attr::BackgroundColor(color::BRIGHT_BLUE), Attr::Dim]),
+                     StyledSpan::new("e", vec![Attr::BackgroundColor(color::BRIGHT_BLUE)]),
+                     StyledSpan::new(" **{x}", vec![])]);
+    }
+
+    #[test]
+    fn skip_escapes() {
+        let mut rec = SpanRecorder::new();
+        rec.write_all(b"a\x1b[38;2;1;2;3mb\x1b]1337;File=x:Zm9v\x07c").unwrap();
+        rec.write_all(b"\x1b_Ga=T;Zm9v\x1b\\d\x1b7e").unwrap();
+        let lines = rec.into_lines();
+        assert_eq!(lines, vec![vec![StyledSpan::new("abcde", vec![])]]);
+    }
+}
@@ -0,0 +1,303 @@
+//! This module contains definition of sparse tables, suited for very wide and mostly empty tables
+use std::io::{self, Write, Error};
+use std::collections::BTreeMap;
+use std::collections::btree_map::Iter;
+use std::iter::Peekable;
+use std::fmt;
+
+use term::{Terminal, stdout};
+
+use super::{Table, print_rows_with_width};
+use super::row::Row;
+use super::cell::Cell;
+use super::format::{TableFormat, PrintLimits, consts};
+use super::utils::{StringWriter, LineCounter, stdout_colorized, write_lines};
+
+/// A printable table storing only its non-empty cells, indexed by `(row, column)`.
+///
+/// Missing cells are printed as empty cells, so very wide and mostly empty tables
+/// can be built and printed without allocating all their empty cells.
+/// The number of rows and columns is inferred from the highest cell indices.
+///
+/// # Examples
+/// ```rust
+/// use prettytable::sparse::SparseTable;
+/// use prettytable::cell::Cell;
+///
+/// let mut table = SparseTable::new();
+/// table.set_cell(0, 0, Cell::new("top left"));
+/// table.set_cell(2, 3, Cell::new("bottom right"));
+/// table.printstd(); // Prints a 3 rows, 4 columns table
+/// ```
+#[derive(Clone, Debug)]
+pub struct SparseTable {
+    format: TableFormat,
+    titles: Option<Row>,
+    cells: BTreeMap<(usize, usize), Cell>,
+}
+
+impl SparseTable {
+    /// Create an empty sparse table
+    pub fn new() -> SparseTable {
+        SparseTable {
+            format: *consts::FORMAT_DEFAULT,
+            titles: None,
+            cells: BTreeMap::new(),
+        }
+    }
+
+    /// Change the table format. Eg : Separators
+    pub fn set_format(&mut self, format: TableFormat) {
+        self.format = format;
+    }
+
+    /// Get a mutable reference to the internal format
+    pub fn get_format(&mut self) -> &mut TableFormat {
+        &mut self.format
+    }
+
+    /// Set the optional title lines
+    pub fn set_titles(&mut self, titles: Row) {
+        self.titles = Some(titles);
+    }
+
+    /// Unset the title line
+    pub fn unset_titles(&mut self) {
+        self.titles = None;
+    }
+
+    /// Get the number of rows, which is the highest row index containing a cell, plus one
+    pub fn len(&self) -> usize {
+        self.cells.keys().next_back().map_or(0, |&(r, _)| r + 1)
+    }
+
+    /// Check if the table is empty
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// Compute and return the number of column, which is the highest
+    /// column index containing a cell, plus one
+    pub fn get_column_num(&self) -> usize {
+        self.cells.keys().map(|&(_, c)| c + 1).max().unwrap_or(0)
+    }
+
+    /// Get the number of non-empty cells actually stored in the table
+    pub fn get_cell_count(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// Get the cell at `(row, column)`, if it exists
+    pub fn get_cell(&self, row: usize, column: usize) -> Option<&Cell> {
+        self.cells.get(&(row, column))
+    }
+
+    /// Get the mutable cell at `(row, column)`, if it exists
+    pub fn get_mut_cell(&mut self, row: usize, column: usize) -> Option<&mut Cell> {
+        self.cells.get_mut(&(row, column))
+    }
+
+    /// Set the `cell` at `(row, column)`, and return the cell previously stored there
+    pub fn set_cell(&mut self, row: usize, column: usize, cell: Cell) -> Option<Cell> {
+        self.cells.insert((row, column), cell)
+    }
+
+    /// Remove the cell at `(row, column)`, and return it
+    pub fn remove_cell(&mut self, row: usize, column: usize) -> Option<Cell> {
+        self.cells.remove(&(row, column))
+    }
+
+    /// Internal only. Returns an iterator building every full row of the table
+    fn rows(&self) -> SparseRows {
+        SparseRows {
+            cells: self.cells.iter().peekable(),
+            next_row: 0,
+            len: self.len(),
+        }
+    }
+
+    /// Get the width of all columns, and return a vector
+    /// with the result for each column
+    fn get_all_column_width(&self) -> Vec<usize> {
+        let mut col_width = vec![0usize; self.get_column_num()];
+        for (&(_, c), cell) in &self.cells {
+            if cell.get_width() > col_width[c] {
+                col_width[c] = cell.get_width();
+            }
+        }
+        if let Some(ref t) = self.titles {
+            for (c, w) in col_width.iter_mut().enumerate() {
+                if t.get_cell_width(c) > *w {
+                    *w = t.get_cell_width(c);
+                }
+            }
+        }
+        col_width
+    }
+
+    /// Convert this sparse table into a regular `Table`, filling missing cells with empty ones
+    pub fn to_table(&self) -> Table {
+        let mut table = Table::init(self.rows().collect());
+        table.set_format(self.format);
+        if let Some(ref t) = self.titles {
+            table.set_titles(t.clone());
+        }
+        table
+    }
+
+    /// Internal only
+    fn __print<'w, T: Write + ?Sized, F>(&self,
+                                         out: &'w mut T,
+                                         limits: &PrintLimits,
+                                         f: F)
+                                         -> Result<(), Error>
+        where F: Fn(&Row, &mut LineCounter<'w, T>, &TableFormat, &[usize]) -> Result<(), Error>
+    {
+        print_rows_with_width(&mut LineCounter::new(out),
+                              &self.format,
+                              &self.titles,
+                              &None,
+                              &None,
+                              self.rows(),
+                              self.get_all_column_width(),
+                              limits,
+                              f)
+    }
+
+    /// Print the table to `out`
+    pub fn print<T: Write + ?Sized>(&self, out: &mut T) -> Result<(), Error> {
+        self.__print(out, &PrintLimits::new(), Row::print)
+    }
+
+    /// Print the table to terminal `out`, applying styles when needed
+    pub fn print_term<T: Terminal + ?Sized>(&self, out: &mut T) -> Result<(), Error> {
+        self.__print(out, &PrintLimits::new(), Row::print_term)
+    }
+
+    /// Print the table to standard output. See `TableSlice::print_tty` for details
+    /// # Panic
+    /// Panic if writing to standard output fails
+    pub fn print_tty(&self, force_colorize: bool) {
+        let limits = self.format.get_limits();
+        let r = match (stdout(), stdout_colorized() || force_colorize) {
+            (Some(mut o), true) => self.__print(&mut *o, &limits, Row::print_term),
+            _ => self.__print(&mut io::stdout(), &limits, Row::print),
+        };
+        if let Err(e) = r {
+            panic!("Cannot print table to standard output : {}", e);
+        }
+    }
+
+    /// Print the table to standard output. See `TableSlice::printstd` for details
+    /// # Panic
+    /// Panic if writing to standard output fails
+    pub fn printstd(&self) {
+        self.print_tty(false);
+    }
+}
+
+/// Internal only. Iterator building full rows out of the cells of a `SparseTable`,
+/// filling missing cells with empty ones
+struct SparseRows<'a> {
+    cells: Peekable<Iter<'a, (usize, usize), Cell>>,
+    next_row: usize,
+    len: usize,
+}
+
+impl<'a> Iterator for SparseRows<'a> {
+    type Item = Row;
+    fn next(&mut self) -> Option<Row> {
+        if self.next_row >= self.len {
+            return None;
+        }
+        let mut row = Row::empty();
+        while let Some((&(r, c), cell)) = self.cells.peek().cloned() {
+            if r != self.next_row {
+                break;
+            }
+            while row.len() < c {
+                row.add_cell(Cell::default());
+            }
+            row.add_cell(cell.clone());
+            self.cells.next();
+        }
+        self.next_row += 1;
+        Some(row)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.len - self.next_row;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<'a> ExactSizeIterator for SparseRows<'a> {}
+
+impl Default for SparseTable {
+    fn default() -> SparseTable {
+        SparseTable::new()
+    }
+}
+
+impl fmt::Display for SparseTable {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        let mut writer = StringWriter::new();
+        if self.print(&mut writer).is_err() {
+            return Err(fmt::Error);
+        }
+        write_lines(fmt, writer.as_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn size() {
+        let mut table = SparseTable::new();
+        assert!(table.is_empty());
+        assert_eq!(table.len(), 0);
+        assert_eq!(table.get_column_num(), 0);
+        table.set_cell(3, 1, Cell::new("a"));
+        table.set_cell(1, 1_000_000, Cell::new("b"));
+        assert_eq!(table.len(), 4);
+        assert_eq!(table.get_column_num(), 1_000_001);
+        assert_eq!(table.get_cell_count(), 2);
+        assert_eq!(table.remove_cell(1, 1_000_000).unwrap().get_content(), "b");
+        assert_eq!(table.get_column_num(), 2);
+        assert!(table.get_cell(1, 1_000_000).is_none());
+    }
+
+    #[test]
+    fn get_set_cell() {
+        let mut table = SparseTable::new();
+        assert!(table.set_cell(0, 0, Cell::new("a")).is_none());
+        assert_eq!(table.set_cell(0, 0, Cell::new("b")).unwrap().get_content(), "a");
+        assert_eq!(table.get_cell(0, 0).unwrap().get_content(), "b");
+        table.get_mut_cell(0, 0).unwrap().align(::format::Alignment::RIGHT);
+        assert!(table.get_mut_cell(1, 0).is_none());
+    }
+
+    #[test]
+    fn print() {
+        let mut table = SparseTable::new();
+        table.set_titles(Row::new(vec![Cell::new("t1"), Cell::new("t2")]));
+        table.set_cell(0, 0, Cell::new("a"));
+        table.set_cell(2, 2, Cell::new("def"));
+        let out = "\
++----+----+-----+
+| t1 | t2 |     |
++====+====+=====+
+| a  |    |     |
++----+----+-----+
+|    |    |     |
++----+----+-----+
+|    |    | def |
++----+----+-----+
+";
+        assert_eq!(out, table.to_string().replace("\r\n", "\n"));
+        assert_eq!(out, table.to_table().to_string().replace("\r\n", "\n"));
+        assert_eq!(format!("{:.4}", table).replace("\r\n", "\n").lines().nth(3), Some("| a "));
+    }
+}
@@ -0,0 +1,323 @@
+//! This module contains a table printer writing rows as soon as they are pushed,
+//! suited for long running producers and outputs too large to be kept in memory
+use std::cmp;
+use std::io::{Write, Error};
+#[cfg(feature = "csv")]
+use std::io::{Read, Seek};
+
+#[cfg(feature = "csv")]
+use csv;
+
+use super::get_all_column_width;
+#[cfg(feature = "csv")]
+use super::Table;
+use super::row::Row;
+use super::format::{TableFormat, LinePosition, consts};
+#[cfg(feature = "csv")]
+use super::format::display_width;
+
+/// A table printed row by row as rows are pushed, without storing them.
+///
+/// Since rows are printed before the next ones are known, the width of the columns is
+/// committed once, either explicitly from titles and a sample of rows with
+/// `commit_widths_from`, or implicitly from titles and the first pushed row.
+/// Content of later rows wider than the committed widths is truncated, so that
+/// the layout never shifts.
+///
+/// # Examples
+/// ```rust
+/// # #[macro_use] extern crate prettytable;
+/// use prettytable::stream::TableStream;
+/// # fn main() {
+/// let mut stream = TableStream::new(Vec::new());
+/// stream.set_titles(row!["Name", "Status"]);
+/// stream.min_col_width(6);
+/// stream.commit_widths_from(&[row!["build", "ok"]]);
+/// stream.push(&row!["build", "ok"]).unwrap();
+/// stream.push(&row!["integration", "failed"]).unwrap();
+/// let out = String::from_utf8(stream.finish().unwrap()).unwrap();
+/// assert!(out.contains("| integr | failed |"));
+/// # }
+/// ```
+pub struct TableStream<W: Write> {
+    out: W,
+    format: TableFormat,
+    titles: Option<Row>,
+    min_col_width: usize,
+    widths: Option<Vec<usize>>,
+    started: bool,
+    rows: usize,
+}
+
+impl<W: Write> TableStream<W> {
+    /// Create a new stream printing to `out` with the default format
+    pub fn new(out: W) -> TableStream<W> {
+        TableStream {
+            out: out,
+            format: *consts::FORMAT_DEFAULT,
+            titles: None,
+            min_col_width: 0,
+            widths: None,
+            started: false,
+            rows: 0,
+        }
+    }
+
+    /// Change the format. It must be set before the first row is pushed
+    pub fn set_format(&mut self, format: TableFormat) {
+        self.format = format;
+    }
+
+    /// Set the titles. They must be set before the first row is pushed
+    pub fn set_titles(&mut self, titles: Row) {
+        self.titles = Some(titles);
+    }
+
+    /// Set the minimum width of the columns, applied when widths are committed
+    pub fn min_col_width(&mut self, width: usize) {
+        self.min_col_width = width;
+    }
+
+    /// Commit the width of the columns from the titles and `sample` rows, instead of
+    /// the titles and the first pushed row. Has no effect once widths are committed
+    pub fn commit_widths_from(&mut self, sample: &[Row]) {
+        if self.widths.is_some() {
+            return;
+        }
+        let widths = get_all_column_width(&self.titles,
+                                          sample.iter(),
+                                          &[],
+                                          self.format.get_ambiguous_width());
+        let min = self.min_col_width;
+        self.widths = Some(widths.into_iter().map(|w| cmp::max(w, min)).collect());
+    }
+
+    /// Commit the width of the columns, raised to the minimum width if needed.
+    /// Has no effect once widths are committed
+    pub fn commit_widths(&mut self, widths: &[usize]) {
+        if self.widths.is_none() {
+            let min = self.min_col_width;
+            self.widths = Some(widths.iter().map(|&w| cmp::max(w, min)).collect());
+        }
+    }
+
+    /// Get the committed width of the columns, if any
+    pub fn get_widths(&self) -> Option<&[usize]> {
+        self.widths.as_ref().map(|w| &w[..])
+    }
+
+    /// Get the number of rows pushed so far
+    pub fn len(&self) -> usize {
+        self.rows
+    }
+
+    /// Check if no row has been pushed so far
+    pub fn is_empty(&self) -> bool {
+        self.rows == 0
+    }
+
+    /// Print the top border and the titles, committing widths from `first` if needed
+    fn start(&mut self, first: Option<&Row>) -> Result<(), Error> {
+        if self.started {
+            return Ok(());
+        }
+        let sample: Vec<Row> = first.into_iter().cloned().collect();
+        self.commit_widths_from(&sample);
+        self.started = true;
+        self.separator(LinePosition::Top)?;
+        if let (&Some(ref titles), &Some(ref widths)) = (&self.titles, &self.widths) {
+            titles.print(&mut self.out, &self.format, widths)?;
+        }
+        if self.titles.is_some() {
+            self.separator(LinePosition::Title)?;
+        }
+        Ok(())
+    }
+
+    /// Print the line separator at `pos` with the committed widths
+    fn separator(&mut self, pos: LinePosition) -> Result<(), Error> {
+        match self.widths {
+            Some(ref widths) => self.format.print_line_separator(&mut self.out, widths, pos),
+            None => Ok(()),
+        }
+    }
+
+    /// Print `row` right away, truncating content wider than the committed widths
+    pub fn push(&mut self, row: &Row) -> Result<(), Error> {
+        self.start(Some(row))?;
+        if self.rows > 0 {
+            self.separator(LinePosition::Intern)?;
+        }
+        if let Some(ref widths) = self.widths {
+            row.print(&mut self.out, &self.format, widths)?;
+        }
+        self.rows += 1;
+        self.out.flush()
+    }
+
+    /// Print the bottom border, and return the output
+    pub fn finish(mut self) -> Result<W, Error> {
+        self.start(None)?;
+        self.separator(LinePosition::Bottom)?;
+        self.out.flush()?;
+        Ok(self.out)
+    }
+}
+
+#[cfg(feature = "csv")]
+impl Table {
+    /// Print the records of a CSV reader to `out` with `format` as they are read, without
+    /// keeping them in memory. A first pass over the records computes the width of the
+    /// columns, then the reader is rewound to print them.
+    /// If the reader has headers, they are printed as titles.
+    /// Return `out` once every record has been printed
+    ///
+    /// # Examples
+    /// ```rust
+    /// # extern crate csv;
+    /// # extern crate prettytable;
+    /// use std::io::Cursor;
+    /// use prettytable::Table;
+    /// use prettytable::format::consts::FORMAT_CLEAN;
+    /// # fn main() {
+    /// let mut reader = csv::Reader::from_reader(Cursor::new("id,name\n1,apple\n2,pear"));
+    /// let out = Table::print_csv_stream(&mut reader, Vec::new(), *FORMAT_CLEAN).unwrap();
+    /// let out = String::from_utf8(out).unwrap().replace("\r\n", "\n");
+    /// assert_eq!(out, " id  name \n 1   apple \n 2   pear \n");
+    /// # }
+    /// ```
+    pub fn print_csv_stream<R, W>(reader: &mut csv::Reader<R>,
+                                  out: W,
+                                  format: TableFormat)
+                                  -> csv::Result<W>
+        where R: Read + Seek,
+              W: Write
+    {
+        let mut widths: Vec<usize> = Vec::new();
+        if reader.has_headers() {
+            measure_record(&mut widths, reader.headers()?);
+        }
+        let start = reader.position().clone();
+        for record in reader.records() {
+            measure_record(&mut widths, &record?);
+        }
+        reader.seek(start)?;
+        Table::print_csv_stream_with_widths(reader, out, format, &widths)
+    }
+
+    /// Print the records of a CSV reader to `out` with `format` as they are read, in
+    /// columns of the given `widths`, without keeping them in memory nor reading them twice.
+    /// Content wider than its column is truncated.
+    /// If the reader has headers, they are printed as titles.
+    /// Return `out` once every record has been printed
+    pub fn print_csv_stream_with_widths<R, W>(reader: &mut csv::Reader<R>,
+                                              out: W,
+                                              format: TableFormat,
+                                              widths: &[usize])
+                                              -> csv::Result<W>
+        where R: Read,
+              W: Write
+    {
+        let mut stream = TableStream::new(out);
+        stream.set_format(format);
+        if reader.has_headers() {
+            stream.set_titles(Row::from(reader.headers()?));
+        }
+        stream.commit_widths(widths);
+        for record in reader.records() {
+            stream.push(&Row::from(&record?))?;
+        }
+        Ok(stream.finish()?)
+    }
+}
+
+/// Internal only. Raise `widths` to the width of the fields of `record`
+#[cfg(feature = "csv")]
+fn measure_record(widths: &mut Vec<usize>, record: &csv::StringRecord) {
+    for (i, field) in record.iter().enumerate() {
+        let width = display_width(field);
+        if i < widths.len() {
+            widths[i] = cmp::max(widths[i], width);
+        } else {
+            widths.push(width);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cell::Cell;
+
+    fn row(cells: &[&str]) -> Row {
+        Row::new(cells.iter().map(|c| Cell::new(c)).collect())
+    }
+
+    #[test]
+    fn stream() {
+        let mut stream = TableStream::new(Vec::new());
+        stream.set_titles(row(&["a", "b"]));
+        stream.push(&row(&["foo", "x"])).unwrap();
+        assert_eq!(stream.get_widths(), Some(&[3, 1][..]));
+        stream.push(&row(&["foobar", "yz"])).unwrap();
+        assert_eq!(stream.len(), 2);
+        let out = String::from_utf8(stream.finish().unwrap()).unwrap();
+        assert_eq!(out.replace("\r\n", "\n"),
+                   "\
++-----+---+
+| a   | b |
++=====+===+
+| foo | x |
++-----+---+
+| foo | y |
++-----+---+
+");
+    }
+
+    #[test]
+    fn committed_widths() {
+        let mut stream = TableStream::new(Vec::new());
+        stream.min_col_width(2);
+        stream.commit_widths_from(&[row(&["a", "bbb"]), row(&["cccc"])]);
+        stream.commit_widths_from(&[row(&["dddddd"])]);
+        assert_eq!(stream.get_widths(), Some(&[4, 3][..]));
+        assert!(stream.is_empty());
+        let out = String::from_utf8(stream.finish().unwrap()).unwrap();
+        assert_eq!(out.replace("\r\n", "\n"), "+------+-----+\n+------+-----+\n");
+    }
+
+    #[test]
+    #[cfg(feature = "csv")]
+    fn csv_stream() {
+        use std::io::Cursor;
+        use csv::ReaderBuilder;
+
+        let data = "a,bb\nccc,d\n";
+        let mut reader = ReaderBuilder::new().has_headers(false).from_reader(Cursor::new(data));
+        let out = Table::print_csv_stream(&mut reader, Vec::new(), *consts::FORMAT_DEFAULT);
+        let out = String::from_utf8(out.unwrap()).unwrap();
+        assert_eq!(out.replace("\r\n", "\n"),
+                   "\
++-----+----+
+| a   | bb |
++-----+----+
+| ccc | d  |
++-----+----+
+");
+        let mut reader = ReaderBuilder::new().from_reader(data.as_bytes());
+        let widths = [2, 1];
+        let out = Table::print_csv_stream_with_widths(&mut reader, Vec::new(),
+                                                      *consts::FORMAT_DEFAULT, &widths);
+        let out = String::from_utf8(out.unwrap()).unwrap();
+        assert_eq!(out.replace("\r\n", "\n"),
+                   "\
++----+---+
+| a  | b |
++====+===+
+| cc | d |
++----+---+
+");
+        let mut reader = ReaderBuilder::new().from_reader(Cursor::new("a,b\nc\n"));
+        assert!(Table::print_csv_stream(&mut reader, Vec::new(), *consts::FORMAT_DEFAULT).is_err());
+    }
+}
@@ -0,0 +1,168 @@
+//! This module contains helpers to generate tables for tests and benchmarks
+use super::Table;
+use super::row::Row;
+use super::cell::Cell;
+use super::format::{Alignment, consts, line_width};
+use super::utils::XorShift;
+
+/// Words used to fill generated cells, including wide and accented characters
+static WORDS: &'static [&'static str] = &["lorem", "ipsum", "dolor", "sit", "amet",
+                                          "consectetur", "adipiscing", "elit", "sed", "do",
+                                          "eiusmod", "tempor", "incididunt", "ut", "labore",
+                                          "et", "dolore", "magna", "aliqua", "café", "naïve",
+                                          "Ünïcödé", "表格", "漂亮的", "テーブル", "데이터"];
+
+/// Generate a table of `rows` rows and `cols` columns, with titles, filled with pseudo-random
+/// content : words of various widths including unicode wide characters, numbers aligned
+/// to the right, multi-line cells and empty cells.
+/// The same `seed` always generates the same table, so it can be used in tests comparing
+/// rendered output, or in benchmarks
+///
+/// # Examples
+/// ```rust
+/// use prettytable::testing::lorem_table;
+///
+/// let table = lorem_table(10, 4, 42);
+/// assert_eq!(table.len(), 10);
+/// assert_eq!(table.to_string(), lorem_table(10, 4, 42).to_string());
+/// ```
+pub fn lorem_table(rows: usize, cols: usize, seed: u64) -> Table {
+    let mut rng = XorShift::new(seed);
+    let words = |rng: &mut XorShift, n: usize| {
+        (0..n)
+            .map(|_| WORDS[rng.next_below(WORDS.len())])
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+    let mut table = Table::new();
+    table.set_titles(Row::new((0..cols).map(|c| Cell::new(&format!("col{}", c))).collect()));
+    for _ in 0..rows {
+        let mut cells = Vec::with_capacity(cols);
+        for _ in 0..cols {
+            let cell = match rng.next_below(10) {
+                0 => Cell::default(),
+                1 | 2 => {
+                    let value = rng.next_below(1000000) as f64 / 100.0;
+                    Cell::new_align(&format!("{:.2}", value), Alignment::RIGHT)
+                }
+                3 => {
+                    let lines: Vec<String> = (0..2 + rng.next_below(3))
+                        .map(|_| {
+                                 let n = 1 + rng.next_below(3);
+                                 words(&mut rng, n)
+                             })
+                        .collect();
+                    Cell::new(&lines.join("\n"))
+                }
+                _ => {
+                    let n = 1 + rng.next_below(4);
+                    Cell::new(&words(&mut rng, n))
+                }
+            };
+            cells.push(cell);
+        }
+        table.add_row(Row::new(cells));
+    }
+    table
+}
+
+/// The result of rendering a table with one of the predefined formats
+#[derive(Clone, Debug)]
+pub struct Rendering {
+    /// Name of the format, like `FORMAT_DEFAULT`
+    name: &'static str,
+    /// Rendered table, with `\n` line endings
+    output: String,
+    /// Display width of the widest line
+    width: usize,
+    /// Number of lines
+    height: usize,
+}
+
+impl Rendering {
+    /// Get the name of the format used for this rendering
+    pub fn get_name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Get the rendered table, with `\n` line endings on every platform
+    pub fn get_output(&self) -> &str {
+        &self.output
+    }
+
+    /// Get the display width of the widest rendered line
+    pub fn get_width(&self) -> usize {
+        self.width
+    }
+
+    /// Get the number of rendered lines
+    pub fn get_height(&self) -> usize {
+        self.height
+    }
+}
+
+/// Render `table` with every predefined format listed by `format::consts::presets()`,
+/// reporting the size of each rendering. This helps picking the format best fitting the
+/// available space, and can be used in tests to compare outputs against golden files
+///
+/// # Examples
+/// ```rust
+/// use prettytable::testing::{lorem_table, render_presets};
+///
+/// let table = lorem_table(5, 3, 1);
+/// let smallest = render_presets(&table).into_iter().min_by_key(|r| r.get_width()).unwrap();
+/// println!("{} :\n{}", smallest.get_name(), smallest.get_output());
+/// ```
+pub fn render_presets(table: &Table) -> Vec<Rendering> {
+    consts::presets()
+        .into_iter()
+        .map(|(name, format)| {
+            let mut table = table.clone();
+            table.set_format(*format);
+            let output = table.to_string().replace("\r\n", "\n");
+            let width = output.lines().map(line_width).max().unwrap_or(0);
+            let height = output.lines().count();
+            Rendering {
+                name: name,
+                output: output,
+                width: width,
+                height: height,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deterministic() {
+        let table = lorem_table(50, 5, 7);
+        assert_eq!(table.len(), 50);
+        assert_eq!(table.get_column_num(), 5);
+        assert_eq!(table.to_string(), lorem_table(50, 5, 7).to_string());
+        assert!(table.to_string() != lorem_table(50, 5, 8).to_string());
+        assert!(table.row_iter().any(|r| r.iter().any(|c| c.get_height() > 1)));
+        assert!(lorem_table(0, 0, 1).is_empty());
+    }
+
+    #[test]
+    fn presets() {
+        let table = lorem_table(4, 3, 3);
+        let renderings = render_presets(&table);
+        assert_eq!(renderings.len(), consts::presets().len());
+        let default = &renderings[0];
+        assert_eq!(default.get_name(), "FORMAT_DEFAULT");
+        assert_eq!(default.get_output(), table.to_string().replace("\r\n", "\n"));
+        let rows_height: usize = table.row_iter().map(|r| r.get_height()).sum();
+        for r in &renderings {
+            assert_eq!(r.get_height(), r.get_output().lines().count());
+            assert!(r.get_height() > rows_height, "{} is too short", r.get_name());
+            assert!(r.get_width() <= default.get_width(), "{} is too wide", r.get_name());
+        }
+        let clean = renderings.iter().find(|r| r.get_name() == "FORMAT_CLEAN").unwrap();
+        assert!(clean.get_width() < default.get_width());
+        assert_eq!(clean.get_height(), rows_height + 1);
+    }
+}
@@ -0,0 +1,136 @@
+//! This module contains definition of themes, bundling styling settings shared by many tables
+use term::Attr;
+
+use super::Table;
+use super::format::{TableFormat, consts};
+
+/// A set of styling settings which can be applied at once to many tables,
+/// so that all the tables of an application look the same
+///
+/// # Examples
+/// ```rust
+/// # #[macro_use] extern crate prettytable;
+/// use prettytable::theme::Theme;
+/// # fn main() {
+/// let mut table = table![["foo", 1], ["bar", 2]];
+/// table.set_titles(row!["name", "qty"]);
+/// table.set_theme(&Theme::dark());
+/// table.printstd();
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct Theme {
+    /// Format of the tables, including the title style and row stripes
+    format: TableFormat,
+    /// Align cells according to the type of content of their column
+    auto_align: bool,
+}
+
+impl Theme {
+    /// Create a new theme using `format`, without any other setting
+    pub fn new(format: TableFormat) -> Theme {
+        Theme {
+            format: format,
+            auto_align: false,
+        }
+    }
+
+    /// Set the style specifier applied to title cells. See `TableFormat::title_style`.
+    /// Can be chained
+    pub fn title_style(mut self, spec: &'static str) -> Theme {
+        self.format.title_style(spec);
+        self
+    }
+
+    /// Set the style attribute applied to every other data row. See `TableFormat::stripe`.
+    /// Can be chained
+    pub fn stripe(mut self, attr: Attr) -> Theme {
+        self.format.stripe(attr);
+        self
+    }
+
+    /// Set whether the theme aligns cells according to the type of content of their column
+    /// when it is applied. See `Table::auto_align`. Can be chained
+    pub fn auto_align(mut self, auto_align: bool) -> Theme {
+        self.auto_align = auto_align;
+        self
+    }
+
+    /// Get the format of the theme
+    pub fn get_format(&self) -> &TableFormat {
+        &self.format
+    }
+
+    /// Check whether the theme aligns cells according to the type of content of their column
+    pub fn get_auto_align(&self) -> bool {
+        self.auto_align
+    }
+
+    /// A theme for terminals with a dark background : bold bright yellow centered titles,
+    /// and dark gray stripes on 256-color terminals
+    pub fn dark() -> Theme {
+        Theme::new(*consts::FORMAT_DEFAULT)
+            .title_style("bcFY")
+            .stripe(Attr::BackgroundColor(236))
+            .auto_align(true)
+    }
+
+    /// A theme for terminals with a light background : bold blue centered titles,
+    /// and light gray stripes on 256-color terminals
+    pub fn light() -> Theme {
+        Theme::new(*consts::FORMAT_DEFAULT)
+            .title_style("bcFb")
+            .stripe(Attr::BackgroundColor(254))
+            .auto_align(true)
+    }
+
+    /// A theme without colors : bold centered titles and no stripes
+    pub fn monochrome() -> Theme {
+        Theme::new(*consts::FORMAT_NO_LINESEP_WITH_TITLE)
+            .title_style("bc")
+            .auto_align(true)
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Theme {
+        Theme::new(*consts::FORMAT_DEFAULT)
+    }
+}
+
+impl Table {
+    /// Apply `theme` to the table : the table format is replaced with the theme format, and
+    /// if required by the theme, cells are aligned according to the content of their column
+    pub fn set_theme(&mut self, theme: &Theme) {
+        self.set_format(theme.format);
+        if theme.auto_align {
+            self.auto_align();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use row::Row;
+    use span::StyledSpan;
+    use term::color;
+
+    #[test]
+    fn set_theme() {
+        let mut table = Table::new();
+        table.set_titles(Row::from(vec!["name", "qty"]));
+        for &(name, qty) in &[("a", 1), ("b", 22), ("c", 3)] {
+            table.add_row(Row::from(vec![name.to_string(), qty.to_string()]));
+        }
+        table.set_theme(&Theme::dark());
+        let lines = table.render_spans();
+        let title_style = vec![Attr::Bold, Attr::ForegroundColor(color::BRIGHT_YELLOW)];
+        assert_eq!(lines[1][1], StyledSpan::new("name", title_style));
+        assert_eq!(lines[3], vec![StyledSpan::new("| a    |   1 |", vec![])]);
+        assert_eq!(lines[5][1], StyledSpan::new("b   ", vec![Attr::BackgroundColor(236)]));
+        assert_eq!(table.get_format().get_title_style(), Some("bcFY"));
+        table.set_theme(&Theme::monochrome());
+        assert_eq!(table.get_format().get_stripe(), None);
+    }
+}
@@ -1,10 +1,24 @@
 //! Internal only utilities
 use std::io::{Error, ErrorKind, Write};
 use std::str;
+use std::fmt;
+use std::sync::Arc;
+use std::env;
 
-use unicode_width::UnicodeWidthStr;
+use atty;
 
-use super::format::Alignment;
+use unicode_width::UnicodeWidthChar;
+#[cfg(feature = "unicode-segmentation")]
+use unicode_segmentation::UnicodeSegmentation;
+use term::{self, Attr, Terminal, color};
+
+use super::format::{Alignment, AmbiguousWidth, TableFormat, ColumnPosition, line_width,
+                    line_width_with};
+#[cfg(feature = "unicode-segmentation")]
+use super::format::grapheme_width;
+#[cfg(not(feature = "unicode-segmentation"))]
+use super::format::char_width;
+use super::row::Row;
 
 #[cfg(any(not(windows), not(feature="win_crlf")))]
 pub static NEWLINE: &'static [u8] = b"\n";
@@ -47,6 +61,577 @@ impl Write for StringWriter {
     }
 }
 
+/// Internal utility measuring the display width and the number of lines of the data written
+/// into it, without storing it
+pub struct MeasureWriter {
+    line: Vec<u8>,
+    ambiguous: AmbiguousWidth,
+    width: usize,
+    height: usize,
+}
+
+impl MeasureWriter {
+    /// Create a new `MeasureWriter`, measuring East Asian ambiguous characters
+    /// according to `ambiguous`
+    pub fn new(ambiguous: AmbiguousWidth) -> MeasureWriter {
+        MeasureWriter {
+            line: Vec::new(),
+            ambiguous: ambiguous,
+            width: 0,
+            height: 0,
+        }
+    }
+
+    /// Measure the line being written
+    fn end_line(&mut self) {
+        if self.line.last() == Some(&b'\r') {
+            self.line.pop();
+        }
+        let width = line_width_with(&String::from_utf8_lossy(&self.line)[..], self.ambiguous);
+        if width > self.width {
+            self.width = width;
+        }
+        self.height += 1;
+        self.line.clear();
+    }
+
+    /// Return the width of the widest line and the number of lines. Text written after the
+    /// last newline counts as a line
+    pub fn into_size(mut self) -> (usize, usize) {
+        if !self.line.is_empty() {
+            self.end_line();
+        }
+        (self.width, self.height)
+    }
+}
+
+impl Write for MeasureWriter {
+    fn write(&mut self, data: &[u8]) -> Result<usize, Error> {
+        for &b in data {
+            if b == b'\n' {
+                self.end_line();
+            } else {
+                self.line.push(b);
+            }
+        }
+        Ok(data.len())
+    }
+
+    fn flush(&mut self) -> Result<(), Error> {
+        // Nothing to do here
+        Ok(())
+    }
+}
+
+/// Internal writer counting the lines and bytes written through it to `out`,
+/// which may be a terminal
+pub struct LineCounter<'a, T: 'a + ?Sized> {
+    out: &'a mut T,
+    lines: usize,
+    bytes: usize,
+}
+
+impl<'a, T: 'a + ?Sized> LineCounter<'a, T> {
+    /// Create a new `LineCounter` writing to `out`
+    pub fn new(out: &'a mut T) -> LineCounter<'a, T> {
+        LineCounter {
+            out: out,
+            lines: 0,
+            bytes: 0,
+        }
+    }
+
+    /// Get the number of newlines written so far
+    pub fn lines(&self) -> usize {
+        self.lines
+    }
+
+    /// Get the number of bytes written so far. Styles applied to a terminal are not counted
+    pub fn bytes(&self) -> usize {
+        self.bytes
+    }
+}
+
+impl<'a, T: Write + ?Sized> Write for LineCounter<'a, T> {
+    fn write(&mut self, data: &[u8]) -> Result<usize, Error> {
+        let n = self.out.write(data)?;
+        self.lines += data[..n].iter().filter(|&&b| b == b'\n').count();
+        self.bytes += n;
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> Result<(), Error> {
+        self.out.flush()
+    }
+}
+
+/// Styles are applied to the wrapped terminal. Since the terminal is borrowed,
+/// the counter stands for its output
+impl<'a, T: Terminal + ?Sized> Terminal for LineCounter<'a, T> {
+    type Output = LineCounter<'a, T>;
+
+    fn fg(&mut self, color: color::Color) -> term::Result<()> {
+        self.out.fg(color)
+    }
+
+    fn bg(&mut self, color: color::Color) -> term::Result<()> {
+        self.out.bg(color)
+    }
+
+    fn attr(&mut self, attr: Attr) -> term::Result<()> {
+        self.out.attr(attr)
+    }
+
+    fn supports_attr(&self, attr: Attr) -> bool {
+        self.out.supports_attr(attr)
+    }
+
+    fn reset(&mut self) -> term::Result<()> {
+        self.out.reset()
+    }
+
+    fn supports_reset(&self) -> bool {
+        self.out.supports_reset()
+    }
+
+    fn supports_color(&self) -> bool {
+        self.out.supports_color()
+    }
+
+    fn cursor_up(&mut self) -> term::Result<()> {
+        self.out.cursor_up()
+    }
+
+    fn delete_line(&mut self) -> term::Result<()> {
+        self.out.delete_line()
+    }
+
+    fn carriage_return(&mut self) -> term::Result<()> {
+        self.out.carriage_return()
+    }
+
+    fn get_ref(&self) -> &LineCounter<'a, T> {
+        self
+    }
+
+    fn get_mut(&mut self) -> &mut LineCounter<'a, T> {
+        self
+    }
+
+    fn into_inner(self) -> LineCounter<'a, T> {
+        self
+    }
+}
+
+/// Size of the buffer of a `TermBuffer`, above which gathered text is written to the terminal
+const TERM_BUFFER_SIZE: usize = 8192;
+
+/// Internal terminal gathering the text written through it, to write it to the wrapped
+/// terminal `out` in large chunks. Styles are applied by `out` directly, so gathered text
+/// is written before each style change, for styles to apply to the right text
+pub struct TermBuffer<'a, T: 'a + Terminal + ?Sized> {
+    out: &'a mut T,
+    buffer: Vec<u8>,
+}
+
+impl<'a, T: 'a + Terminal + ?Sized> TermBuffer<'a, T> {
+    /// Create a new `TermBuffer` writing to `out`
+    pub fn new(out: &'a mut T) -> TermBuffer<'a, T> {
+        TermBuffer {
+            out: out,
+            buffer: Vec::with_capacity(TERM_BUFFER_SIZE),
+        }
+    }
+
+    /// Write the gathered text to `out`
+    fn write_buffer(&mut self) -> Result<(), Error> {
+        if !self.buffer.is_empty() {
+            self.out.write_all(&self.buffer)?;
+            self.buffer.clear();
+        }
+        Ok(())
+    }
+}
+
+impl<'a, T: Terminal + ?Sized> Write for TermBuffer<'a, T> {
+    fn write(&mut self, data: &[u8]) -> Result<usize, Error> {
+        self.buffer.extend_from_slice(data);
+        if self.buffer.len() >= TERM_BUFFER_SIZE {
+            self.write_buffer()?;
+        }
+        Ok(data.len())
+    }
+
+    fn flush(&mut self) -> Result<(), Error> {
+        self.write_buffer()?;
+        self.out.flush()
+    }
+}
+
+impl<'a, T: Terminal + ?Sized> Drop for TermBuffer<'a, T> {
+    fn drop(&mut self) {
+        let _ = self.write_buffer();
+    }
+}
+
+impl<'a, T: Terminal + ?Sized> Terminal for TermBuffer<'a, T> {
+    type Output = TermBuffer<'a, T>;
+
+    fn fg(&mut self, color: color::Color) -> term::Result<()> {
+        self.write_buffer()?;
+        self.out.fg(color)
+    }
+
+    fn bg(&mut self, color: color::Color) -> term::Result<()> {
+        self.write_buffer()?;
+        self.out.bg(color)
+    }
+
+    fn attr(&mut self, attr: Attr) -> term::Result<()> {
+        self.write_buffer()?;
+        self.out.attr(attr)
+    }
+
+    fn supports_attr(&self, attr: Attr) -> bool {
+        self.out.supports_attr(attr)
+    }
+
+    fn reset(&mut self) -> term::Result<()> {
+        self.write_buffer()?;
+        self.out.reset()
+    }
+
+    fn supports_reset(&self) -> bool {
+        self.out.supports_reset()
+    }
+
+    fn supports_color(&self) -> bool {
+        self.out.supports_color()
+    }
+
+    fn cursor_up(&mut self) -> term::Result<()> {
+        self.write_buffer()?;
+        self.out.cursor_up()
+    }
+
+    fn delete_line(&mut self) -> term::Result<()> {
+        self.write_buffer()?;
+        self.out.delete_line()
+    }
+
+    fn carriage_return(&mut self) -> term::Result<()> {
+        self.write_buffer()?;
+        self.out.carriage_return()
+    }
+
+    fn get_ref(&self) -> &TermBuffer<'a, T> {
+        self
+    }
+
+    fn get_mut(&mut self) -> &mut TermBuffer<'a, T> {
+        self
+    }
+
+    fn into_inner(self) -> TermBuffer<'a, T> {
+        self
+    }
+}
+
+/// Check if styles should be applied when printing to standard output : styles are applied
+/// if stdout is a tty terminal, unless disabled with the `NO_COLOR` or `CLICOLOR=0`
+/// environment variables. They can also be forced with `CLICOLOR_FORCE`
+pub fn stdout_colorized() -> bool {
+    let var = |name| env::var_os(name).map(|v| v.to_string_lossy().into_owned());
+    colorized(atty::is(atty::Stream::Stdout),
+              var("NO_COLOR"),
+              var("CLICOLOR"),
+              var("CLICOLOR_FORCE"))
+}
+
+/// Decide whether to apply styles, from the tty status of the output
+/// and the values of the `NO_COLOR`, `CLICOLOR`, and `CLICOLOR_FORCE` environment variables.
+/// `CLICOLOR_FORCE` takes precedence over `NO_COLOR`, which takes precedence over `CLICOLOR`
+fn colorized(tty: bool,
+             no_color: Option<String>,
+             clicolor: Option<String>,
+             clicolor_force: Option<String>)
+             -> bool {
+    let set = |v: &Option<String>| v.as_ref().map_or(false, |v| !v.is_empty() && v != "0");
+    if set(&clicolor_force) {
+        true
+    } else if no_color.as_ref().map_or(false, |v| !v.is_empty()) {
+        false
+    } else if clicolor.as_ref().map_or(false, |v| v == "0") {
+        false
+    } else {
+        tty
+    }
+}
+
+/// Internal wrapper around a function applied to each printed line
+#[derive(Clone)]
+pub struct LineFilter(Arc<Fn(String) -> String + Send + Sync>);
+
+impl LineFilter {
+    /// Create a new `LineFilter` from function `f`
+    pub fn new<F>(f: F) -> LineFilter
+        where F: Fn(String) -> String + Send + Sync + 'static
+    {
+        LineFilter(Arc::new(f))
+    }
+}
+
+impl fmt::Debug for LineFilter {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        fmt.write_str("LineFilter")
+    }
+}
+
+/// Internal wrapper around a function computing style attributes of cells at print time
+#[derive(Clone)]
+pub struct CellFormatter(Arc<Fn(usize, usize, &str) -> Option<Vec<Attr>> + Send + Sync>);
+
+impl CellFormatter {
+    /// Create a new `CellFormatter` from function `f`
+    pub fn new<F>(f: F) -> CellFormatter
+        where F: Fn(usize, usize, &str) -> Option<Vec<Attr>> + Send + Sync + 'static
+    {
+        CellFormatter(Arc::new(f))
+    }
+
+    /// Get the style attributes to add to the cell at `row` and `column` containing `content`
+    pub fn format(&self, row: usize, column: usize, content: &str) -> Option<Vec<Attr>> {
+        (self.0)(row, column, content)
+    }
+}
+
+impl fmt::Debug for CellFormatter {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        fmt.write_str("CellFormatter")
+    }
+}
+
+/// Internal wrapper around a function checking rows added to a table
+#[derive(Clone)]
+pub struct RowValidator(Arc<Fn(&Row) -> Result<(), String> + Send + Sync>);
+
+impl RowValidator {
+    /// Create a new `RowValidator` from function `f`
+    pub fn new<F>(f: F) -> RowValidator
+        where F: Fn(&Row) -> Result<(), String> + Send + Sync + 'static
+    {
+        RowValidator(Arc::new(f))
+    }
+
+    /// Check `row`, returning an error message if it is rejected
+    pub fn validate(&self, row: &Row) -> Result<(), String> {
+        (self.0)(row)
+    }
+}
+
+impl fmt::Debug for RowValidator {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        fmt.write_str("RowValidator")
+    }
+}
+
+/// Internal writer buffering data written to it line by line,
+/// and writing each line to `out` after passing it through a `LineFilter`
+pub struct LineFilterWriter<'a, T: Write + ?Sized + 'a> {
+    out: &'a mut T,
+    filter: &'a LineFilter,
+    line: Vec<u8>,
+}
+
+impl<'a, T: Write + ?Sized + 'a> LineFilterWriter<'a, T> {
+    /// Create a new `LineFilterWriter` writing to `out`
+    pub fn new(out: &'a mut T, filter: &'a LineFilter) -> LineFilterWriter<'a, T> {
+        LineFilterWriter {
+            out: out,
+            filter: filter,
+            line: Vec::new(),
+        }
+    }
+
+    /// Write the buffered line to `out` through the filter
+    fn write_line(&mut self) -> Result<(), Error> {
+        let line = String::from_utf8_lossy(&self.line).into_owned();
+        self.line.clear();
+        self.out.write_all((self.filter.0)(line).as_bytes())
+    }
+}
+
+impl<'a, T: Write + ?Sized + 'a> Write for LineFilterWriter<'a, T> {
+    fn write(&mut self, data: &[u8]) -> Result<usize, Error> {
+        for &b in data {
+            if b == b'\n' {
+                if self.line.last() == Some(&b'\r') {
+                    self.line.pop();
+                }
+                self.write_line()?;
+                self.out.write_all(NEWLINE)?;
+            } else {
+                self.line.push(b);
+            }
+        }
+        Ok(data.len())
+    }
+
+    fn flush(&mut self) -> Result<(), Error> {
+        if !self.line.is_empty() {
+            self.write_line()?;
+        }
+        self.out.flush()
+    }
+}
+
+/// Internal pseudo-random number generator (xorshift64*), used where
+/// a stable sequence for a given seed is needed
+pub struct XorShift {
+    state: u64,
+}
+
+impl XorShift {
+    /// Create a new generator from `seed`
+    pub fn new(seed: u64) -> XorShift {
+        // State must never be 0
+        XorShift { state: (seed ^ 0x9E37_79B9_7F4A_7C15) | 1 }
+    }
+
+    /// Return the next pseudo-random number
+    pub fn next_u64(&mut self) -> u64 {
+        self.state ^= self.state >> 12;
+        self.state ^= self.state << 25;
+        self.state ^= self.state >> 27;
+        self.state.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+
+    /// Return the next pseudo-random number in range `0..bound`.
+    /// `bound` must not be 0
+    pub fn next_below(&mut self, bound: usize) -> usize {
+        (self.next_u64() % bound as u64) as usize
+    }
+}
+
+/// Try to parse `text` as a number, ignoring surrounding whitespaces.
+/// Return `None` if `text` is not a finite number
+pub fn parse_number(text: &str) -> Option<f64> {
+    text.trim().parse::<f64>().ok().and_then(|v| if v.is_finite() { Some(v) } else { None })
+}
+
+/// Print the `height` lines of a row to `out`, handling indentation, padding and column separators
+/// according to `format`. For each line and each column, `print_cell` is called with the line index,
+/// the column index, the column width, and whether filling the cell on the right can be skipped
+pub fn print_row_lines<T: Write + ?Sized, F>(out: &mut T,
+                                             format: &TableFormat,
+                                             col_width: &[usize],
+                                             height: usize,
+                                             print_cell: F)
+                                             -> Result<(), Error>
+    where F: Fn(&mut T, usize, usize, usize, bool) -> Result<(), Error>
+{
+    print_spanned_row_lines(out, format, col_width, &vec![1; col_width.len()], height, print_cell)
+}
+
+/// Same as `print_row_lines`, but each printed cell spans over the number of columns given
+/// in `spans`. `print_cell` is called with the index of each cell in `spans`,
+/// and a width including the padding and separators of the merged columns
+pub fn print_spanned_row_lines<T: Write + ?Sized, F>(out: &mut T,
+                                                     format: &TableFormat,
+                                                     col_width: &[usize],
+                                                     spans: &[usize],
+                                                     height: usize,
+                                                     print_cell: F)
+                                                     -> Result<(), Error>
+    where F: Fn(&mut T, usize, usize, usize, bool) -> Result<(), Error>
+{
+    let (lp, rp) = format.get_padding();
+    let sep_width = format.get_column_separator(ColumnPosition::Intern)
+        .map_or(0, |c| UnicodeWidthChar::width(c).unwrap_or(0));
+    for i in 0..height {
+        //TODO: Wrap this into dedicated function one day
+        out.write_all(&vec![b' '; format.get_indent()])?;
+        format.print_column_separator(out, ColumnPosition::Left)?;
+        let mut j = 0;
+        for (k, &span) in spans.iter().enumerate() {
+            let end = j + span;
+            let width = col_width[j..end].iter().sum::<usize>() +
+                        (span - 1) * (lp + rp + sep_width);
+            out.write_all(&vec![b' '; lp])?;
+            let skip_r_fill = (end == col_width.len()) &&
+                              format.get_column_separator(ColumnPosition::Right).is_none();
+            print_cell(out, i, k, width, skip_r_fill)?;
+            out.write_all(&vec![b' '; rp])?;
+            if end < col_width.len() {
+                format.print_column_separator(out, ColumnPosition::Intern)?;
+            }
+            j = end;
+        }
+        format.print_column_separator(out, ColumnPosition::Right)?;
+        out.write_all(NEWLINE)?;
+    }
+    Ok(())
+}
+
+/// Quote `text`, escaping line breaks, tabulations, quotes, control and zero width characters,
+/// and replacing trailing spaces with `·`
+pub fn escape_debug(text: &str) -> String {
+    let trailing = text.chars().rev().take_while(|&c| c == ' ').count();
+    let mut escaped = String::from("\"");
+    for c in text[..text.len() - trailing].chars() {
+        match c {
+            '\n' => escaped.push_str("\\n"),
+            '\t' => escaped.push_str("\\t"),
+            '\r' => escaped.push_str("\\r"),
+            '\\' | '"' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            c if c.is_control() || UnicodeWidthChar::width(c) == Some(0) => {
+                escaped.push_str(&format!("\\u{{{:x}}}", c as u32))
+            }
+            c => escaped.push(c),
+        }
+    }
+    for _ in 0..trailing {
+        escaped.push('·');
+    }
+    escaped.push('"');
+    escaped
+}
+
+/// Return the longest prefix of `text` whose display width is at most `width`.
+/// With the `unicode-segmentation` feature, grapheme clusters are never split
+pub fn truncate(text: &str, width: usize) -> &str {
+    truncate_with(text, width, AmbiguousWidth::Narrow)
+}
+
+/// Return the longest prefix of `text` whose display width is at most `width` like
+/// `truncate`, measuring East Asian ambiguous characters according to `ambiguous`
+#[cfg(feature = "unicode-segmentation")]
+pub fn truncate_with(text: &str, width: usize, ambiguous: AmbiguousWidth) -> &str {
+    let mut w = 0;
+    for (i, g) in text.grapheme_indices(true) {
+        w += grapheme_width(g, ambiguous);
+        if w > width {
+            return &text[..i];
+        }
+    }
+    text
+}
+
+#[cfg(not(feature = "unicode-segmentation"))]
+pub fn truncate_with(text: &str, width: usize, ambiguous: AmbiguousWidth) -> &str {
+    let mut w = 0;
+    for (i, c) in text.char_indices() {
+        w += char_width(c, ambiguous);
+        if w > width {
+            return &text[..i];
+        }
+    }
+    text
+}
+
 /// Align/fill a string and print it to `out`
 /// If `skip_right_fill` is set to `true`, then no space will be added after the string
 /// to complete alignment
@@ -57,7 +642,19 @@ pub fn print_align<T: Write + ?Sized>(out: &mut T,
                                       size: usize,
                                       skip_right_fill: bool)
                                       -> Result<(), Error> {
-    let text_len = UnicodeWidthStr::width(text);
+    print_align_measured(out, align, text, line_width(text), fill, size, skip_right_fill)
+}
+
+/// Align/fill a string whose display width is `text_len`, and print it to `out`.
+/// See `print_align`
+pub fn print_align_measured<T: Write + ?Sized>(out: &mut T,
+                                               align: Alignment,
+                                               text: &str,
+                                               text_len: usize,
+                                               fill: char,
+                                               size: usize,
+                                               skip_right_fill: bool)
+                                               -> Result<(), Error> {
     let mut nfill = if text_len < size { size - text_len } else { 0 };
     let n = match align {
         Alignment::LEFT => 0,
@@ -65,16 +662,81 @@ pub fn print_align<T: Write + ?Sized>(out: &mut T,
         Alignment::CENTER => nfill / 2,
     };
     if n > 0 {
-        out.write_all(&vec![fill as u8; n])?;
+        out.write_all(repeat_char(fill, n).as_bytes())?;
         nfill -= n;
     }
     out.write_all(text.as_bytes())?;
     if nfill > 0 && !skip_right_fill {
-        out.write_all(&vec![fill as u8; nfill])?;
+        out.write_all(repeat_char(fill, nfill).as_bytes())?;
+    }
+    Ok(())
+}
+
+/// Write `text`, made of the lines of a printed table, to `fmt` according to its flags :
+/// each line is truncated to the precision, if any, and padded with the fill character
+/// up to the width, if any, according to the alignment. Line endings are kept.
+/// The alignment flag needs Rust 1.28 or later, lines are aligned to the left otherwise
+pub fn write_lines(fmt: &mut fmt::Formatter, text: &str) -> Result<(), fmt::Error> {
+    if fmt.width().is_none() && fmt.precision().is_none() {
+        return fmt.write_str(text);
+    }
+    let width = fmt.width().unwrap_or(0);
+    let mut rest = text;
+    while !rest.is_empty() {
+        let end = rest.find('\n').map_or(rest.len(), |i| i + 1);
+        let (full, tail) = rest.split_at(end);
+        rest = tail;
+        let mut line = full;
+        for c in &['\n', '\r'] {
+            if line.ends_with(*c) {
+                line = &line[..line.len() - 1];
+            }
+        }
+        let ending = &full[line.len()..];
+        let line = match fmt.precision() {
+            Some(precision) => truncate(line, precision),
+            None => line,
+        };
+        let line_width = line_width(line);
+        let nfill = if line_width < width { width - line_width } else { 0 };
+        let left = match fmt_alignment(fmt) {
+            Alignment::LEFT => 0,
+            Alignment::RIGHT => nfill,
+            Alignment::CENTER => nfill / 2,
+        };
+        if left > 0 {
+            fmt.write_str(&repeat_char(fmt.fill(), left))?;
+        }
+        fmt.write_str(line)?;
+        if nfill > left {
+            fmt.write_str(&repeat_char(fmt.fill(), nfill - left))?;
+        }
+        fmt.write_str(ending)?;
     }
     Ok(())
 }
 
+/// Internal only. Get the alignment requested by the flags of `fmt`, left by default
+#[cfg(fmt_align)]
+fn fmt_alignment(fmt: &fmt::Formatter) -> Alignment {
+    match fmt.align() {
+        Some(fmt::Alignment::Right) => Alignment::RIGHT,
+        Some(fmt::Alignment::Center) => Alignment::CENTER,
+        _ => Alignment::LEFT,
+    }
+}
+
+/// Internal only. `Formatter::align` is not available before Rust 1.28 : always align left
+#[cfg(not(fmt_align))]
+fn fmt_alignment(_fmt: &fmt::Formatter) -> Alignment {
+    Alignment::LEFT
+}
+
+/// Internal only. Return a string made of `n` times `c`
+fn repeat_char(c: char, n: usize) -> String {
+    ::std::iter::repeat(c).take(n).collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -110,6 +772,26 @@ mod tests {
         assert_eq!(out.as_string(), "foo");
     }
 
+    #[test]
+    fn color_env() {
+        let s = |v: &str| Some(v.to_string());
+        assert!(colorized(true, None, None, None));
+        assert!(!colorized(false, None, None, None));
+        assert!(!colorized(true, s("1"), None, None));
+        assert!(colorized(true, s(""), None, None));
+        assert!(!colorized(true, None, s("0"), None));
+        assert!(colorized(true, None, s("1"), None));
+        assert!(colorized(false, s("1"), s("0"), s("1")));
+        assert!(!colorized(false, None, None, s("0")));
+    }
+
+    #[test]
+    fn escape() {
+        assert_eq!(escape_debug("a\tb\n\"c\"  "), "\"a\\tb\\n\\\"c\\\"··\"");
+        assert_eq!(escape_debug("x\u{200b}y\u{1b}"), "\"x\\u{200b}y\\u{1b}\"");
+        assert_eq!(escape_debug("é"), "\"é\"");
+    }
+
     #[test]
     fn skip_right_fill() {
         let mut out = StringWriter::new();
@@ -129,6 +811,79 @@ mod tests {
         assert_eq!(out.as_string(), "foo");
     }
 
+    #[test]
+    fn line_counter() {
+        let mut out = StringWriter::new();
+        {
+            let mut counter = LineCounter::new(&mut out);
+            counter.write_all(b"foo\nbar\r\n").unwrap();
+            counter.write_all(b"baz").unwrap();
+            assert_eq!(counter.lines(), 2);
+        }
+        assert_eq!(out.as_string(), "foo\nbar\r\nbaz");
+    }
+
+    #[test]
+    fn truncate_width() {
+        assert_eq!(truncate("foobar", 3), "foo");
+        assert_eq!(truncate("foo", 10), "foo");
+        assert_eq!(truncate("foo", 0), "");
+        assert_eq!(truncate("由系统", 3), "由");
+        assert_eq!(truncate("由系统", 4), "由系");
+    }
+
+    #[test]
+    #[cfg(feature = "unicode-segmentation")]
+    fn grapheme_widths() {
+        // Family joined with zero width joiners, thumbs up with a skin tone, French flag,
+        // heart with an emoji presentation selector
+        let status = "\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}\u{1F44D}\u{1F3FD}\
+                      \u{1F1EB}\u{1F1F7}\u{2764}\u{FE0F}";
+        assert_eq!(line_width(status), 8);
+        assert_eq!(line_width("Cafe\u{301} \u{2764}\u{FE0E}"), 6);
+        assert_eq!(truncate(status, 3), "\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}");
+        assert_eq!(truncate("e\u{301}e\u{301}", 1), "e\u{301}");
+        let mut out = StringWriter::new();
+        print_align(&mut out, Alignment::RIGHT, "\u{1F44D}\u{1F3FD}!", '.', 5, false).unwrap();
+        assert_eq!(out.as_string(), "..\u{1F44D}\u{1F3FD}!");
+    }
+
+    #[test]
+    fn parse_numbers() {
+        assert_eq!(parse_number("12"), Some(12.0));
+        assert_eq!(parse_number(" -1.5 "), Some(-1.5));
+        assert_eq!(parse_number("1e3"), Some(1000.0));
+        assert_eq!(parse_number("abc"), None);
+        assert_eq!(parse_number(""), None);
+        assert_eq!(parse_number("inf"), None);
+        assert_eq!(parse_number("NaN"), None);
+    }
+
+    #[test]
+    fn xorshift() {
+        let mut rng1 = XorShift::new(42);
+        let mut rng2 = XorShift::new(42);
+        for _ in 0..100 {
+            let v = rng1.next_below(10);
+            assert!(v < 10);
+            assert_eq!(v, rng2.next_below(10));
+        }
+        assert!(XorShift::new(0).next_u64() != 0);
+    }
+
+    #[test]
+    fn line_filter() {
+        let filter = LineFilter::new(|l| format!("> {}", l));
+        let mut out = StringWriter::new();
+        {
+            let mut writer = LineFilterWriter::new(&mut out, &filter);
+            writer.write_all(b"foo\nb").unwrap();
+            writer.write_all(b"ar\nbaz").unwrap();
+            writer.flush().unwrap();
+        }
+        assert_eq!(out.as_string().replace("\r\n", "\n"), "> foo\n> bar\n> baz");
+    }
+
     #[test]
     fn utf8_error() {
         let mut out = StringWriter::new();
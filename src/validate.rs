@@ -0,0 +1,348 @@
+//! This module contains utilities to validate the content of tables against a schema
+use std::fmt;
+
+use term::{Attr, color};
+#[cfg(feature = "regex")]
+use regex::Regex;
+
+use super::{Table, TableSlice};
+use super::row::Row;
+use super::utils::parse_number;
+
+/// Type of the values of a column
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ValueType {
+    /// Any text
+    Text,
+    /// Whole numbers, like `-12`
+    Integer,
+    /// Finite numbers, like `1.5e3`
+    Number,
+    /// `true` or `false`
+    Boolean,
+}
+
+impl Default for ValueType {
+    fn default() -> ValueType {
+        ValueType::Text
+    }
+}
+
+/// Rules the cells of a column must follow. Empty cells are only checked for being required
+#[derive(Clone, Debug, Default)]
+pub struct ColumnRule {
+    /// Reject empty cells
+    required: bool,
+    /// Type of the values
+    value_type: ValueType,
+    /// Smallest allowed numeric value
+    min: Option<f64>,
+    /// Greatest allowed numeric value
+    max: Option<f64>,
+    /// Pattern the whole content must match
+    #[cfg(feature = "regex")]
+    pattern: Option<Regex>,
+}
+
+impl ColumnRule {
+    /// Create a new `ColumnRule`, accepting any content
+    pub fn new() -> ColumnRule {
+        ColumnRule::default()
+    }
+
+    /// Set whether empty cells are rejected. Can be chained
+    pub fn required(mut self, required: bool) -> ColumnRule {
+        self.required = required;
+        self
+    }
+
+    /// Set the type of the values. Default is `ValueType::Text`. Can be chained
+    pub fn value_type(mut self, value_type: ValueType) -> ColumnRule {
+        self.value_type = value_type;
+        self
+    }
+
+    /// Set the range of allowed numeric values, bounds included. Content which is
+    /// not a number is rejected. Can be chained
+    pub fn range(mut self, min: f64, max: f64) -> ColumnRule {
+        self.min = Some(min);
+        self.max = Some(max);
+        self
+    }
+
+    /// Set the smallest allowed numeric value. Can be chained
+    pub fn min(mut self, min: f64) -> ColumnRule {
+        self.min = Some(min);
+        self
+    }
+
+    /// Set the greatest allowed numeric value. Can be chained
+    pub fn max(mut self, max: f64) -> ColumnRule {
+        self.max = Some(max);
+        self
+    }
+
+    /// Set a regular expression the content must match. Use anchors (`^...$`) to match
+    /// the whole content. Requires the `regex` feature. Can be chained
+    #[cfg(feature = "regex")]
+    pub fn pattern(mut self, pattern: Regex) -> ColumnRule {
+        self.pattern = Some(pattern);
+        self
+    }
+
+    /// Check `content` against the rule, and return the reason why it is rejected, if any
+    pub fn check(&self, content: &str) -> Option<String> {
+        if content.is_empty() {
+            return if self.required { Some("missing value".to_string()) } else { None };
+        }
+        let valid = match self.value_type {
+            ValueType::Text => true,
+            ValueType::Integer => content.trim().parse::<i64>().is_ok(),
+            ValueType::Number => parse_number(content).is_some(),
+            ValueType::Boolean => content == "true" || content == "false",
+        };
+        if !valid {
+            let expected = match self.value_type {
+                ValueType::Integer => "an integer",
+                ValueType::Number => "a number",
+                _ => "a boolean",
+            };
+            return Some(format!("expected {}", expected));
+        }
+        if self.min.is_some() || self.max.is_some() {
+            let value = match parse_number(content) {
+                Some(value) => value,
+                None => return Some("expected a number".to_string()),
+            };
+            match (self.min, self.max) {
+                (Some(min), _) if value < min => {
+                    return Some(format!("{} is less than {}", value, min))
+                }
+                (_, Some(max)) if value > max => {
+                    return Some(format!("{} is greater than {}", value, max))
+                }
+                _ => (),
+            }
+        }
+        self.check_pattern(content)
+    }
+
+    #[cfg(feature = "regex")]
+    fn check_pattern(&self, content: &str) -> Option<String> {
+        match self.pattern {
+            Some(ref pattern) if !pattern.is_match(content) => {
+                Some(format!("does not match {}", pattern))
+            }
+            _ => None,
+        }
+    }
+
+    #[cfg(not(feature = "regex"))]
+    fn check_pattern(&self, _content: &str) -> Option<String> {
+        None
+    }
+}
+
+/// Rules of the columns of a table
+///
+/// # Examples
+/// ```rust
+/// # #[macro_use] extern crate prettytable;
+/// use prettytable::validate::{Schema, ColumnRule, ValueType};
+/// # fn main() {
+/// let table = table!(["id", "age"]; => ["1", "34"], ["x", "250"], ["3", ""]);
+/// let schema = Schema::new()
+///     .column(0, ColumnRule::new().value_type(ValueType::Integer))
+///     .column(1, ColumnRule::new().required(true).range(0.0, 150.0));
+/// let report = table.validate_against(&schema);
+/// assert_eq!(report.len(), 3);
+/// assert_eq!(report[0][2].get_content(), "expected an integer");
+/// assert_eq!(report[1][2].get_content(), "250 is greater than 150");
+/// assert_eq!(report[2][2].get_content(), "missing value");
+/// # }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Schema {
+    /// Rule of each column, by column index
+    columns: Vec<(usize, ColumnRule)>,
+}
+
+impl Schema {
+    /// Create a new `Schema`, accepting any table
+    pub fn new() -> Schema {
+        Schema::default()
+    }
+
+    /// Set the rule of column `column`, replacing its previous rule. Can be chained
+    pub fn column(mut self, column: usize, rule: ColumnRule) -> Schema {
+        self.columns.retain(|&(c, _)| c != column);
+        self.columns.push((column, rule));
+        self.columns.sort_by_key(|&(c, _)| c);
+        self
+    }
+
+    /// Get the rule of column `column`, if any
+    pub fn get_column(&self, column: usize) -> Option<&ColumnRule> {
+        self.columns.iter().find(|&&(c, _)| c == column).map(|&(_, ref rule)| rule)
+    }
+
+    /// Check the cells of `row`, which is at index `index`, and return their violations.
+    /// Missing cells are checked as empty cells
+    pub fn check_row(&self, index: usize, row: &Row) -> Vec<Violation> {
+        let mut violations = Vec::new();
+        if row.is_spanned() {
+            return violations;
+        }
+        for &(column, ref rule) in &self.columns {
+            let content = row.get_cell(column).map(|c| c.get_content()).unwrap_or_else(String::new);
+            if let Some(reason) = rule.check(&content) {
+                violations.push(Violation {
+                                    row: index,
+                                    column: column,
+                                    reason: reason,
+                                });
+            }
+        }
+        violations
+    }
+}
+
+/// A cell rejected by a `Schema`
+#[derive(Clone, Debug, PartialEq)]
+pub struct Violation {
+    /// Index of the data row
+    row: usize,
+    /// Index of the column
+    column: usize,
+    /// Why the content is rejected
+    reason: String,
+}
+
+impl Violation {
+    /// Get the index of the data row
+    pub fn get_row(&self) -> usize {
+        self.row
+    }
+
+    /// Get the index of the column
+    pub fn get_column(&self) -> usize {
+        self.column
+    }
+
+    /// Get the reason why the content is rejected
+    pub fn get_reason(&self) -> &str {
+        &self.reason
+    }
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(fmt, "row {}, column {}: {}", self.row, self.column, self.reason)
+    }
+}
+
+/// Build the report table of `violations`
+fn report(violations: &[Violation]) -> Table {
+    let mut table = Table::new();
+    table.set_titles(Row::from(vec!["row", "column", "reason"]));
+    for v in violations {
+        table.add_row(Row::from(vec![v.row.to_string(), v.column.to_string(), v.reason.clone()]));
+    }
+    table
+}
+
+impl<'a> TableSlice<'a> {
+    /// Check the data rows against `schema`, and return the violations in row order.
+    /// Titles and spanned rows are not checked
+    pub fn violations(&self, schema: &Schema) -> Vec<Violation> {
+        self.rows
+            .iter()
+            .enumerate()
+            .flat_map(|(i, row)| schema.check_row(i, row))
+            .collect()
+    }
+
+    /// Check the data rows against `schema`, and return a report table with the row index,
+    /// column index and reason of each violation. See `violations` for details
+    pub fn validate_against(&self, schema: &Schema) -> Table {
+        report(&self.violations(schema))
+    }
+}
+
+impl Table {
+    /// Check the data rows against `schema`, and return the violations in row order.
+    /// See `TableSlice::violations` for details
+    pub fn violations(&self, schema: &Schema) -> Vec<Violation> {
+        self.as_slice().violations(schema)
+    }
+
+    /// Check the data rows against `schema`, and return a report table of the violations.
+    /// See `TableSlice::validate_against` for details
+    pub fn validate_against(&self, schema: &Schema) -> Table {
+        self.as_slice().validate_against(schema)
+    }
+
+    /// Check the data rows against `schema` like `validate_against`, and also color the
+    /// content of the offending cells in red
+    pub fn mark_violations(&mut self, schema: &Schema) -> Table {
+        let violations = self.violations(schema);
+        for v in &violations {
+            if let Some(cell) = self.get_mut((v.row, v.column)) {
+                cell.style(Attr::ForegroundColor(color::RED));
+            }
+        }
+        report(&violations)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check() {
+        let rule = ColumnRule::new().value_type(ValueType::Integer).min(0.0);
+        assert_eq!(rule.check(""), None);
+        assert_eq!(rule.check(" 12 "), None);
+        assert_eq!(rule.check("1.5"), Some("expected an integer".to_string()));
+        assert_eq!(rule.check("-1"), Some("-1 is less than 0".to_string()));
+        let rule = ColumnRule::new().required(true).value_type(ValueType::Boolean);
+        assert_eq!(rule.check(""), Some("missing value".to_string()));
+        assert_eq!(rule.check("true"), None);
+        assert_eq!(rule.check("yes"), Some("expected a boolean".to_string()));
+        let rule = ColumnRule::new().max(1.5);
+        assert_eq!(rule.check("abc"), Some("expected a number".to_string()));
+        assert_eq!(rule.check("1.6"), Some("1.6 is greater than 1.5".to_string()));
+        assert_eq!(ColumnRule::new().value_type(ValueType::Number).check("1e3"), None);
+    }
+
+    #[test]
+    #[cfg(feature = "regex")]
+    fn pattern() {
+        let rule = ColumnRule::new().pattern(Regex::new("^[A-Z]{2}$").unwrap());
+        assert_eq!(rule.check("FR"), None);
+        assert_eq!(rule.check("FRA"), Some("does not match ^[A-Z]{2}$".to_string()));
+    }
+
+    #[test]
+    fn mark_violations() {
+        let mut table = Table::new();
+        table.set_titles(Row::from(vec!["id", "score"]));
+        table.add_row(Row::from(vec!["1", "5"]));
+        table.add_row(Row::from(vec!["a"]));
+        table.add_banner("total", "");
+        let schema = Schema::new()
+            .column(1, ColumnRule::new().required(true))
+            .column(0, ColumnRule::new().value_type(ValueType::Integer));
+        assert_eq!(schema.get_column(1).map(|r| r.required), Some(true));
+        let violations = table.violations(&schema);
+        assert_eq!(violations.len(), 2);
+        assert_eq!(violations[0].to_string(), "row 1, column 0: expected an integer");
+        assert_eq!(violations[1].to_string(), "row 1, column 1: missing value");
+        let report = table.mark_violations(&schema);
+        assert_eq!(report.len(), 2);
+        assert_eq!(report[1][2].get_content(), "missing value");
+        assert_eq!(table[1][0].get_style(), &[Attr::ForegroundColor(color::RED)]);
+        assert!(table[0][0].get_style().is_empty());
+    }
+}